@@ -1,33 +1,44 @@
 use std::collections::VecDeque;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use log::{info, warn, log_enabled};
+use log::{error, info, warn, log_enabled};
 use log::Level;
 use rodio::{OutputStream, Sink};
 use rodio::source::Source;
 
 use crate::apu::apu_clock::CycleParity;
+use crate::apu::blip_buffer::BlipBuffer;
 use crate::apu::mixer::Mixer;
+use crate::apu::sample_rate::SampleRate;
+use crate::apu::speech_chip::SpeechChip;
 use crate::bus::Bus;
-
-const MAX_QUEUE_LENGTH: usize = 2 * Mixer::SAMPLE_RATE as usize;
+use crate::mapper::mapper::Mapper;
+use crate::region::Region;
+use crate::util::wav::WavWriter;
 
 pub struct Apu {
     mixer: Mixer,
+    blip_buffer: BlipBuffer,
+    sample_rate: SampleRate,
+    max_queue_length: usize,
     pulse_queue: Arc<Mutex<VecDeque<f32>>>,
+    // Every output sample is also written here while recording, whether or not audio is disabled.
+    recorder: Option<WavWriter>,
 }
 
 impl Apu {
-    pub fn new(disable_audio: bool) -> Apu {
+    pub fn new(disable_audio: bool, sample_rate: SampleRate, region: Region) -> Apu {
         // TODO: Select a proper capacity value.
-        let pulse_queue = Arc::new(Mutex::new(VecDeque::with_capacity(MAX_QUEUE_LENGTH)));
+        let max_queue_length = 2 * sample_rate.hz() as usize;
+        let pulse_queue = Arc::new(Mutex::new(VecDeque::with_capacity(max_queue_length)));
 
         if !disable_audio {
             let cloned_queue = pulse_queue.clone();
             thread::spawn(move || {
-                let source = AudioSource::new(cloned_queue);
+                let source = AudioSource::new(cloned_queue, sample_rate);
                 let (_stream, stream_handle) = OutputStream::try_default().unwrap();
                 let sink = Sink::try_new(&stream_handle).unwrap();
                 sink.append(source);
@@ -38,18 +49,62 @@ impl Apu {
             });
         }
 
+        info!("Audio sample rate: {sample_rate}");
         Apu {
-            mixer: Mixer::new(),
+            mixer: Mixer::new(sample_rate),
+            // The mixed amplitude is fed in once per APU cycle.
+            blip_buffer: BlipBuffer::new(region.cpu_frequency() / 2, sample_rate.hz()),
+            sample_rate,
+            max_queue_length,
             pulse_queue,
+            recorder: None,
         }
     }
 
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
     pub fn mute(&mut self) {
         self.mixer.pulse_1_force_muted = true;
         self.mixer.pulse_2_force_muted = true;
         self.mixer.triangle_force_muted = true;
         self.mixer.noise_force_muted = true;
         self.mixer.dmc_force_muted = true;
+        self.mixer.expansion_force_muted = true;
+    }
+
+    // Drops any samples that haven't been played yet.
+    pub fn clear_queued_samples(&mut self) {
+        self.pulse_queue.lock().unwrap().clear();
+    }
+
+    pub fn take_queued_samples(&mut self) -> Vec<f32> {
+        self.pulse_queue.lock().unwrap().drain(..).collect()
+    }
+
+    pub fn start_recording(&mut self, path: &Path) -> Result<(), String> {
+        self.recorder = Some(WavWriter::create(path, self.sample_rate.hz())?);
+        info!("Recording audio to {}.", path.display());
+        Ok(())
+    }
+
+    pub fn stop_recording(&mut self) {
+        if let Some(mut recorder) = self.recorder.take() && let Err(err) = recorder.update_header() {
+            error!("Failed to finish the audio recording. {err}");
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    // Called at the end of every frame, so the recording is always a valid WAV file.
+    pub fn flush_recording(&mut self) {
+        if let Some(recorder) = &mut self.recorder && let Err(err) = recorder.update_header() {
+            error!("Stopping audio recording. {err}");
+            self.recorder = None;
+        }
     }
 
     pub fn mute_pulse_1(&mut self) {
@@ -72,7 +127,7 @@ impl Apu {
         self.mixer.dmc_force_muted = true;
     }
 
-    pub fn step(bus: &mut Bus) {
+    pub fn step(bus: &mut Bus, mapper: &dyn Mapper) {
         let clock = &mut bus.master_clock.apu_clock;
         let cycle = clock.cpu_cycle();
         let parity = clock.cycle_parity();
@@ -86,18 +141,25 @@ impl Apu {
                 bus.joypad1.tick();
                 bus.joypad2.tick();
                 bus.apu_regs.tick_put(clock, &mut bus.cpu_pinout, &mut bus.dmc_dma);
-                Self::maybe_enqueue_mixed_sample(bus);
+                Self::mix_sample(bus, mapper);
             }
         }
     }
 
-    fn maybe_enqueue_mixed_sample(bus: &mut Bus) {
-        if bus.apu_clock().raw_apu_cycle().is_multiple_of(20) {
-            let mixed_sample = bus.apu.mixer.mix_filtered(&bus.apu_regs);
+    // Every APU cycle's amplitude goes through the blip buffer, which only occasionally completes an output sample.
+    fn mix_sample(bus: &mut Bus, mapper: &dyn Mapper) {
+        let speech_sample = bus.speech_chip.as_ref().map_or(0.0, SpeechChip::sample);
+        let amplitude = bus.apu.mixer.mix_with_expansion(&bus.apu_regs, mapper.expansion_audio_sample() + speech_sample);
+        if let Some(sample) = bus.apu.blip_buffer.clock(bus.apu_clock().raw_apu_cycle(), amplitude) {
+            let mixed_sample = bus.apu.mixer.filter(sample);
+            if let Some(recorder) = &mut bus.apu.recorder && let Err(err) = recorder.write_sample(mixed_sample) {
+                error!("Stopping audio recording. {err}");
+                bus.apu.recorder = None;
+            }
 
             {
                 let mut queue = bus.apu.pulse_queue.lock().unwrap();
-                if queue.len() < MAX_QUEUE_LENGTH {
+                if queue.len() < bus.apu.max_queue_length {
                     queue.push_back(mixed_sample);
                 } else {
                     warn!("Samples dropped: maximum APU queue length exceeded. Length: {}", queue.len());
@@ -134,14 +196,16 @@ impl Apu {
 #[derive(Clone, Debug)]
 pub struct AudioSource {
     queue: Arc<Mutex<VecDeque<f32>>>,
+    sample_rate: SampleRate,
     previous_value: f32,
 }
 
 impl AudioSource {
     #[inline]
-    pub fn new(queue: Arc<Mutex<VecDeque<f32>>>) -> Self {
+    pub fn new(queue: Arc<Mutex<VecDeque<f32>>>, sample_rate: SampleRate) -> Self {
         AudioSource {
             queue,
+            sample_rate,
             previous_value: 0.0,
         }
     }
@@ -175,7 +239,7 @@ impl Source for AudioSource {
 
     #[inline]
     fn sample_rate(&self) -> u32 {
-        Mixer::SAMPLE_RATE
+        self.sample_rate.hz()
     }
 
     #[inline]
@@ -1,21 +1,27 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::region::Region;
+
+#[derive(Serialize, Deserialize)]
 pub struct ApuClock {
     total_cpu_cycles: u64,
     cpu_cycle: u16,
     parity: CycleParity,
     step_mode: StepMode,
     is_in_frame_irq_window: bool,
+    region: Region,
 }
 
 impl ApuClock {
-    pub fn new() -> Self {
+    pub fn new(region: Region) -> Self {
         Self {
             total_cpu_cycles: 0,
             cpu_cycle: 0,
             parity: CycleParity::Get,
             step_mode: StepMode::FourStep,
             is_in_frame_irq_window: false,
+            region,
         }
     }
 
@@ -28,7 +34,8 @@ impl ApuClock {
     pub fn tick(&mut self) {
         self.total_cpu_cycles += 1;
         self.cpu_cycle += 1;
-        if self.step_mode == StepMode::FourStep && self.apu_cycle() == self.step_mode.frame_length() - 1 {
+        let frame_length = self.step_mode.frame_length(self.region);
+        if self.step_mode == StepMode::FourStep && self.apu_cycle() == frame_length - 1 {
             self.is_in_frame_irq_window = true;
         }
 
@@ -36,10 +43,14 @@ impl ApuClock {
             self.is_in_frame_irq_window = false;
         }
 
-        self.cpu_cycle %= 2 * self.step_mode.frame_length();
+        self.cpu_cycle %= 2 * frame_length;
         self.parity.toggle();
     }
 
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
     pub fn step_mode(&self) -> StepMode {
         self.step_mode
     }
@@ -72,7 +83,7 @@ impl ApuClock {
     }
 }
 
-#[derive(PartialEq, Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum StepMode {
     #[default]
     FourStep,
@@ -80,18 +91,18 @@ pub enum StepMode {
 }
 
 impl StepMode {
-    pub const FOUR_STEP_FRAME_LENGTH: u16 = 14915;
-    pub const FIVE_STEP_FRAME_LENGTH: u16 = 18641;
-
-    pub const fn frame_length(self) -> u16 {
-        match self {
-            StepMode::FourStep => StepMode::FOUR_STEP_FRAME_LENGTH,
-            StepMode::FiveStep => StepMode::FIVE_STEP_FRAME_LENGTH,
+    // Measured in APU cycles.
+    pub const fn frame_length(self, region: Region) -> u16 {
+        match (self, region) {
+            (StepMode::FourStep, Region::Ntsc | Region::Dendy) => 14915,
+            (StepMode::FiveStep, Region::Ntsc | Region::Dendy) => 18641,
+            (StepMode::FourStep, Region::Pal) => 16627,
+            (StepMode::FiveStep, Region::Pal) => 20783,
         }
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum CycleParity {
     Get,
     Put,
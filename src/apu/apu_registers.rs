@@ -1,6 +1,7 @@
 #![allow(clippy::many_single_char_names)]
 
 use log::info;
+use serde::{Deserialize, Serialize};
 use splitbits::{combinebits, splitbits};
 
 use crate::apu::apu_clock::{ApuClock, StepMode};
@@ -12,10 +13,20 @@ use crate::apu::dmc::Dmc;
 use crate::cpu::dmc_dma::DmcDma;
 use crate::memory::read_result::ReadResult;
 use crate::memory::cpu::cpu_pinout::CpuPinout;
+use crate::region::Region;
 use crate::util::circular_buffer::CircularBuffer;
 
 const STORED_SAMPLE_COUNT: u32 = 1000;
 
+// The APU cycles at which the frame counter clocks the envelopes, length counters, and sweeps.
+#[rustfmt::skip]
+const NTSC_FRAME_COUNTER_STEPS: FrameCounterSteps =
+    FrameCounterSteps { first: 3728, second: 7456, third: 11185, fourth: 14914, fifth: 18640 };
+#[rustfmt::skip]
+const PAL_FRAME_COUNTER_STEPS: FrameCounterSteps =
+    FrameCounterSteps { first: 4156, second: 8313, third: 12469, fourth: 16626, fifth: 20782 };
+
+#[derive(Serialize, Deserialize)]
 pub struct ApuRegisters {
     pub pulse_1: PulseChannel<{NegateBehavior::OnesComplement}>,
     pub pulse_2: PulseChannel<{NegateBehavior::TwosComplement}>,
@@ -44,13 +55,13 @@ pub struct ApuRegisters {
 }
 
 impl ApuRegisters {
-    pub fn new() -> ApuRegisters {
+    pub fn new(region: Region) -> ApuRegisters {
         ApuRegisters {
             pulse_1: PulseChannel::default(),
             pulse_2: PulseChannel::default(),
             triangle: TriangleChannel::default(),
             noise: NoiseChannel::default(),
-            dmc: Dmc::default(),
+            dmc: Dmc::new(region),
 
             pulse1_volumes: CircularBuffer::default_filled(STORED_SAMPLE_COUNT),
             pulse2_volumes: CircularBuffer::default_filled(STORED_SAMPLE_COUNT),
@@ -207,38 +218,37 @@ impl ApuRegisters {
     }
 
     fn maybe_decrement_counters(&mut self, clock: &ApuClock) {
-        const FIRST_STEP  : u16 = 3728;
-        const SECOND_STEP : u16 = 7456;
-        const THIRD_STEP  : u16 = 11185;
-        const FOURTH_STEP : u16 = 14914;
-        const FIFTH_STEP  : u16 = 18640;
+        let FrameCounterSteps { first, second, third, fourth, fifth } = match clock.region() {
+            Region::Ntsc | Region::Dendy => NTSC_FRAME_COUNTER_STEPS,
+            Region::Pal => PAL_FRAME_COUNTER_STEPS,
+        };
 
         match clock.apu_cycle() {
-            FIRST_STEP => {
+            cycle if cycle == first => {
                 self.tick_envelopes();
                 self.triangle.decrement_linear_counter();
                 self.counter_suppression_cycles = 2;
             }
-            SECOND_STEP => {
+            cycle if cycle == second => {
                 self.tick_envelopes();
                 self.triangle.decrement_linear_counter();
                 self.decrement_length_counters(clock);
                 self.tick_sweeps();
                 self.counter_suppression_cycles = 2;
             }
-            THIRD_STEP => {
+            cycle if cycle == third => {
                 self.tick_envelopes();
                 self.triangle.decrement_linear_counter();
                 self.counter_suppression_cycles = 2;
             }
-            FOURTH_STEP if clock.step_mode() == StepMode::FourStep => {
+            cycle if cycle == fourth && clock.step_mode() == StepMode::FourStep => {
                 self.tick_envelopes();
                 self.triangle.decrement_linear_counter();
                 self.decrement_length_counters(clock);
                 self.tick_sweeps();
                 self.counter_suppression_cycles = 2;
             }
-            FIFTH_STEP if clock.step_mode() == StepMode::FiveStep => {
+            cycle if cycle == fifth && clock.step_mode() == StepMode::FiveStep => {
                 self.tick_envelopes();
                 self.triangle.decrement_linear_counter();
                 self.decrement_length_counters(clock);
@@ -294,7 +304,15 @@ impl ApuRegisters {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+struct FrameCounterSteps {
+    first: u16,
+    second: u16,
+    third: u16,
+    fourth: u16,
+    fifth: u16,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum ClockResetStatus {
     Inactive,
     Pending,
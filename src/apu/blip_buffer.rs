@@ -0,0 +1,89 @@
+use std::f64::consts::PI;
+
+// How many output samples each amplitude change is spread over. Must be a power of two.
+const KERNEL_WIDTH: usize = 32;
+const HALF_KERNEL_WIDTH: f64 = (KERNEL_WIDTH / 2) as f64;
+// How many sub-sample positions an amplitude change can be placed at.
+const PHASE_COUNT: usize = 64;
+// Just below the Nyquist frequency of the output, as a fraction of the output sample rate.
+const CUTOFF: f64 = 0.45;
+
+// Band-limited synthesis, in the style of Blargg's Blip_Buffer. The input is an amplitude per
+// clock, which is much faster than the output sample rate. Rather than picking out every Nth input
+// amplitude (which aliases any frequencies above the output's Nyquist frequency back down into the
+// audible range), every change in amplitude is added to the output as a band-limited step.
+pub struct BlipBuffer {
+    clock_rate: u64,
+    sample_rate: u64,
+    amplitude: f32,
+
+    // The band-limited steps, stored as their derivatives. Integrated as they are output.
+    deltas: [f32; KERNEL_WIDTH],
+    next_delta_index: usize,
+    integrator: f32,
+
+    // The derivative of a band-limited step, for each phase.
+    kernels: Vec<[f32; KERNEL_WIDTH]>,
+}
+
+impl BlipBuffer {
+    pub fn new(clock_rate: u32, sample_rate: u32) -> Self {
+        Self {
+            clock_rate: clock_rate.into(),
+            sample_rate: sample_rate.into(),
+            amplitude: 0.0,
+
+            deltas: [0.0; KERNEL_WIDTH],
+            next_delta_index: 0,
+            integrator: 0.0,
+
+            kernels: (0..PHASE_COUNT).map(kernel).collect(),
+        }
+    }
+
+    // Called every input clock with the amplitude for that clock. Returns an output sample if one
+    // was completed. Output lags input by half the kernel width.
+    // Output samples are timed by the clock count alone, so they always land on the same clocks
+    // regardless of when the buffer was created (such as after loading a save state).
+    pub fn clock(&mut self, clock: u64, amplitude: f32) -> Option<f32> {
+        // How far this clock is between the next output sample and the one after it, scaled by the clock rate.
+        let offset = clock * self.sample_rate % self.clock_rate;
+        let delta = amplitude - self.amplitude;
+        if delta != 0.0 {
+            self.amplitude = amplitude;
+            let phase = (offset * PHASE_COUNT as u64 / self.clock_rate) as usize;
+            for (i, &tap) in self.kernels[phase].iter().enumerate() {
+                self.deltas[(self.next_delta_index + i) & (KERNEL_WIDTH - 1)] += delta * tap;
+            }
+        }
+
+        if offset + self.sample_rate < self.clock_rate {
+            return None;
+        }
+
+        self.integrator += self.deltas[self.next_delta_index];
+        self.deltas[self.next_delta_index] = 0.0;
+        self.next_delta_index = (self.next_delta_index + 1) & (KERNEL_WIDTH - 1);
+        Some(self.integrator)
+    }
+}
+
+// A Blackman-windowed sinc, centered half a kernel width (plus the phase's fraction of a sample)
+// after the first tap. Normalized so that each step reaches exactly the new amplitude.
+fn kernel(phase: usize) -> [f32; KERNEL_WIDTH] {
+    let center = HALF_KERNEL_WIDTH + phase as f64 / PHASE_COUNT as f64;
+    let mut taps = [0.0; KERNEL_WIDTH];
+    for (i, tap) in taps.iter_mut().enumerate() {
+        let x = i as f64 - center;
+        let sinc = if x == 0.0 { 1.0 } else { (2.0 * PI * CUTOFF * x).sin() / (2.0 * PI * CUTOFF * x) };
+        let window = if x.abs() < HALF_KERNEL_WIDTH {
+            0.42 + 0.5 * (PI * x / HALF_KERNEL_WIDTH).cos() + 0.08 * (2.0 * PI * x / HALF_KERNEL_WIDTH).cos()
+        } else {
+            0.0
+        };
+        *tap = 2.0 * CUTOFF * sinc * window;
+    }
+
+    let total: f64 = taps.iter().sum();
+    taps.map(|tap| (tap / total) as f32)
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use splitbits::{combinebits, splitbits, splitbits_named_ux};
 use ux::u7;
 
@@ -5,10 +6,14 @@ use crate::apu::apu_clock::CycleParity;
 use crate::cpu::dmc_dma::DmcDma;
 use crate::memory::cpu::cpu_address::CpuAddress;
 use crate::memory::cpu::cpu_pinout::CpuPinout;
+use crate::region::Region;
 
 const NTSC_PERIODS: [u16; 16] =
     [428, 380, 340, 320, 286, 254, 226, 214, 190, 160, 142, 128, 106,  84,  72,  54];
+const PAL_PERIODS: [u16; 16] =
+    [398, 354, 316, 298, 276, 236, 210, 198, 176, 148, 132, 118,  98,  78,  66,  50];
 
+#[derive(Serialize, Deserialize)]
 pub struct Dmc {
     // TODO: The wiki claims there is an irq_status flag independent of frame_irq_asserted in CpuPinout.
     // But there seem to be no tests to verify the behavior of these two flags in relationship to each other,
@@ -17,6 +22,7 @@ pub struct Dmc {
 
     should_loop: bool,
 
+    periods: [u16; 16],
     period: u16,
     cycles_remaining: u16,
 
@@ -33,7 +39,7 @@ impl Dmc {
         let fields = splitbits!(cpu_pinout.data_bus, "il..pppp");
         self.irq_enabled = fields.i;
         self.should_loop = fields.l;
-        self.period = NTSC_PERIODS[fields.p as usize] - 1;
+        self.period = self.periods[fields.p as usize] - 1;
         if !self.irq_enabled {
             cpu_pinout.acknowledge_dmc_irq();
         }
@@ -67,7 +73,7 @@ impl Dmc {
     }
 
     pub(super) fn tick(&mut self, dmc_dma: &mut DmcDma) {
-        // If we don't early return here, then we must be on a PUT cycle since all NTSC_PERIODs are even.
+        // If we don't early return here, then we must be on a PUT cycle since all periods are even.
         if self.cycles_remaining >= 1 {
             self.cycles_remaining -= 1;
             return;
@@ -123,13 +129,19 @@ impl Dmc {
     }
 }
 
-impl Default for Dmc {
-    fn default() -> Self {
+impl Dmc {
+    pub fn new(region: Region) -> Self {
+        let periods = match region {
+            Region::Ntsc | Region::Dendy => NTSC_PERIODS,
+            Region::Pal => PAL_PERIODS,
+        };
+
         Dmc {
             irq_enabled: false,
             should_loop: false,
-            period: NTSC_PERIODS[0] - 1,
-            cycles_remaining: NTSC_PERIODS[0] - 1,
+            periods,
+            period: periods[0] - 1,
+            cycles_remaining: periods[0] - 1,
             sample_start_address: CpuAddress::new(0xC000),
             sample_address: CpuAddress::new(0xC000),
             sample_buffer: None,
@@ -138,10 +150,12 @@ impl Default for Dmc {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 struct OutputUnit {
     // Values from 0 to 8.
     bits_remaining: u8,
     sample_shifter: u8,
+    #[serde(with = "crate::util::serde_util::ux")]
     volume: u7,
     silenced: bool,
 }
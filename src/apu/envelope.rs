@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use ux::u4;
 
 //                                    Loop flag
@@ -10,7 +11,7 @@ use ux::u4;
 //                            |                  | Select --> Envelope output
 //                            |                  |
 //         Envelope parameter +----------------> |
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Envelope {
     start: bool,
     divider: Divider,
@@ -62,9 +63,11 @@ impl Envelope {
 const ZERO: u4 = u4::new(0);
 const ONE: u4 = u4::new(0);
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Divider {
+    #[serde(with = "crate::util::serde_util::ux")]
     count: u4,
+    #[serde(with = "crate::util::serde_util::ux")]
     reload_value: u4,
 }
 
@@ -93,8 +96,9 @@ impl Divider {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct DecayLevelCounter {
+    #[serde(with = "crate::util::serde_util::ux")]
     volume: u4,
     should_loop: bool,
 }
@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use ux::u3;
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct FrequencyTimer {
     period: u16,
     index: u16,
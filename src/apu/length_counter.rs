@@ -1,5 +1,6 @@
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
 use ux::u5;
 
 const TABLE: [u8; 0x20] = [
@@ -7,7 +8,7 @@ const TABLE: [u8; 0x20] = [
      12,  16,  24,  18,  48,  20,  96,  22, 192,  24,  72,  26,  16,  28,  32,  30,
 ];
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub struct LengthCounter {
     count: u8,
     pending_count: Option<u8>,
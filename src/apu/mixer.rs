@@ -1,4 +1,8 @@
 use crate::apu::apu_registers::ApuRegisters;
+use crate::apu::sample_rate::SampleRate;
+
+// The filter coefficients below were tuned for this rate, and are adjusted for other rates.
+const REFERENCE_SAMPLE_RATE: f32 = 44100.0;
 
 pub struct Mixer {
     pub pulse_1_force_muted: bool,
@@ -6,6 +10,7 @@ pub struct Mixer {
     pub triangle_force_muted: bool,
     pub noise_force_muted: bool,
     pub dmc_force_muted: bool,
+    pub expansion_force_muted: bool,
 
     high90_filter: HighPassFilter,
     high440_filter: HighPassFilter,
@@ -13,24 +18,34 @@ pub struct Mixer {
 }
 
 impl Mixer {
-    pub const SAMPLE_RATE: u32 = 44100;
-
-    pub fn new() -> Self {
+    pub fn new(sample_rate: SampleRate) -> Self {
+        let rate_ratio = REFERENCE_SAMPLE_RATE / sample_rate.hz() as f32;
         Self {
             pulse_1_force_muted: false,
             pulse_2_force_muted: false,
             triangle_force_muted: false,
             noise_force_muted: false,
             dmc_force_muted: false,
+            expansion_force_muted: false,
 
-            high90_filter: HighPassFilter::new(0.996),
-            high440_filter: HighPassFilter::new(0.983),
-            low14000_filter: LowPassFilter::new(0.666),
+            high90_filter: HighPassFilter::new(0.996f32.powf(rate_ratio)),
+            high440_filter: HighPassFilter::new(0.983f32.powf(rate_ratio)),
+            low14000_filter: LowPassFilter::new(1.0 - 0.334f32.powf(rate_ratio)),
         }
     }
 
-    pub fn mix_filtered(&mut self, regs: &ApuRegisters) -> f32 {
+    // Expansion audio is summed after the APU's non-linear mixing, as it is on the cartridge.
+    pub fn mix_with_expansion(&self, regs: &ApuRegisters, expansion_sample: f32) -> f32 {
         let mut sample = self.mix(regs);
+        if !self.expansion_force_muted {
+            sample += expansion_sample;
+        }
+
+        sample
+    }
+
+    // Applied to each output sample.
+    pub fn filter(&mut self, mut sample: f32) -> f32 {
         sample = self.high90_filter.transform(sample);
         sample = self.high440_filter.transform(sample);
         sample = self.low14000_filter.transform(sample);
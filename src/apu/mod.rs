@@ -1,11 +1,14 @@
 pub mod apu;
 pub mod apu_clock;
 pub mod apu_registers;
+pub mod blip_buffer;
 pub mod envelope;
 pub mod sweep;
 pub mod length_counter;
 pub mod mixer;
+pub mod sample_rate;
 pub mod frequency_timer;
+pub mod speech_chip;
 
 // Write-only registers.
 pub mod pulse_channel;
@@ -1,17 +1,22 @@
+use serde::{Deserialize, Serialize};
 use splitbits::{splitbits, splitbits_named_ux};
 use ux::{u4, u15};
 
 use crate::apu::envelope::Envelope;
 use crate::apu::length_counter::LengthCounter;
 use crate::apu::frequency_timer::FrequencyTimer;
+use crate::region::Region;
 
 const NTSC_PERIODS: [u16; 16] =
     [4, 8, 16, 32, 64, 96, 128, 160, 202, 254, 380, 508, 762, 1016, 2034, 4068];
+const PAL_PERIODS: [u16; 16] =
+    [4, 8, 14, 30, 60, 88, 118, 148, 188, 236, 354, 472, 708,  944, 1890, 3778];
 
 //    Timer --> Shift Register   Length Counter
 //                   |                |
 //                   v                v
 // Envelope -------> Gate ----------> Gate --> (to mixer)
+#[derive(Serialize, Deserialize)]
 pub struct NoiseChannel {
     pub(super) length_counter: LengthCounter,
 
@@ -45,10 +50,14 @@ impl NoiseChannel {
     }
 
     // Write 0x400E
-    pub fn set_loop_and_period(&mut self, value: u8) {
+    pub fn set_loop_and_period(&mut self, value: u8, region: Region) {
         let fields = splitbits!(value, "m... pppp");
         self.bit_6_mode = fields.m;
-        let period = NTSC_PERIODS[fields.p as usize];
+        let periods = match region {
+            Region::Ntsc | Region::Dendy => NTSC_PERIODS,
+            Region::Pal => PAL_PERIODS,
+        };
+        let period = periods[fields.p as usize];
         self.frequency_timer.set_period_and_reset_index(period);
     }
 
@@ -95,7 +104,8 @@ impl NoiseChannel {
     }
 }
 
-pub struct LinearFeedbackShiftRegister(u15);
+#[derive(Serialize, Deserialize)]
+pub struct LinearFeedbackShiftRegister(#[serde(with = "crate::util::serde_util::ux")] u15);
 
 impl LinearFeedbackShiftRegister {
     pub fn new() -> Self {
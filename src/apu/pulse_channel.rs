@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use splitbits::{splitbits_ux, splitbits_named_ux};
 use ux::{u2, u4};
 
@@ -14,7 +15,7 @@ use crate::apu::sweep::{NegateBehavior, Sweep};
 //                    |            |             |
 //                    v            v             v
 // Envelope -------> Gate -----> Gate -------> Gate ---> (to mixer)
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct PulseChannel<const N: NegateBehavior> {
     pub(super) length_counter: LengthCounter,
 
@@ -102,7 +103,7 @@ impl <const N: NegateBehavior> PulseChannel<N> {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Sequencer {
     index: u32,
     duty: Duty,
@@ -127,7 +128,7 @@ impl Sequencer {
     }
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
 pub enum Duty {
     #[default]
     Low     = 0b0100_0000,
@@ -0,0 +1,40 @@
+use std::fmt;
+use std::str::FromStr;
+
+// The supported rates for the audio output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SampleRate {
+    #[default]
+    Hz44100,
+    Hz48000,
+    Hz96000,
+}
+
+impl SampleRate {
+    pub const fn hz(self) -> u32 {
+        match self {
+            SampleRate::Hz44100 => 44100,
+            SampleRate::Hz48000 => 48000,
+            SampleRate::Hz96000 => 96000,
+        }
+    }
+}
+
+impl FromStr for SampleRate {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().as_str() {
+            "44100" | "44.1k" => Ok(SampleRate::Hz44100),
+            "48000" | "48k" => Ok(SampleRate::Hz48000),
+            "96000" | "96k" => Ok(SampleRate::Hz96000),
+            _ => Err(format!("Invalid sample rate: {value}. Must be 44100, 48000, or 96000.")),
+        }
+    }
+}
+
+impl fmt::Display for SampleRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}Hz", self.hz())
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::region::Region;
+use crate::util::wav::WavAudio;
+
+// About as loud as a single APU pulse channel at full volume.
+const VOLUME: f32 = 0.12;
+
+// A NEC uPD7755/uPD7756 ADPCM speech chip, as used on some Jaleco and Bandai boards.
+// The voice clips are in the chip's internal mask ROM, which isn't part of any ROM image, so they
+// must be supplied separately as a directory of WAV files named by clip number (0.wav, 1.wav, etc.).
+// None of the supported boards connect the chip's /BUSY pin to anything the CPU can read, so it
+// isn't emulated. Games just wait long enough for each clip to finish.
+pub struct SpeechChip {
+    clips: BTreeMap<u8, WavAudio>,
+    cpu_frequency: u32,
+    state: SpeechChipState,
+}
+
+impl SpeechChip {
+    pub fn new(region: Region) -> Self {
+        Self {
+            clips: BTreeMap::new(),
+            cpu_frequency: region.cpu_frequency(),
+            state: SpeechChipState::default(),
+        }
+    }
+
+    pub fn load_clips(&mut self, directory: &Path) -> Result<(), String> {
+        let entries = fs::read_dir(directory)
+            .map_err(|err| format!("Failed to read speech clip directory {}. {err}", directory.display()))?;
+        for entry in entries {
+            let path = entry.map_err(|err| format!("Failed to read speech clip directory entry. {err}"))?.path();
+            let is_wav = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("wav"));
+            let clip_number = path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u8>().ok());
+            let (true, Some(clip_number)) = (is_wav, clip_number) else {
+                continue;
+            };
+
+            let raw = fs::read(&path).map_err(|err| format!("Failed to read speech clip {}. {err}", path.display()))?;
+            self.add_clip(clip_number, &raw).map_err(|err| format!("{err} Path: {}", path.display()))?;
+        }
+
+        info!("Loaded {} speech clips from {}.", self.clips.len(), directory.display());
+        Ok(())
+    }
+
+    // The raw contents of a WAV file.
+    pub fn add_clip(&mut self, clip_number: u8, raw: &[u8]) -> Result<(), String> {
+        let clip = WavAudio::parse(raw).map_err(|err| format!("Failed to load speech clip {clip_number}. {err}"))?;
+        self.clips.insert(clip_number, clip);
+        Ok(())
+    }
+
+    // Called every CPU cycle.
+    pub fn step(&mut self) {
+        if let Some((clip_number, cycle)) = self.state.playing {
+            let finished = self.sample_index(clip_number, cycle + 1) >= self.clips[&clip_number].samples.len();
+            self.state.playing = if finished { None } else { Some((clip_number, cycle + 1)) };
+        }
+    }
+
+    // Drives the /RESET and /ST pins (true means asserted). Asserting /ST starts playing the
+    // selected clip, unless the chip is being held in reset, which stops any playback.
+    pub fn set_pins(&mut self, clip_number: u8, reset: bool, start: bool) {
+        let start_edge = start && !self.state.start_asserted;
+        self.state.start_asserted = start;
+        if reset {
+            self.state.playing = None;
+        } else if start_edge {
+            if self.clips.get(&clip_number).is_some_and(|clip| !clip.samples.is_empty()) {
+                self.state.playing = Some((clip_number, 0));
+            } else {
+                warn!("Speech clip {clip_number} wasn't supplied (see --speechclips), so it won't be played.");
+                self.state.playing = None;
+            }
+        }
+    }
+
+    // On the same scale as the APU's mixed output.
+    pub fn sample(&self) -> f32 {
+        let Some((clip_number, cycle)) = self.state.playing else {
+            return 0.0;
+        };
+
+        VOLUME * self.clips[&clip_number].samples[self.sample_index(clip_number, cycle)]
+    }
+
+    pub fn state(&self) -> SpeechChipState {
+        self.state.clone()
+    }
+
+    pub fn check_state(&self, state: &SpeechChipState) -> Result<(), String> {
+        if let Some((clip_number, _)) = state.playing && !self.clips.contains_key(&clip_number) {
+            return Err(format!("Save state is playing speech clip {clip_number}, which wasn't supplied."));
+        }
+
+        Ok(())
+    }
+
+    // The state must have passed check_state().
+    pub fn load_state(&mut self, state: SpeechChipState) {
+        self.state = state;
+    }
+
+    // Clips are resampled from their own sample rate to the CPU clock rate.
+    fn sample_index(&self, clip_number: u8, cycle: u32) -> usize {
+        let sample_rate = self.clips[&clip_number].sample_rate;
+        (u64::from(cycle) * u64::from(sample_rate) / u64::from(self.cpu_frequency)) as usize
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SpeechChipState {
+    start_asserted: bool,
+    // The clip being played, and how many CPU cycles it has been playing for.
+    playing: Option<(u8, u32)>,
+}
@@ -1,14 +1,16 @@
 use std::marker::ConstParamTy;
 
+use serde::{Deserialize, Serialize};
 use ux::{u3, u11};
 
 use crate::apu::frequency_timer::FrequencyTimer;
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Sweep<const N: NegateBehavior> {
     enabled: bool,
     divider: Divider,
     negate: bool,
+    #[serde(with = "crate::util::serde_util::ux")]
     shift_count: u3,
     frequency_timer: FrequencyTimer,
 }
@@ -72,9 +74,11 @@ impl <const N: NegateBehavior> Sweep<N> {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Divider {
+    #[serde(with = "crate::util::serde_util::ux")]
     period: u3,
+    #[serde(with = "crate::util::serde_util::ux")]
     index: u3,
     should_reload: bool,
 }
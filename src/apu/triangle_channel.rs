@@ -1,5 +1,6 @@
 use splitbits::splitbits_named_ux;
 
+use serde::{Deserialize, Serialize};
 use ux::u7;
 
 use crate::apu::frequency_timer::FrequencyTimer;
@@ -10,12 +11,14 @@ const VOLUME_SEQUENCE: [u8; 0x20] = [
      0,  1,  2,  3,  4,  5,  6,  7,  8,  9, 10, 11, 12, 13, 14, 15,
 ];
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct TriangleChannel {
     enabled: bool,
 
     counter_control: bool,
+    #[serde(with = "crate::util::serde_util::ux")]
     linear_counter_reload_value: u7,
+    #[serde(with = "crate::util::serde_util::ux")]
     linear_counter: u7,
     frequency_timer: FrequencyTimer,
     pub(super) length_counter: LengthCounter,
@@ -1,6 +1,7 @@
 use crate::apu::apu::Apu;
 use crate::apu::apu_clock::ApuClock;
 use crate::apu::apu_registers::ApuRegisters;
+use crate::apu::speech_chip::SpeechChip;
 use crate::controller::joypad::Joypad;
 use crate::cpu::cpu::Cpu;
 use crate::cpu::dmc_dma::DmcDma;
@@ -13,11 +14,13 @@ use crate::memory::register_ids::bank::{ChrBankRegisterId, PrgBankRegisterId, Me
 use crate::memory::bank::bank_number::{ReadStatus, WriteStatus};
 use crate::memory::cpu::cpu_address::{CpuAddress, FriendlyCpuAddress};
 use crate::memory::regions::cpu_internal_ram::CpuInternalRam;
+use crate::memory::regions::disk::Disk;
 use crate::memory::cpu::cpu_pinout::CpuPinout;
 use crate::memory::cpu::prg_memory::PrgMemory;
+use crate::memory::raw_memory::{RawData, SaveRam};
 use crate::memory::ppu::chr_memory::{ChrMemory, PpuPeek};
-use crate::memory::regions::palette_ram::PaletteRam;
-use crate::memory::regions::ciram::{Ciram, CiramSide};
+use crate::memory::regions::palette_ram::{PaletteRam, PALETTE_RAM_SIZE};
+use crate::memory::regions::ciram::{Ciram, CiramSide, CIRAM_SIZE};
 use crate::memory::ppu::ppu_address::{PpuAddress, PpuAddressSection};
 use crate::memory::ppu::ppu_pinout::PpuPinout;
 use crate::memory::read_result::ReadResult;
@@ -31,6 +34,9 @@ use crate::ppu::ppu::Ppu;
 use crate::ppu::register::ppu_registers::{PpuRegisters, WriteToggle};
 use crate::ppu::sprite::oam::Oam;
 use crate::util::unit::KIBIBYTE;
+use crate::nsf_player::NsfPlayer;
+use crate::power_on::PowerOnValues;
+use crate::vs_system::VsSystem;
 
 pub const NMI_VECTOR_LOW: CpuAddress     = CpuAddress::new(0xFFFA);
 pub const NMI_VECTOR_HIGH: CpuAddress    = CpuAddress::new(0xFFFB);
@@ -64,6 +70,10 @@ pub struct Bus {
     pub prg_memory: PrgMemory,
     pub chr_memory: ChrMemory,
     pub mapper_custom_pages: Vec<SmallPage>,
+    // Option ROMs, sound sample ROMs, etc. Only a few boards have this.
+    pub miscellaneous_rom: RawData,
+    // RAM inside the mapper chip itself. Only a few mappers have this.
+    pub mapper_internal_ram: SaveRam,
 
     // Pinouts
     pub cpu_pinout: CpuPinout,
@@ -74,6 +84,13 @@ pub struct Bus {
     // Miscellaneous
     pub name_table_mirrorings: &'static [NameTableMirroring], // TODO: Move into ChrMemory.
     pub dip_switch: u8,
+    pub vs_system: Option<VsSystem>,
+    // The Famicom Disk System disk, if one was loaded instead of a cartridge.
+    pub disk: Option<Disk>,
+    // The NSF music player, if an NSF file was loaded instead of a cartridge.
+    pub nsf_player: Option<NsfPlayer>,
+    // The speech chip that plays voice clips, for the few boards that have one.
+    pub speech_chip: Option<SpeechChip>,
 
     pub system_palette: SystemPalette,
 }
@@ -87,10 +104,15 @@ impl Bus {
         apu: Apu,
         prg_memory: PrgMemory,
         chr_memory: ChrMemory,
+        miscellaneous_rom: RawData,
         name_table_mirrorings: &'static [NameTableMirroring],
         dip_switch: u8,
+        vs_system: Option<VsSystem>,
+        disk: Option<Disk>,
+        nsf_player: Option<NsfPlayer>,
         system_palette: SystemPalette,
     ) -> Self {
+        let region = master_clock.region();
         Self {
             cpu,
             ppu,
@@ -103,7 +125,7 @@ impl Bus {
             joypad2: Joypad::new(),
 
             ppu_regs: PpuRegisters::new(),
-            apu_regs: ApuRegisters::new(),
+            apu_regs: ApuRegisters::new(region),
 
             cpu_internal_ram: CpuInternalRam::new(),
             ciram: Ciram::new(),
@@ -112,6 +134,8 @@ impl Bus {
             prg_memory,
             chr_memory,
             mapper_custom_pages: Vec::new(),
+            miscellaneous_rom,
+            mapper_internal_ram: SaveRam::empty(),
 
             cpu_pinout: CpuPinout::new(),
             ppu_pinout: PpuPinout::new(),
@@ -120,6 +144,10 @@ impl Bus {
 
             name_table_mirrorings,
             dip_switch,
+            vs_system,
+            disk,
+            nsf_player,
+            speech_chip: None,
 
             system_palette,
         }
@@ -213,6 +241,24 @@ impl Bus {
         self.chr_memory.set_rom_outer_bank_number(0);
     }
 
+    // Fills the memory and bank registers whose contents are undefined at power-on. Save RAM is left alone.
+    pub fn apply_power_on_state(&mut self, values: &mut PowerOnValues) {
+        self.cpu_internal_ram.fill(&values.values(2 * KIBIBYTE as usize));
+        self.oam.fill(&values.values(256).try_into().unwrap());
+        self.palette_ram.fill(&values.values(PALETTE_RAM_SIZE).try_into().unwrap());
+        self.ciram.fill(&values.values(CIRAM_SIZE).try_into().unwrap());
+        for (index, value) in (0..).zip(values.values(self.prg_memory.work_ram_size() as usize)) {
+            self.prg_memory.write_raw_work_ram(index, value);
+        }
+
+        for (index, value) in (0..).zip(values.values(self.chr_memory.ram_size() as usize)) {
+            self.chr_memory.write_raw_ram(index, value);
+        }
+
+        self.prg_memory.fill_bank_registers(&values.values(self.prg_memory.bank_registers().registers().len()));
+        self.chr_memory.fill_bank_registers(&values.values(self.chr_memory.bank_registers().registers().len()));
+    }
+
     pub fn set_reads_enabled(&mut self, id: ReadStatusRegisterId, enabled: bool) {
         let status = if enabled { ReadStatus::Enabled } else { ReadStatus::Disabled };
         self.prg_memory.set_read_status(id, status);
@@ -265,7 +311,7 @@ impl Bus {
         use FriendlyCpuAddress as Addr;
         let normal_peek_value = match addr.to_friendly() {
             Addr::CpuInternalRam(index) => self.cpu_internal_ram().peek(index),
-            Addr::PpuStatus             => self.ppu_regs.peek_status(),
+            Addr::PpuStatus             => self.vs_ppu_status(self.ppu_regs.peek_status()),
             Addr::OamData               => self.ppu_regs.peek_oam_data(&self.oam, &self.ppu_clock()),
             Addr::PpuData => {
                 let old_value = mapper.ppu_peek(self, self.ppu_regs.current_address).value();
@@ -277,8 +323,9 @@ impl Bus {
             }
             Addr::MapperRegisters => {
                 match *addr {
-                    0x4020..=0x5FFF => mapper.peek_register(self, addr),
-                    0x6000..=0xFFFF => self.prg_memory.peek(addr),
+                    0x4020..=0x5FFF => self.vs_protection(addr).unwrap_or_else(|| mapper.peek_register(self, addr)),
+                    0x6000..=0xFFF9 => self.prg_memory.peek(addr),
+                    0xFFFA..=0xFFFF => self.peek_prg_or_interrupt_vector(mapper, addr),
                     _ => unreachable!(),
                 }
             }
@@ -297,8 +344,8 @@ impl Bus {
                     should_apu_read_dominate_normal_read = true;
                     self.apu_regs.peek_status(&self.cpu_pinout, &self.dmc_dma)
                 }
-                Addr::Controller1AndStrobe       => self.joypad1.peek_status(),
-                Addr::Controller2AndFrameCounter => self.joypad2.peek_status(),
+                Addr::Controller1AndStrobe       => self.peek_controller1(),
+                Addr::Controller2AndFrameCounter => self.peek_controller2(),
 
                 // APU channel registers and OAM DMA are write-only. CPU Test Mode is not yet supported.
                 _ if addr.is_in_apu_register_range() => ReadResult::OPEN_BUS,
@@ -322,7 +369,10 @@ impl Bus {
         use FriendlyCpuAddress as Addr;
         let normal_read_value = match addr.to_friendly() {
             Addr::CpuInternalRam(index) => self.cpu_internal_ram().peek(index),
-            Addr::PpuStatus             => self.ppu_regs.read_status(),
+            Addr::PpuStatus             => {
+                let status = self.ppu_regs.read_status();
+                self.vs_ppu_status(status)
+            }
             Addr::OamData               => self.ppu_regs.read_oam_data(&mut self.oam, self.master_clock.ppu_clock()),
             Addr::PpuData => {
                 self.set_ppu_address_bus(mapper, self.ppu_regs.current_address);
@@ -344,8 +394,16 @@ impl Bus {
             Addr::MapperRegisters => {
                 match *addr {
                     0x0000..=0x401F => unreachable!(),
-                    0x4020..=0x5FFF => mapper.peek_register(self, addr),
-                    0x6000..=0xFFFF => self.prg_memory.peek(addr),
+                    0x4020..=0x5FFF => {
+                        let value = self.vs_protection(addr).unwrap_or_else(|| mapper.peek_register(self, addr));
+                        if let Some(vs_system) = &mut self.vs_system {
+                            vs_system.on_cpu_read(addr);
+                        }
+
+                        value
+                    }
+                    0x6000..=0xFFF9 => self.prg_memory.peek(addr),
+                    0xFFFA..=0xFFFF => self.peek_prg_or_interrupt_vector(mapper, addr),
                 }
             }
 
@@ -366,8 +424,16 @@ impl Bus {
                     should_apu_read_update_data_bus = address_bus_type != AddressBusType::Cpu;
                     self.apu_regs.read_status(self.master_clock.apu_clock(), &self.cpu_pinout, &self.dmc_dma)
                 }
-                Addr::Controller1AndStrobe       => self.joypad1.read_status(),
-                Addr::Controller2AndFrameCounter => self.joypad2.read_status(),
+                Addr::Controller1AndStrobe => {
+                    let status = self.peek_controller1();
+                    self.joypad1.read_status();
+                    status
+                }
+                Addr::Controller2AndFrameCounter => {
+                    let status = self.peek_controller2();
+                    self.joypad2.read_status();
+                    status
+                }
                 // Most APU registers and OAM DMA are write-only. CPU Test Mode is not yet supported.
                 _ if addr.is_in_apu_register_range() => ReadResult::OPEN_BUS,
                 _ => unreachable!(),
@@ -399,15 +465,24 @@ impl Bus {
     pub fn cpu_write(&mut self, mapper: &mut dyn Mapper, address_bus_type: AddressBusType) {
         let addr = self.cpu_address_bus(address_bus_type);
 
+        let mut friendly_addr = addr.to_friendly();
+        if self.vs_system.as_ref().is_some_and(VsSystem::ppu_control_and_mask_swapped) {
+            friendly_addr = match friendly_addr {
+                FriendlyCpuAddress::PpuControl => FriendlyCpuAddress::PpuMask,
+                FriendlyCpuAddress::PpuMask => FriendlyCpuAddress::PpuControl,
+                other => other,
+            };
+        }
+
         use FriendlyCpuAddress as Addr;
-        match addr.to_friendly() {
+        match friendly_addr {
             Addr::CpuInternalRam(index) => self.cpu_internal_ram.write(index, self.cpu_pinout.data_bus),
 
             // PPU registers.
             Addr::PpuControl => {
                 self.ppu_regs.write_ctrl(self.cpu_pinout.data_bus);
             }
-            Addr::PpuMask    => self.ppu_regs.write_mask(self.cpu_pinout.data_bus),
+            Addr::PpuMask    => self.ppu_regs.write_mask(self.cpu_pinout.data_bus, self.master_clock.region()),
             Addr::PpuStatus  => self.ppu_regs.write_status(self.cpu_pinout.data_bus), // Read-only
             Addr::OamAddress => self.ppu_regs.write_oam_addr(self.cpu_pinout.data_bus),
             Addr::OamData    => self.ppu_regs.write_oam_data(&mut self.oam, self.master_clock.ppu_clock(), self.cpu_pinout.data_bus),
@@ -439,7 +514,7 @@ impl Bus {
             Addr::TrianglePeriod  => self.apu_regs.triangle.set_timer_low(self.cpu_pinout.data_bus),
             Addr::TriangleLength  => self.apu_regs.triangle.set_length_and_timer_high(self.cpu_pinout.data_bus),
             Addr::NoiseControl    => self.apu_regs.noise.set_control(self.cpu_pinout.data_bus),
-            Addr::NoisePeriod     => self.apu_regs.noise.set_loop_and_period(self.cpu_pinout.data_bus),
+            Addr::NoisePeriod     => self.apu_regs.noise.set_loop_and_period(self.cpu_pinout.data_bus, self.master_clock.region()),
             Addr::NoiseLength     => self.apu_regs.noise.set_length(self.cpu_pinout.data_bus),
             Addr::DmcControl      => self.apu_regs.dmc.write_control_byte(&mut self.cpu_pinout),
             Addr::DmcVolume       => self.apu_regs.dmc.write_volume(self.cpu_pinout.data_bus),
@@ -548,6 +623,34 @@ impl Bus {
         self.chr_memory.set_chr_bank_register_to_ciram_side(id, ciram_side);
     }
 
+    // Peek 0x4016
+    fn peek_controller1(&self) -> ReadResult {
+        match &self.vs_system {
+            Some(vs_system) => vs_system.peek_controller1(&self.joypad1, self.dip_switch),
+            None => self.joypad1.peek_status(),
+        }
+    }
+
+    // Peek 0x4017
+    fn peek_controller2(&self) -> ReadResult {
+        match &self.vs_system {
+            Some(vs_system) => vs_system.peek_controller2(&self.joypad2, self.dip_switch),
+            None => self.joypad2.peek_status(),
+        }
+    }
+
+    fn vs_ppu_status(&self, status: ReadResult) -> ReadResult {
+        self.vs_system.as_ref().map_or(status, |vs_system| vs_system.peek_ppu_status(status))
+    }
+
+    fn peek_prg_or_interrupt_vector(&self, mapper: &dyn Mapper, addr: CpuAddress) -> ReadResult {
+        mapper.peek_interrupt_vector(addr).map_or_else(|| self.prg_memory.peek(addr), ReadResult::full)
+    }
+
+    fn vs_protection(&self, addr: CpuAddress) -> Option<ReadResult> {
+        self.vs_system.as_ref().and_then(|vs_system| vs_system.peek_protection(addr))
+    }
+
     // See "APU Register Activation" in the README and asm file here: https://github.com/100thCoin/AccuracyCoin
     pub fn apu_registers_active(&self) -> bool {
         matches!(*self.cpu_pinout.address_bus, 0x4000..=0x401F)
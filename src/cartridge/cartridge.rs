@@ -1,10 +1,13 @@
 use std::path::{Path, PathBuf};
 
 use num_traits::FromPrimitive;
-use splitbits::{combinebits, splitbits};
+use splitbits::{combinebits, splitbits, splitbits_named};
 use ux::u2;
 
-use crate::cartridge::cartridge_metadata::{CartridgeMetadata, CartridgeMetadataBuilder, ConsoleType};
+use crate::cartridge::cartridge_metadata::{CartridgeMetadata, CartridgeMetadataBuilder, ConsoleType, ExpansionDevice, TimingMode};
+use crate::cartridge::fds::{self, FdsImage};
+use crate::cartridge::nsf::NsfFile;
+use crate::cartridge::unif::UnifFile;
 use crate::memory::raw_memory::{RawData, RawMemory, RawMemoryArray};
 use crate::util::unit::KIBIBYTE;
 
@@ -12,6 +15,10 @@ pub const PRG_ROM_CHUNK_LENGTH: u32 = 16 * KIBIBYTE;
 pub const CHR_ROM_CHUNK_LENGTH: u32 = 8 * KIBIBYTE;
 const INES_HEADER_CONSTANT: u32 = u32::from_be_bytes([b'N', b'E', b'S', 0x1A]);
 const NES2_0_HEADER_CONSTANT: u8 = 0b10;
+const HEADER_SIZE: u32 = 0x10;
+const TRAINER_SIZE: u32 = 512;
+const PLAY_CHOICE_INST_ROM_SIZE: usize = 8 * KIBIBYTE as usize;
+const PLAY_CHOICE_PROM_SIZE: usize = 16;
 
 // TODO: Move path and allow_saving elsewhere.
 // TODO: Rename? To CartridgeRom? Name depends on if the trainer can be called ROM or not.
@@ -26,16 +33,45 @@ pub struct Cartridge {
     prg_rom: RawMemory,
     chr_rom: RawMemory,
     trainer: Option<RawMemoryArray<512>>,
+    // Everything after CHR ROM, for the boards that need it. Not a RawMemory since its size can be anything.
+    miscellaneous_rom: RawData,
+    play_choice: Option<PlayChoice>,
+    disk: Option<FdsImage>,
+    nsf: Option<NsfFile>,
 }
 
 impl Cartridge {
     #[rustfmt::skip]
     pub fn load(path: &Path, raw_header_and_data: &RawData) -> Result<Cartridge, String> {
-        let mut header = Self::parse(path, raw_header_and_data)?;
+        if FdsImage::is_fds_image(raw_header_and_data.as_slice()) {
+            return Self::load_fds(path, raw_header_and_data);
+        }
+
+        if NsfFile::is_nsf_file(raw_header_and_data.as_slice()) {
+            return Self::load_nsf(path, raw_header_and_data);
+        }
+
+        if UnifFile::is_unif_file(raw_header_and_data.as_slice()) {
+            return Self::load_unif(path, raw_header_and_data);
+        }
+
+        let (mut header, has_trainer) = Self::parse(path, raw_header_and_data)?;
 
         let path = CartridgePath(path.to_path_buf());
 
-        let prg_rom_start = 0x10;
+        // The trainer sits between the header and PRG ROM.
+        let mut trainer = None;
+        let mut prg_rom_start = HEADER_SIZE;
+        if has_trainer {
+            let Some(raw_trainer) = raw_header_and_data.maybe_slice(HEADER_SIZE..HEADER_SIZE + TRAINER_SIZE) else {
+                return Err(format!("ROM {} was too short to contain its trainer.", path.rom_file_name()));
+            };
+            let raw_trainer = RawMemoryArray::<TRAINER_SIZE>::from_raw(raw_trainer.to_raw())?;
+            header.set_trainer_hash(raw_trainer.hash());
+            trainer = Some(raw_trainer);
+            prg_rom_start += TRAINER_SIZE;
+        }
+
         let prg_rom_end = prg_rom_start + header.prg_rom_size().unwrap();
         let Some(prg_rom) = raw_header_and_data.maybe_slice(prg_rom_start..prg_rom_end) else {
             return Err(format!("ROM {} was too short (claimed to have {}KiB PRG ROM).", path.rom_file_name(), header.prg_rom_size().unwrap() / KIBIBYTE));
@@ -57,8 +93,23 @@ impl Cartridge {
         header.set_prg_rom_hash(prg_rom.hash());
         header.set_chr_rom_hash(chr_rom.hash());
 
-        let title_start = chr_rom_end;
-        let title = raw_header_and_data.slice(title_start..raw_header_and_data.size()).to_raw().to_vec();
+        let remainder = raw_header_and_data.slice(chr_rom_end..raw_header_and_data.size()).to_raw().to_vec();
+        let has_miscellaneous_rom = header.miscellaneous_rom_count().is_some_and(|count| count > 0);
+        // The PlayChoice-10 INST ROM and PROMs are stored where miscellaneous ROM goes.
+        if has_miscellaneous_rom || header.console_type() == Some(ConsoleType::PlayChoice10) {
+            let play_choice = if header.console_type() == Some(ConsoleType::PlayChoice10) {
+                Some(PlayChoice::parse(&remainder)?)
+            } else {
+                None
+            };
+
+            let miscellaneous_rom = RawData::from_vec(remainder);
+            return Ok(Cartridge {
+                path, header, title: String::new(), trainer, miscellaneous_rom, play_choice, disk: None, nsf: None, prg_rom, chr_rom,
+            });
+        }
+
+        let title = remainder;
         let title_length_is_proper = title.is_empty() || title.len() == 127 || title.len() == 128;
         if !title_length_is_proper {
             return Err(format!("Title must be empty or 127 or 128 bytes, but was {} bytes.", title.len()));
@@ -70,7 +121,133 @@ impl Cartridge {
             .take_while(|&c| c != '\u{0}')
             .collect();
 
-        Ok(Cartridge { path, header, title, trainer: None, prg_rom, chr_rom })
+        Ok(Cartridge {
+            path, header, title, trainer, miscellaneous_rom: RawData::from_vec(Vec::new()), play_choice: None, disk: None, nsf: None, prg_rom, chr_rom,
+        })
+    }
+
+    // A disk image isn't a cartridge, but the RAM adapter that it is played through is.
+    // The BIOS (the adapter's PRG ROM) must be supplied separately, see with_fds_bios().
+    fn load_fds(path: &Path, raw_image: &RawData) -> Result<Cartridge, String> {
+        let disk = FdsImage::parse(raw_image.as_slice())?;
+        let header = CartridgeMetadataBuilder::new()
+            .mapper_and_submapper_number(20, None)
+            .console_type(ConsoleType::NesFamiconDendy)
+            .timing_mode(TimingMode::Ntsc)
+            .has_persistent_memory(false)
+            .name_table_mirroring_index(u2::new(0))
+            .full_hash(crc32fast::hash(raw_image.as_slice()))
+            .prg_rom_size(fds::BIOS_SIZE)
+            // Set once the BIOS is supplied.
+            .prg_rom_hash(0)
+            .prg_work_ram_size(32 * KIBIBYTE)
+            .prg_save_ram_size(0)
+            .chr_rom_size(0)
+            .chr_rom_hash(0)
+            .chr_work_ram_size(8 * KIBIBYTE)
+            .chr_save_ram_size(0)
+            .miscellaneous_rom_count(0)
+            .default_expansion_device(ExpansionDevice::StandardNesFamicomControllers)
+            .build();
+
+        Ok(Cartridge {
+            path: CartridgePath(path.to_path_buf()),
+            header,
+            title: String::new(),
+            prg_rom: RawMemory::new(0)?,
+            chr_rom: RawMemory::new(0)?,
+            trainer: None,
+            miscellaneous_rom: RawData::from_vec(Vec::new()),
+            play_choice: None,
+            disk: Some(disk),
+            nsf: None,
+        })
+    }
+
+    // NSF music files are played on a synthetic cartridge with mapper 31's (NSF-compatible) bank switching.
+    fn load_nsf(path: &Path, raw_file: &RawData) -> Result<Cartridge, String> {
+        let nsf = NsfFile::parse(raw_file.as_slice())?;
+        let prg_rom = RawMemory::from_vec(nsf.prg_rom())?;
+        let header = CartridgeMetadataBuilder::new()
+            .mapper_and_submapper_number(31, None)
+            .console_type(ConsoleType::NesFamiconDendy)
+            .timing_mode(nsf.timing_mode)
+            .has_persistent_memory(false)
+            .name_table_mirroring_index(u2::new(0))
+            .full_hash(crc32fast::hash(raw_file.as_slice()))
+            .prg_rom_size(prg_rom.size())
+            .prg_rom_hash(prg_rom.hash())
+            .prg_work_ram_size(8 * KIBIBYTE)
+            .prg_save_ram_size(0)
+            .chr_rom_size(0)
+            .chr_rom_hash(0)
+            .chr_work_ram_size(8 * KIBIBYTE)
+            .chr_save_ram_size(0)
+            .miscellaneous_rom_count(0)
+            .default_expansion_device(ExpansionDevice::StandardNesFamicomControllers)
+            .build();
+
+        Ok(Cartridge {
+            path: CartridgePath(path.to_path_buf()),
+            header,
+            title: nsf.title.clone(),
+            prg_rom,
+            chr_rom: RawMemory::new(0)?,
+            trainer: None,
+            miscellaneous_rom: RawData::from_vec(Vec::new()),
+            play_choice: None,
+            disk: None,
+            nsf: Some(nsf),
+        })
+    }
+
+    // UNIF boards are translated to their iNES mapper numbers so they can be looked up like any other cartridge.
+    // RAM sizes aren't stored, so they are left up to the header database and the mapper's layout.
+    fn load_unif(path: &Path, raw_file: &RawData) -> Result<Cartridge, String> {
+        let unif = UnifFile::parse(raw_file.as_slice())?;
+        let (mapper_number, submapper_number) = unif.mapper_and_submapper_number()?;
+        let prg_rom = RawMemory::from_vec(unif.prg_rom.clone())?;
+        let chr_rom = RawMemory::from_vec(unif.chr_rom.clone())?;
+        let mut builder = CartridgeMetadataBuilder::new();
+        builder
+            .mapper_and_submapper_number(mapper_number, submapper_number)
+            .console_type(ConsoleType::NesFamiconDendy)
+            .has_persistent_memory(unif.has_battery)
+            .name_table_mirroring_index(unif.name_table_mirroring_index())
+            .full_hash(crc32fast::hash(raw_file.as_slice()))
+            .prg_rom_size(prg_rom.size())
+            .prg_rom_hash(prg_rom.hash())
+            .chr_rom_size(chr_rom.size())
+            .chr_rom_hash(chr_rom.hash())
+            .miscellaneous_rom_count(0)
+            .default_expansion_device(unif.default_expansion_device());
+        if let Some(timing_mode) = unif.timing_mode {
+            builder.timing_mode(timing_mode);
+        }
+
+        Ok(Cartridge {
+            path: CartridgePath(path.to_path_buf()),
+            header: builder.build(),
+            title: unif.name,
+            prg_rom,
+            chr_rom,
+            trainer: None,
+            miscellaneous_rom: RawData::from_vec(Vec::new()),
+            play_choice: None,
+            disk: None,
+            nsf: None,
+        })
+    }
+
+    pub fn with_fds_bios(&self, bios: RawMemory) -> Result<Cartridge, String> {
+        if bios.size() != fds::BIOS_SIZE {
+            return Err(format!("FDS BIOS must be {}KiB, but was {} bytes.", fds::BIOS_SIZE / KIBIBYTE, bios.size()));
+        }
+
+        let mut cartridge = self.clone();
+        cartridge.header.set_prg_rom_hash(bios.hash());
+        cartridge.prg_rom = bios;
+        Ok(cartridge)
     }
 
     pub fn name(&self) -> String {
@@ -85,6 +262,27 @@ impl Cartridge {
         &self.path
     }
 
+    // Loaded into PRG RAM at $7000-$71FF at power-on.
+    pub fn trainer(&self) -> Option<&[u8]> {
+        self.trainer.as_ref().map(RawMemoryArray::as_slice)
+    }
+
+    pub fn miscellaneous_rom(&self) -> &RawData {
+        &self.miscellaneous_rom
+    }
+
+    pub fn play_choice(&self) -> Option<&PlayChoice> {
+        self.play_choice.as_ref()
+    }
+
+    pub fn disk(&self) -> Option<&FdsImage> {
+        self.disk.as_ref()
+    }
+
+    pub fn nsf(&self) -> Option<&NsfFile> {
+        self.nsf.as_ref()
+    }
+
     pub fn prg_rom(&self) -> &RawMemory {
         &self.prg_rom
     }
@@ -101,7 +299,8 @@ impl Cartridge {
         self.chr_rom.size()
     }
 
-    fn parse(path: &Path, raw_header_and_data: &RawData) -> Result<CartridgeMetadata, String> {
+    // Also returns whether there is a trainer.
+    fn parse(path: &Path, raw_header_and_data: &RawData) -> Result<(CartridgeMetadata, bool), String> {
         let Some(low_header) = raw_header_and_data.peek_u64(0..=7) else {
             return Err(format!("ROM file should have a 16 byte header. ROM: {}", path.display()));
         };
@@ -110,10 +309,6 @@ impl Cartridge {
             return Err(format!("Cannot load non-iNES ROM. Found {:08X} but need {INES_HEADER_CONSTANT:08X}.", low_header.i));
         }
 
-        if low_header.t {
-            return Err(format!("Trainer isn't implemented yet. ROM: {}", path.display()));
-        }
-
         let mut builder = CartridgeMetadataBuilder::new();
         builder
             .has_persistent_memory(low_header.b)
@@ -126,15 +321,13 @@ impl Cartridge {
                 return Err(format!("ROM file should have a 16 byte header. ROM: {}", path.display()));
             };
             let high_header = splitbits!(high_header, "ssssmmmm ccccpppp ffffgggg hhhhiiii ......tt vvvvxxxx ......rr ..dddddd");
-            assert!(high_header.c != 0xF, "CHR exponent notation not yet supported.");
-            assert!(high_header.p != 0xF, "PRG exponent notation not yet supported.");
 
             let mapper_number = combinebits!(high_header.m, low_header.m, low_header.l, "0000hhhh mmmmllll");
             let console_type = ConsoleType::extended(low_header.x, high_header.x)?;
             builder
                 .mapper_and_submapper_number(mapper_number, Some(high_header.s))
-                .prg_rom_size(combinebits!(high_header.p, low_header.p, "000000hh hhllllll ll000000 00000000"))
-                .chr_rom_size(combinebits!(high_header.c, low_header.c, "0000000h hhhlllll lll00000 00000000"))
+                .prg_rom_size(nes2_rom_size(high_header.p, low_header.p, PRG_ROM_CHUNK_LENGTH)?)
+                .chr_rom_size(nes2_rom_size(high_header.c, low_header.c, CHR_ROM_CHUNK_LENGTH)?)
                 .prg_save_ram_size(if high_header.f == 0 { 0 } else { 64 << high_header.f })
                 .prg_work_ram_size(if high_header.g == 0 { 0 } else { 64 << high_header.g })
                 .chr_save_ram_size(if high_header.h == 0 { 0 } else { 64 << high_header.h })
@@ -159,10 +352,23 @@ impl Cartridge {
                 .console_type(ConsoleType::basic(low_header.x)?);
         }
 
-        Ok(builder.build())
+        Ok((builder.build(), low_header.t))
     }
 }
 
+// The MSB nibble is normally the high bits of the chunk count, but 0xF indicates exponent-multiplier notation:
+// the LSB byte is then EEEEEEMM, and the size is 2^E * (MM * 2 + 1) bytes.
+fn nes2_rom_size(msb_nibble: u8, lsb_byte: u8, chunk_length: u32) -> Result<u32, String> {
+    if msb_nibble != 0xF {
+        return Ok(u32::from(combinebits!(msb_nibble, lsb_byte, "0000mmmm llllllll")) * chunk_length);
+    }
+
+    let (exponent, multiplier) = splitbits_named!(lsb_byte, "eeeeeemm");
+    1u32.checked_shl(exponent.into())
+        .and_then(|power| power.checked_mul(u32::from(multiplier) * 2 + 1))
+        .ok_or_else(|| format!("ROM size 2^{exponent} * {} is too large.", multiplier * 2 + 1))
+}
+
 #[derive(Clone, Debug)]
 pub struct CartridgePath(PathBuf);
 
@@ -182,14 +388,62 @@ impl CartridgePath {
         save_path.set_extension("prg.saveram");
         save_path
     }
+
+    pub fn to_mapper_internal_ram_file_path(&self) -> PathBuf {
+        let mut save_path = PathBuf::new();
+        save_path.push("saveram");
+        save_path.push(self.0.file_stem().unwrap());
+        save_path.set_extension("mapper.saveram");
+        save_path
+    }
+
+    // Disk images are never written to. Changes made by games go here instead.
+    pub fn to_disk_save_file_path(&self) -> PathBuf {
+        let mut save_path = PathBuf::new();
+        save_path.push("saveram");
+        save_path.push(self.0.file_stem().unwrap());
+        save_path.set_extension("disk.saveram");
+        save_path
+    }
 }
 
-#[allow(dead_code)]
 #[derive(Clone, Debug)]
 pub struct PlayChoice {
-    inst_rom: [u8; 8192],
-    prom_data: [u8; 16],
-    prom_counter_out: [u8; 16],
+    inst_rom: Vec<u8>,
+    prom_data: [u8; PLAY_CHOICE_PROM_SIZE],
+    prom_counter_out: [u8; PLAY_CHOICE_PROM_SIZE],
+}
+
+impl PlayChoice {
+    // The PROMs are missing from many dumps.
+    fn parse(raw: &[u8]) -> Result<PlayChoice, String> {
+        let Some((inst_rom, proms)) = raw.split_at_checked(PLAY_CHOICE_INST_ROM_SIZE) else {
+            return Err(format!("PlayChoice-10 INST ROM must be {}KiB, but only {} bytes were present.",
+                PLAY_CHOICE_INST_ROM_SIZE / KIBIBYTE as usize, raw.len()));
+        };
+
+        let mut prom_data = [0; PLAY_CHOICE_PROM_SIZE];
+        let mut prom_counter_out = [0; PLAY_CHOICE_PROM_SIZE];
+        if let Some((data, counter_out)) = proms.split_first_chunk::<PLAY_CHOICE_PROM_SIZE>()
+                && let Some(counter_out) = counter_out.first_chunk::<PLAY_CHOICE_PROM_SIZE>() {
+            prom_data = *data;
+            prom_counter_out = *counter_out;
+        }
+
+        Ok(PlayChoice { inst_rom: inst_rom.to_vec(), prom_data, prom_counter_out })
+    }
+
+    pub fn inst_rom(&self) -> &[u8] {
+        &self.inst_rom
+    }
+
+    pub fn prom_data(&self) -> &[u8; PLAY_CHOICE_PROM_SIZE] {
+        &self.prom_data
+    }
+
+    pub fn prom_counter_out(&self) -> &[u8; PLAY_CHOICE_PROM_SIZE] {
+        &self.prom_counter_out
+    }
 }
 
 #[cfg(test)]
@@ -217,6 +471,10 @@ pub mod test_data {
             prg_rom,
             chr_rom,
             trainer: None,
+            miscellaneous_rom: RawData::from_vec(Vec::new()),
+            play_choice: None,
+            disk: None,
+            nsf: None,
             header,
         }
     }
@@ -1,6 +1,7 @@
 use std::fmt;
 
 use num_derive::FromPrimitive;
+use serde::{Deserialize, Serialize};
 use ux::u2;
 
 use crate::mapper::mapper_list::MAPPERS_WITHOUT_SUBMAPPER_0;
@@ -50,6 +51,10 @@ impl CartridgeMetadata {
         self.chr_rom_hash
     }
 
+    pub fn trainer_hash(&self) -> Option<u32> {
+        self.trainer_hash
+    }
+
     pub fn mapper_number(&self) -> Option<u16> {
         self.mapper_number
     }
@@ -131,6 +136,10 @@ impl CartridgeMetadata {
         self.chr_rom_hash = Some(chr_rom_hash);
     }
 
+    pub fn set_trainer_hash(&mut self, trainer_hash: u32) {
+        self.trainer_hash = Some(trainer_hash);
+    }
+
     pub const fn into_builder(self) -> CartridgeMetadataBuilder {
         CartridgeMetadataBuilder {
             mapper_number: self.mapper_number,
@@ -268,6 +277,11 @@ impl CartridgeMetadataBuilder {
         self
     }
 
+    pub const fn trainer_hash(&mut self, trainer_hash: u32) -> &mut Self {
+        self.trainer_hash = Some(trainer_hash);
+        self
+    }
+
     pub const fn prg_rom_size(&mut self, prg_rom_size: u32) -> &mut Self {
         self.prg_rom_size = Some(prg_rom_size);
         self
@@ -387,7 +401,7 @@ impl ConsoleType {
         match basic_console_type {
             0..=2 =>
                 Ok(Self::from_u8(basic_console_type)),
-            3 if extended_console_type <= 3 =>
+            3 if extended_console_type < 3 =>
                 Err(format!("Extended console type was less than 3 (value: {extended_console_type}) while basic console type was 3.")),
             3 =>
                 Ok(Self::from_u8(extended_console_type)),
@@ -448,7 +462,7 @@ pub enum TimingMode {
     Dendy,
 }
 
-#[derive(Clone, Copy, Debug, Default, FromPrimitive)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, FromPrimitive, Serialize, Deserialize)]
 pub enum VsHardwareType {
     #[default]
     Unisystem,
@@ -460,7 +474,7 @@ pub enum VsHardwareType {
     DualSystemRaidOnBungelingBayProtection,
 }
 
-#[derive(Clone, Copy, Debug, Default, FromPrimitive)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, FromPrimitive, Serialize, Deserialize)]
 pub enum VsPpuType {
     #[default]
     Rp2c03Rc2c03 = 0,
@@ -0,0 +1,131 @@
+use crate::util::unit::KIBIBYTE;
+
+// See https://www.nesdev.org/wiki/FDS_file_format and https://www.nesdev.org/wiki/FDS_disk_format
+pub const FDS_SIDE_SIZE: usize = 65500;
+const FDS_HEADER_CONSTANT: [u8; 4] = [b'F', b'D', b'S', 0x1A];
+const FDS_HEADER_SIZE: usize = 16;
+const DISK_INFO_BLOCK_START: &[u8] = b"\x01*NINTENDO-HVC*";
+
+pub const BIOS_SIZE: u32 = 8 * KIBIBYTE;
+
+// Gap lengths (in bytes) as laid out by the drive when the disk was written.
+const LEADING_GAP_SIZE: usize = 28300 / 8;
+const BLOCK_GAP_SIZE: usize = 976 / 8;
+const BLOCK_START_MARK: u8 = 0x80;
+// .fds images don't store block CRCs. Games don't check them, so any value will do.
+const FAKE_CRC: [u8; 2] = [0x4D, 0x62];
+
+// A Famicom Disk System image, with or without the fwNES header.
+#[derive(Clone, Debug)]
+pub struct FdsImage {
+    sides: Vec<Vec<u8>>,
+}
+
+impl FdsImage {
+    pub fn is_fds_image(raw: &[u8]) -> bool {
+        raw.starts_with(&FDS_HEADER_CONSTANT) || raw.starts_with(DISK_INFO_BLOCK_START)
+    }
+
+    pub fn parse(raw: &[u8]) -> Result<FdsImage, String> {
+        let data = if raw.starts_with(&FDS_HEADER_CONSTANT) {
+            raw.get(FDS_HEADER_SIZE..).ok_or("FDS image should have a 16 byte header.")?
+        } else {
+            raw
+        };
+
+        if data.is_empty() || data.len() % FDS_SIDE_SIZE != 0 {
+            return Err(format!("FDS image size must be a multiple of {FDS_SIDE_SIZE} bytes, but was {}.", data.len()));
+        }
+
+        let sides: Vec<Vec<u8>> = data.chunks(FDS_SIDE_SIZE).map(<[u8]>::to_vec).collect();
+        for (i, side) in sides.iter().enumerate() {
+            if !side.starts_with(DISK_INFO_BLOCK_START) {
+                return Err(format!("FDS disk side {i} doesn't start with a disk info block."));
+            }
+        }
+
+        Ok(FdsImage { sides })
+    }
+
+    pub fn side_count(&self) -> u8 {
+        self.sides.len() as u8
+    }
+
+    // Lays out each side the way the drive head sees it: a leading gap, then each block preceded by
+    // a start mark and followed by its CRC and a gap.
+    pub fn gapped_sides(&self) -> Vec<Vec<u8>> {
+        self.sides.iter().map(|side| Self::gapped_side(side)).collect()
+    }
+
+    fn gapped_side(side: &[u8]) -> Vec<u8> {
+        let mut result = vec![0; LEADING_GAP_SIZE];
+        let mut position = 0;
+        let mut file_size = 0;
+        while position < side.len() {
+            let block_length = match side[position] {
+                1 => 56,
+                2 => 2,
+                3 => {
+                    if let Some(&[low, high]) = side.get(position + 13..position + 15) {
+                        file_size = usize::from(u16::from_le_bytes([low, high]));
+                    }
+
+                    16
+                }
+                4 => 1 + file_size,
+                // Anything else is unused space.
+                _ => break,
+            };
+
+            let block_end = (position + block_length).min(side.len());
+            result.push(BLOCK_START_MARK);
+            result.extend_from_slice(&side[position..block_end]);
+            result.extend_from_slice(&FAKE_CRC);
+            result.extend(std::iter::repeat_n(0, BLOCK_GAP_SIZE));
+            position = block_end;
+        }
+
+        // Leave the unused space at the end so that games have room to save new files.
+        result.resize(result.len() + side.len() - position, 0);
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gapped_side_surrounds_blocks_with_marks_crcs_and_gaps() {
+        let mut side = vec![0; FDS_SIDE_SIZE];
+        side[..DISK_INFO_BLOCK_START.len()].copy_from_slice(DISK_INFO_BLOCK_START);
+        // File amount block.
+        side[56] = 2;
+        side[57] = 1;
+        // File header block for a 3 byte file.
+        side[58] = 3;
+        side[58 + 13] = 3;
+        // File data block.
+        side[74..78].copy_from_slice(&[4, 0xAA, 0xBB, 0xCC]);
+
+        let image = FdsImage::parse(&side).unwrap();
+        let gapped = &image.gapped_sides()[0];
+
+        assert!(gapped[..LEADING_GAP_SIZE].iter().all(|&value| value == 0));
+        let disk_info = &gapped[LEADING_GAP_SIZE..];
+        assert_eq!(disk_info[0], BLOCK_START_MARK);
+        assert_eq!(&disk_info[1..16], DISK_INFO_BLOCK_START);
+        assert_eq!(&disk_info[57..59], &FAKE_CRC);
+
+        let file_data_start = LEADING_GAP_SIZE + 3 * (1 + 2 + BLOCK_GAP_SIZE) + 56 + 2 + 16;
+        assert_eq!(&gapped[file_data_start..file_data_start + 5], &[BLOCK_START_MARK, 4, 0xAA, 0xBB, 0xCC]);
+        assert_eq!(gapped.len(), LEADING_GAP_SIZE + 4 * (1 + 2 + BLOCK_GAP_SIZE) + FDS_SIDE_SIZE);
+    }
+
+    #[test]
+    fn headered_image_with_bad_size_is_rejected() {
+        let mut raw = FDS_HEADER_CONSTANT.to_vec();
+        raw.resize(FDS_HEADER_SIZE + 100, 0);
+        assert!(FdsImage::parse(&raw).is_err());
+    }
+}
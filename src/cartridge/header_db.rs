@@ -175,6 +175,11 @@ impl HeaderDb {
                 header_builder.chr_rom_hash(chr_rom_hash);
             }
 
+            if let Some(trainer_hash) = read_attribute(game, "trainer", "crc32") {
+                let trainer_hash = u32::from_str_radix(trainer_hash, 16).unwrap();
+                header_builder.trainer_hash(trainer_hash);
+            }
+
             if let Some(miscellaneous_rom_count) = read_attribute(game, "miscrom", "number") {
                 header_builder.miscellaneous_rom_count(miscellaneous_rom_count.parse().unwrap());
             }
@@ -1,4 +1,7 @@
 pub mod cartridge;
 pub mod cartridge_metadata;
+pub mod fds;
+pub mod nsf;
+pub mod unif;
 pub mod header_db;
 pub mod resolved_metadata;
\ No newline at end of file
@@ -0,0 +1,297 @@
+use log::warn;
+
+use crate::cartridge::cartridge_metadata::TimingMode;
+use crate::util::unit::KIBIBYTE;
+
+// See https://www.nesdev.org/wiki/NSF and https://www.nesdev.org/wiki/NSFe
+const NSF_HEADER_CONSTANT: &[u8] = b"NESM\x1A";
+const NSFE_HEADER_CONSTANT: &[u8] = b"NSFE";
+const NSF_HEADER_SIZE: usize = 0x80;
+const BANK_SIZE: usize = 4 * KIBIBYTE as usize;
+
+const DEFAULT_NTSC_PLAY_PERIOD_MICROS: u16 = 16639;
+const DEFAULT_PAL_PLAY_PERIOD_MICROS: u16 = 19997;
+
+// A parsed NSF or NSFe music file.
+#[derive(Clone, Debug)]
+pub struct NsfFile {
+    pub title: String,
+    pub artist: String,
+    pub copyright: String,
+    pub track_labels: Vec<String>,
+    // Zero-based.
+    pub starting_track: u8,
+    pub load_address: u16,
+    pub init_address: u16,
+    pub play_address: u16,
+    // Only present if the tune uses bank switching.
+    pub initial_banks: Option<[u8; 8]>,
+    pub ntsc_play_period_micros: u16,
+    pub pal_play_period_micros: u16,
+    pub timing_mode: TimingMode,
+    pub expansion_chips: u8,
+    data: Vec<u8>,
+}
+
+impl NsfFile {
+    pub fn is_nsf_file(raw: &[u8]) -> bool {
+        raw.starts_with(NSF_HEADER_CONSTANT) || raw.starts_with(NSFE_HEADER_CONSTANT)
+    }
+
+    pub fn parse(raw: &[u8]) -> Result<NsfFile, String> {
+        let mut nsf = if raw.starts_with(NSFE_HEADER_CONSTANT) {
+            Self::parse_nsfe(raw)?
+        } else {
+            Self::parse_nsf(raw)?
+        };
+
+        // Some rips leave the play speeds as zero.
+        if nsf.ntsc_play_period_micros == 0 {
+            nsf.ntsc_play_period_micros = DEFAULT_NTSC_PLAY_PERIOD_MICROS;
+        }
+
+        if nsf.pal_play_period_micros == 0 {
+            nsf.pal_play_period_micros = DEFAULT_PAL_PLAY_PERIOD_MICROS;
+        }
+
+        if nsf.load_address < 0x8000 {
+            return Err(format!("NSF load address must be at least $8000, but was ${:04X}.", nsf.load_address));
+        }
+
+        if nsf.track_labels.is_empty() {
+            return Err("NSF must have at least one track.".to_string());
+        }
+
+        if nsf.expansion_chips != 0 {
+            warn!("NSF uses expansion audio chips (flags {:08b}), which aren't supported yet. They will be silent.",
+                nsf.expansion_chips);
+        }
+
+        Ok(nsf)
+    }
+
+    fn parse_nsf(raw: &[u8]) -> Result<NsfFile, String> {
+        let Some(header) = raw.get(..NSF_HEADER_SIZE) else {
+            return Err("NSF file should have a 128 byte header.".to_string());
+        };
+
+        let version = header[0x05];
+        let track_count = header[0x06];
+        let mut data = &raw[NSF_HEADER_SIZE..];
+        let program_length = u32::from_le_bytes([header[0x7D], header[0x7E], header[0x7F], 0]) as usize;
+        // NSF2 metadata may follow the program data.
+        if version >= 2 && program_length != 0 {
+            data = data.get(..program_length).ok_or("NSF program data was shorter than specified.")?;
+        }
+
+        let bank_bytes: [u8; 8] = header[0x70..0x78].try_into().unwrap();
+        Ok(NsfFile {
+            title: header_string(&header[0x0E..0x2E]),
+            artist: header_string(&header[0x2E..0x4E]),
+            copyright: header_string(&header[0x4E..0x6E]),
+            track_labels: (1..=track_count).map(|track| format!("Track {track}")).collect(),
+            starting_track: header[0x07].saturating_sub(1),
+            load_address: u16::from_le_bytes([header[0x08], header[0x09]]),
+            init_address: u16::from_le_bytes([header[0x0A], header[0x0B]]),
+            play_address: u16::from_le_bytes([header[0x0C], header[0x0D]]),
+            initial_banks: bank_bytes.iter().any(|&bank| bank != 0).then_some(bank_bytes),
+            ntsc_play_period_micros: u16::from_le_bytes([header[0x6E], header[0x6F]]),
+            pal_play_period_micros: u16::from_le_bytes([header[0x78], header[0x79]]),
+            timing_mode: timing_mode(header[0x7A]),
+            expansion_chips: header[0x7B],
+            data: data.to_vec(),
+        })
+    }
+
+    fn parse_nsfe(raw: &[u8]) -> Result<NsfFile, String> {
+        let mut nsf = NsfFile {
+            title: String::new(),
+            artist: String::new(),
+            copyright: String::new(),
+            track_labels: Vec::new(),
+            starting_track: 0,
+            load_address: 0,
+            init_address: 0,
+            play_address: 0,
+            initial_banks: None,
+            ntsc_play_period_micros: DEFAULT_NTSC_PLAY_PERIOD_MICROS,
+            pal_play_period_micros: DEFAULT_PAL_PLAY_PERIOD_MICROS,
+            timing_mode: TimingMode::Ntsc,
+            expansion_chips: 0,
+            data: Vec::new(),
+        };
+
+        let mut track_count = None;
+        let mut labels = Vec::new();
+        let mut remaining = &raw[NSFE_HEADER_CONSTANT.len()..];
+        loop {
+            let Some((chunk_header, rest)) = remaining.split_first_chunk::<8>() else {
+                return Err("NSFe file ended without an NEND chunk.".to_string());
+            };
+            let length = u32::from_le_bytes(chunk_header[..4].try_into().unwrap()) as usize;
+            let id: &[u8; 4] = chunk_header[4..].try_into().unwrap();
+            let Some(chunk) = rest.get(..length) else {
+                return Err(format!("NSFe chunk {} was truncated.", String::from_utf8_lossy(id)));
+            };
+            remaining = &rest[length..];
+
+            match id {
+                b"INFO" => {
+                    if chunk.len() < 9 {
+                        return Err("NSFe INFO chunk must be at least 9 bytes.".to_string());
+                    }
+
+                    nsf.load_address = u16::from_le_bytes([chunk[0], chunk[1]]);
+                    nsf.init_address = u16::from_le_bytes([chunk[2], chunk[3]]);
+                    nsf.play_address = u16::from_le_bytes([chunk[4], chunk[5]]);
+                    nsf.timing_mode = timing_mode(chunk[6]);
+                    nsf.expansion_chips = chunk[7];
+                    track_count = Some(chunk.get(8).copied().unwrap_or(1));
+                    nsf.starting_track = chunk.get(9).copied().unwrap_or(0);
+                }
+                b"DATA" => nsf.data = chunk.to_vec(),
+                b"BANK" => {
+                    let mut banks = [0; 8];
+                    for (bank, &value) in banks.iter_mut().zip(chunk) {
+                        *bank = value;
+                    }
+
+                    nsf.initial_banks = Some(banks);
+                }
+                b"RATE" => {
+                    if let [low, high, ..] = *chunk {
+                        nsf.ntsc_play_period_micros = u16::from_le_bytes([low, high]);
+                    }
+
+                    if let [_, _, low, high, ..] = *chunk {
+                        nsf.pal_play_period_micros = u16::from_le_bytes([low, high]);
+                    }
+                }
+                b"auth" => {
+                    let mut strings = chunk.split(|&c| c == 0).map(|s| String::from_utf8_lossy(s).into_owned());
+                    nsf.title = strings.next().unwrap_or_default();
+                    nsf.artist = strings.next().unwrap_or_default();
+                    nsf.copyright = strings.next().unwrap_or_default();
+                }
+                b"tlbl" => {
+                    labels = chunk.split(|&c| c == 0).map(|s| String::from_utf8_lossy(s).into_owned()).collect();
+                }
+                b"NEND" => break,
+                // Chunks that start with a capital letter must be understood to play the file correctly.
+                _ if id[0].is_ascii_uppercase() => {
+                    return Err(format!("Unsupported NSFe chunk {}.", String::from_utf8_lossy(id)));
+                }
+                _ => { /* Optional chunk that isn't supported. */ }
+            }
+        }
+
+        let Some(track_count) = track_count else {
+            return Err("NSFe file has no INFO chunk.".to_string());
+        };
+
+        if nsf.data.is_empty() {
+            return Err("NSFe file has no DATA chunk.".to_string());
+        }
+
+        nsf.track_labels = (0..usize::from(track_count))
+            .map(|i| labels.get(i).filter(|label| !label.is_empty()).cloned().unwrap_or_else(|| format!("Track {}", i + 1)))
+            .collect();
+        Ok(nsf)
+    }
+
+    pub fn track_count(&self) -> u8 {
+        self.track_labels.len() as u8
+    }
+
+    // The program data laid out in 4KiB banks. Tunes that don't bank switch are laid out as a
+    // 32KiB image of $8000-$FFFF.
+    pub fn prg_rom(&self) -> Vec<u8> {
+        let padding = if self.initial_banks.is_some() {
+            usize::from(self.load_address & 0x0FFF)
+        } else {
+            usize::from(self.load_address - 0x8000)
+        };
+
+        let mut prg_rom = vec![0; padding];
+        prg_rom.extend_from_slice(&self.data);
+        if self.initial_banks.is_none() {
+            prg_rom.resize(8 * BANK_SIZE, 0);
+        } else {
+            prg_rom.resize(prg_rom.len().next_multiple_of(BANK_SIZE).next_power_of_two(), 0);
+        }
+
+        prg_rom
+    }
+
+    pub fn initial_banks(&self) -> [u8; 8] {
+        self.initial_banks.unwrap_or([0, 1, 2, 3, 4, 5, 6, 7])
+    }
+}
+
+fn header_string(raw: &[u8]) -> String {
+    let end = raw.iter().position(|&c| c == 0).unwrap_or(raw.len());
+    String::from_utf8_lossy(&raw[..end]).into_owned()
+}
+
+fn timing_mode(flags: u8) -> TimingMode {
+    match flags & 0b11 {
+        0b00 => TimingMode::Ntsc,
+        0b01 => TimingMode::Pal,
+        _ => TimingMode::MultiRegion,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nsfe_chunks_are_parsed() {
+        let mut raw = b"NSFE".to_vec();
+        let mut chunk = |id: &[u8], data: &[u8]| {
+            raw.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            raw.extend_from_slice(id);
+            raw.extend_from_slice(data);
+        };
+        chunk(b"INFO", &[0x00, 0x80, 0x00, 0x80, 0x03, 0x80, 0x00, 0x00, 2, 1]);
+        chunk(b"DATA", &[0x60; 4]);
+        chunk(b"auth", b"Title\0Artist\0Copyright\0Ripper\0");
+        chunk(b"tlbl", b"Intro\0");
+        chunk(b"plst", &[1, 0]);
+        chunk(b"NEND", &[]);
+
+        let nsf = NsfFile::parse(&raw).unwrap();
+        assert_eq!(nsf.title, "Title");
+        assert_eq!(nsf.artist, "Artist");
+        assert_eq!(nsf.track_labels, vec!["Intro".to_string(), "Track 2".to_string()]);
+        assert_eq!(nsf.starting_track, 1);
+        assert_eq!(nsf.play_address, 0x8003);
+        assert_eq!(nsf.prg_rom().len(), 32 * KIBIBYTE as usize);
+    }
+
+    #[test]
+    fn nsfe_with_unknown_required_chunk_is_rejected() {
+        let mut raw = b"NSFE".to_vec();
+        raw.extend_from_slice(&0u32.to_le_bytes());
+        raw.extend_from_slice(b"ZZZZ");
+        assert!(NsfFile::parse(&raw).is_err());
+    }
+
+    #[test]
+    fn bank_switched_data_is_offset_within_first_bank() {
+        let mut raw = vec![0; NSF_HEADER_SIZE];
+        raw[..5].copy_from_slice(NSF_HEADER_CONSTANT);
+        raw[0x06] = 1;
+        raw[0x07] = 1;
+        raw[0x08..0x0A].copy_from_slice(&0x8123u16.to_le_bytes());
+        raw[0x71] = 1;
+        raw.extend_from_slice(&[0xAA; 5000]);
+
+        let nsf = NsfFile::parse(&raw).unwrap();
+        let prg_rom = nsf.prg_rom();
+        assert_eq!(prg_rom.len(), 8 * KIBIBYTE as usize);
+        assert_eq!(prg_rom[0x122], 0);
+        assert_eq!(prg_rom[0x123], 0xAA);
+        assert_eq!(nsf.initial_banks(), [0, 1, 0, 0, 0, 0, 0, 0]);
+    }
+}
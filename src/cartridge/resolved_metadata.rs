@@ -19,6 +19,7 @@ pub struct ResolvedMetadata {
     pub full_hash: u32,
     pub prg_rom_hash: u32,
     pub chr_rom_hash: u32,
+    pub trainer_hash: Option<u32>,
 
     pub prg_rom_size: u32,
     pub prg_work_ram_size: u32,
@@ -90,6 +91,7 @@ impl MetadataResolver {
             full_hash: self.cartridge.full_hash().unwrap(),
             prg_rom_hash: self.cartridge.prg_rom_hash().unwrap(),
             chr_rom_hash: self.cartridge.chr_rom_hash().unwrap(),
+            trainer_hash: self.cartridge.trainer_hash(),
 
             // TODO: Verify that all PRG ROM sizes match.
             prg_rom_size: self.cartridge.prg_rom_size().unwrap(),
@@ -117,6 +119,13 @@ impl MetadataResolver {
             );
         }
 
+        if self.database.trainer_hash().is_some() && resolved_metadata.trainer_hash != self.database.trainer_hash() {
+            warn!("Trainer doesn't match the nes20db.xml entry. DB full hash: {:X}, DB PRG ROM hash: {:X}",
+                self.database.full_hash().unwrap(),
+                self.database.prg_rom_hash().unwrap(),
+            );
+        }
+
         resolved_metadata
     }
 
@@ -145,7 +154,7 @@ impl MetadataResolver {
             .default_expansion_device(ExpansionDevice::StandardNesFamicomControllers);
 
         let console_type = resolve_field(&all_metadata, |m| m.console_type()).unwrap();
-        if console_type == ConsoleType::PlayChoice10 {
+        if matches!(console_type, ConsoleType::Vs | ConsoleType::PlayChoice10) {
             builder
                 .vs_hardware_type(VsHardwareType::Unisystem)
                 .vs_ppu_type(VsPpuType::Rp2c03Rc2c03);
@@ -0,0 +1,288 @@
+use ux::u2;
+
+use crate::cartridge::cartridge_metadata::{ExpansionDevice, TimingMode};
+
+// See https://www.nesdev.org/wiki/UNIF
+const UNIF_HEADER_CONSTANT: &[u8] = b"UNIF";
+const UNIF_HEADER_SIZE: usize = 32;
+const CHUNK_HEADER_SIZE: usize = 8;
+
+// Board names are usually prefixed by who made the board. The prefix doesn't affect the mapper.
+const BOARD_NAME_PREFIXES: &[&str] = &["NES-", "HVC-", "UNL-", "BMC-", "BTL-", "AVE-", "IREM-", "KONAMI-", "TAITO-"];
+
+// Board names (without their prefix) and the (mapper, submapper) that each corresponds to.
+// See https://www.nesdev.org/wiki/UNIF_to_NES_2.0_Mapping
+#[rustfmt::skip]
+const BOARDS: &[(&str, u16, Option<u8>)] = &[
+    // NROM
+    ("NROM", 0, None), ("NROM-128", 0, None), ("NROM-256", 0, None), ("HROM", 0, None), ("RROM", 0, None),
+    ("RROM-128", 0, None), ("RTROM", 0, None), ("SROM", 0, None), ("STROM", 0, None),
+    // MMC1
+    ("SAROM", 1, Some(0)), ("SBROM", 1, Some(0)), ("SCROM", 1, Some(0)), ("SC1ROM", 1, Some(0)), ("SFROM", 1, Some(0)),
+    ("SF1ROM", 1, Some(0)), ("SGROM", 1, Some(0)), ("SIROM", 1, Some(0)), ("SJROM", 1, Some(0)), ("SKROM", 1, Some(0)),
+    ("SLROM", 1, Some(0)), ("SL1ROM", 1, Some(0)), ("SL2ROM", 1, Some(0)), ("SL3ROM", 1, Some(0)), ("SLRROM", 1, Some(0)),
+    ("SMROM", 1, Some(0)), ("SNROM", 1, Some(0)), ("SOROM", 1, Some(0)), ("SUROM", 1, Some(0)), ("SXROM", 1, Some(0)),
+    // MMC1 with fixed 32KiB PRG ROM
+    ("SEROM", 1, Some(5)), ("SHROM", 1, Some(5)), ("SH1ROM", 1, Some(5)),
+    // UxROM
+    ("UNROM", 2, Some(2)), ("UOROM", 2, Some(2)), ("UN1ROM", 94, None),
+    // CNROM
+    ("CNROM", 3, Some(2)),
+    // MMC3 and MMC6
+    ("TBROM", 4, Some(0)), ("TEROM", 4, Some(0)), ("TFROM", 4, Some(0)), ("TGROM", 4, Some(0)), ("TKROM", 4, Some(0)),
+    ("TLROM", 4, Some(0)), ("TL1ROM", 4, Some(0)), ("TL2ROM", 4, Some(0)), ("TNROM", 4, Some(0)), ("TR1ROM", 4, Some(0)),
+    ("TSROM", 4, Some(0)), ("TVROM", 4, Some(0)), ("HKROM", 4, Some(1)),
+    ("TKSROM", 118, None), ("TLSROM", 118, None), ("TQROM", 119, None),
+    // MMC5
+    ("EKROM", 5, None), ("ELROM", 5, None), ("ETROM", 5, None), ("EWROM", 5, None),
+    // AxROM
+    ("AMROM", 7, Some(2)), ("AOROM", 7, Some(2)), ("ANROM", 7, Some(1)), ("AN1ROM", 7, Some(1)),
+    // MMC2 and MMC4
+    ("PNROM", 9, None), ("PEEOROM", 9, None), ("FJROM", 10, None), ("FKROM", 10, None),
+    ("CPROM", 13, None),
+    // BNROM and NINA-001
+    ("BNROM", 34, Some(2)), ("NINA-001", 34, Some(1)), ("NINA-01", 34, Some(1)),
+    // GxROM
+    ("GNROM", 66, None), ("MHROM", 66, None),
+    ("NTBROM", 68, None),
+    ("JLROM", 69, None), ("JSROM", 69, None), ("BTR", 69, None),
+    // Namco 108
+    ("DEROM", 206, Some(0)), ("DE1ROM", 206, Some(0)), ("DRROM", 206, Some(0)),
+
+    // Unlicensed boards
+    ("Sachen-8259A", 141, None), ("Sachen-8259B", 138, None), ("Sachen-8259C", 139, None), ("Sachen-8259D", 137, None),
+    ("Sachen-74LS374N", 150, None), ("SA-72007", 145, None), ("SA-72008", 133, None), ("SA-NROM", 143, None),
+    ("SA-0036", 149, None), ("SA-0037", 148, None), ("TC-U01-1.5M", 147, None),
+    ("22211", 132, None), ("H2288", 123, None), ("8237", 215, Some(0)), ("8237A", 215, Some(1)), ("8157", 301, None),
+    ("603-5052", 238, None), ("AC08", 42, None), ("AX5705", 530, None), ("BB", 108, None), ("CITYFIGHT", 266, None),
+    ("EDU2000", 329, None), ("KOF97", 263, None), ("KS7012", 346, None), ("KS7013B", 312, None), ("KS7016", 306, None),
+    ("KS7017", 303, None), ("KS7030", 347, None), ("KS7031", 305, None), ("KS7032", 142, None), ("KS7037", 307, None),
+    ("KS7057", 302, None), ("LH32", 125, None), ("MALISB", 325, None), ("SL1632", 14, None), ("SMB2J", 304, None),
+    ("T-230", 529, None), ("TF1201", 298, None),
+
+    // Multicarts
+    ("12-IN-1", 331, None), ("190in1", 300, None), ("411120-C", 287, None), ("64in1NoRepeat", 314, None),
+    ("70in1", 236, None), ("70in1B", 236, None), ("810544-C-A1", 261, None), ("A65AS", 285, None), ("BS-5", 286, None),
+    ("D1038", 59, None), ("FK23C", 176, None), ("FK23CA", 176, None), ("GS-2004", 283, None), ("GS-2013", 283, None),
+    ("K-3046", 336, None), ("NovelDiamond9999999in1", 201, None), ("NTD-03", 290, None), ("SA005-A", 338, None),
+    ("Super700in1", 62, None), ("SUPERHIK8IN1", 45, None), ("T-262", 265, None), ("TJ-03", 341, None),
+];
+
+// A parsed UNIF ROM image. UNIF files identify their board by name rather than by mapper number.
+#[derive(Clone, Debug)]
+pub struct UnifFile {
+    pub board_name: String,
+    pub name: String,
+    pub prg_rom: Vec<u8>,
+    pub chr_rom: Vec<u8>,
+    pub mirroring: Option<UnifMirroring>,
+    pub has_battery: bool,
+    // Bit flags for which controllers the game supports.
+    pub controllers: u8,
+    pub timing_mode: Option<TimingMode>,
+}
+
+impl UnifFile {
+    pub fn is_unif_file(raw: &[u8]) -> bool {
+        raw.starts_with(UNIF_HEADER_CONSTANT)
+    }
+
+    pub fn parse(raw: &[u8]) -> Result<UnifFile, String> {
+        let Some(mut remaining) = raw.get(UNIF_HEADER_SIZE..) else {
+            return Err(format!("UNIF file should have a {UNIF_HEADER_SIZE} byte header."));
+        };
+
+        let mut unif = UnifFile {
+            board_name: String::new(),
+            name: String::new(),
+            prg_rom: Vec::new(),
+            chr_rom: Vec::new(),
+            mirroring: None,
+            has_battery: false,
+            controllers: 0,
+            timing_mode: None,
+        };
+
+        // PRG and CHR ROM are split into up to 16 chunks each, which are concatenated in ID order
+        // (not file order) to form the full ROMs.
+        let mut prg_chunks: [Option<&[u8]>; 16] = [None; 16];
+        let mut chr_chunks: [Option<&[u8]>; 16] = [None; 16];
+        while !remaining.is_empty() {
+            let Some((chunk_header, rest)) = remaining.split_first_chunk::<CHUNK_HEADER_SIZE>() else {
+                return Err("UNIF file ended in the middle of a chunk header.".to_string());
+            };
+            let id: &[u8; 4] = chunk_header[..4].try_into().unwrap();
+            let length = u32::from_le_bytes(chunk_header[4..].try_into().unwrap()) as usize;
+            let Some(chunk) = rest.get(..length) else {
+                return Err(format!("UNIF chunk {} was truncated.", String::from_utf8_lossy(id)));
+            };
+            remaining = &rest[length..];
+
+            match id {
+                b"MAPR" => unif.board_name = chunk_string(chunk),
+                b"NAME" => unif.name = chunk_string(chunk),
+                [b'P', b'R', b'G', index] => prg_chunks[chunk_index(*index)?] = Some(chunk),
+                [b'C', b'H', b'R', index] => chr_chunks[chunk_index(*index)?] = Some(chunk),
+                b"MIRR" => {
+                    let &[value, ..] = chunk else {
+                        return Err("UNIF MIRR chunk must not be empty.".to_string());
+                    };
+                    unif.mirroring = Some(UnifMirroring::from_u8(value)?);
+                }
+                // Only the presence of the chunk matters, not its contents.
+                b"BATR" => unif.has_battery = true,
+                b"CTRL" => unif.controllers = chunk.first().copied().unwrap_or(0),
+                b"TVCI" => {
+                    unif.timing_mode = match chunk.first() {
+                        Some(0) => Some(TimingMode::Ntsc),
+                        Some(1) => Some(TimingMode::Pal),
+                        Some(2) => Some(TimingMode::MultiRegion),
+                        _ => None,
+                    };
+                }
+                // Checksums, dumper info, comments, etc.
+                _ => { /* Not needed to run the game. */ }
+            }
+        }
+
+        if unif.board_name.is_empty() {
+            return Err("UNIF file has no MAPR (board name) chunk.".to_string());
+        }
+
+        unif.prg_rom = prg_chunks.iter().flatten().flat_map(|chunk| chunk.iter().copied()).collect();
+        unif.chr_rom = chr_chunks.iter().flatten().flat_map(|chunk| chunk.iter().copied()).collect();
+        if unif.prg_rom.is_empty() {
+            return Err("UNIF file has no PRG ROM chunks.".to_string());
+        }
+
+        Ok(unif)
+    }
+
+    // The iNES (mapper, submapper) equivalent of the board.
+    pub fn mapper_and_submapper_number(&self) -> Result<(u16, Option<u8>), String> {
+        let board_name = BOARD_NAME_PREFIXES.iter()
+            .find_map(|prefix| strip_prefix_ignore_ascii_case(&self.board_name, prefix))
+            .unwrap_or(&self.board_name);
+        BOARDS.iter()
+            .find(|(name, _, _)| name.eq_ignore_ascii_case(board_name))
+            .map(|&(_, mapper_number, submapper_number)| (mapper_number, submapper_number))
+            .ok_or_else(|| format!("UNIF board {} doesn't have a known mapper number.", self.board_name))
+    }
+
+    pub fn name_table_mirroring_index(&self) -> u2 {
+        match self.mirroring {
+            None | Some(UnifMirroring::Horizontal) => u2::new(0),
+            Some(UnifMirroring::Vertical) => u2::new(1),
+            Some(UnifMirroring::FourScreen) => u2::new(2),
+            // Boards with these mirrorings set them themselves.
+            Some(UnifMirroring::OneScreenA | UnifMirroring::OneScreenB | UnifMirroring::MapperControlled) => u2::new(0),
+        }
+    }
+
+    // Only the most specific supported controller is picked.
+    pub fn default_expansion_device(&self) -> ExpansionDevice {
+        if self.controllers & 0b0010_0000 != 0 {
+            ExpansionDevice::NesFourScoreSatellite
+        } else if self.controllers & 0b0000_0010 != 0 {
+            ExpansionDevice::Zapper4017
+        } else if self.controllers & 0b0000_1000 != 0 {
+            ExpansionDevice::ArkanoidVausControllerNes
+        } else if self.controllers & 0b0001_0000 != 0 {
+            ExpansionDevice::PowerPadSideB
+        } else {
+            ExpansionDevice::StandardNesFamicomControllers
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnifMirroring {
+    Horizontal,
+    Vertical,
+    OneScreenA,
+    OneScreenB,
+    FourScreen,
+    MapperControlled,
+}
+
+impl UnifMirroring {
+    fn from_u8(value: u8) -> Result<UnifMirroring, String> {
+        Ok(match value {
+            0 => UnifMirroring::Horizontal,
+            1 => UnifMirroring::Vertical,
+            2 => UnifMirroring::OneScreenA,
+            3 => UnifMirroring::OneScreenB,
+            4 => UnifMirroring::FourScreen,
+            5 => UnifMirroring::MapperControlled,
+            _ => return Err(format!("Invalid UNIF mirroring value {value}.")),
+        })
+    }
+}
+
+fn chunk_index(hex_digit: u8) -> Result<usize, String> {
+    char::from(hex_digit).to_digit(16)
+        .map(|index| index as usize)
+        .ok_or_else(|| format!("Invalid UNIF ROM chunk index {}.", char::from(hex_digit)))
+}
+
+fn chunk_string(chunk: &[u8]) -> String {
+    let end = chunk.iter().position(|&c| c == 0).unwrap_or(chunk.len());
+    String::from_utf8_lossy(&chunk[..end]).trim().to_string()
+}
+
+fn strip_prefix_ignore_ascii_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix).then(|| &text[prefix.len()..])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn unif(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut raw = b"UNIF".to_vec();
+        raw.extend_from_slice(&7u32.to_le_bytes());
+        raw.resize(UNIF_HEADER_SIZE, 0);
+        for (id, data) in chunks {
+            raw.extend_from_slice(*id);
+            raw.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            raw.extend_from_slice(data);
+        }
+
+        raw
+    }
+
+    #[test]
+    fn rom_chunks_are_concatenated_in_id_order() {
+        let raw = unif(&[
+            (b"MAPR", b"NES-SLROM\0"),
+            (b"PRG1", &[2, 3]),
+            (b"PRG0", &[0, 1]),
+            (b"CHR0", &[4]),
+            (b"MIRR", &[1]),
+            (b"BATR", &[0]),
+        ]);
+
+        let unif = UnifFile::parse(&raw).unwrap();
+        assert_eq!(unif.prg_rom, vec![0, 1, 2, 3]);
+        assert_eq!(unif.chr_rom, vec![4]);
+        assert_eq!(unif.mirroring, Some(UnifMirroring::Vertical));
+        assert!(unif.has_battery);
+        assert_eq!(unif.mapper_and_submapper_number(), Ok((1, Some(0))));
+    }
+
+    #[test]
+    fn board_names_ignore_prefix_and_case() {
+        let raw = unif(&[(b"MAPR", b"UNL-SACHEN-8259A\0"), (b"PRG0", &[0])]);
+        assert_eq!(UnifFile::parse(&raw).unwrap().mapper_and_submapper_number(), Ok((141, None)));
+
+        let raw = unif(&[(b"MAPR", b"Mystery board\0"), (b"PRG0", &[0])]);
+        assert!(UnifFile::parse(&raw).unwrap().mapper_and_submapper_number().is_err());
+    }
+
+    #[test]
+    fn missing_board_name_is_rejected() {
+        let raw = unif(&[(b"PRG0", &[0])]);
+        assert!(UnifFile::parse(&raw).is_err());
+    }
+}
@@ -4,13 +4,19 @@ use std::str::FromStr;
 
 use structopt::StructOpt;
 
+use crate::apu::sample_rate::SampleRate;
 use crate::controller::joypad::{Button, ButtonStatus};
 use crate::gui::egui_gui::EguiGui;
 use crate::gui::gui::Gui;
 use crate::gui::no_gui::NoGui;
-use crate::ppu::ppu_clock::PpuClock;
+use crate::master_clock::CpuPpuAlignment;
+use crate::power_on::{PowerOnState, PowerOnValues};
+use crate::ppu::ppu_clock::{Overclock, OverclockPosition, PpuClock};
 use crate::ppu::palette::system_palette::SystemPalette;
-use crate::ppu::render::frame_rate::{FrameRate, TargetFrameRate};
+use crate::ppu::render::frame_rate::TargetFrameRate;
+use crate::region::Region;
+
+const MEBIBYTE: usize = 1024 * 1024;
 
 #[derive(Clone, Copy)]
 pub enum Event {
@@ -30,16 +36,39 @@ impl Event {
 pub struct Config {
     pub starting_cpu_cycle: i64,
     pub ppu_clock: PpuClock,
-    pub system_palette: SystemPalette,
+    pub cpu_ppu_alignment: CpuPpuAlignment,
+    pub overclock: Overclock,
+    pub ntsc_system_palette: SystemPalette,
+    pub pal_system_palette: SystemPalette,
     pub target_frame_rate: TargetFrameRate,
+    pub region_override: Option<Region>,
     pub disable_audio: bool,
+    pub sample_rate: SampleRate,
+    // Written whether or not audio is disabled. Only applies to the ROM specified at startup.
+    pub record_audio_path: Option<PathBuf>,
     pub stop_frame: Option<i64>,
     pub frame_dump: bool,
     pub cpu_step_formatting: CpuStepFormatting,
+    // Enables decimal mode even if the cartridge doesn't specify a decimal mode famiclone console type.
+    pub decimal_mode_override: bool,
     pub allow_saving: bool,
     pub scheduled_button_events: BTreeMap<i64, (Event, ButtonStatus)>,
     pub dip_switch: u8,
+    pub fds_bios_path: Option<PathBuf>,
+    // Takes precedence over fds_bios_path. For when the BIOS isn't in a file.
+    pub fds_bios: Option<Vec<u8>>,
+    // WAV files named by clip number, for boards with a speech chip.
+    pub speech_clips_path: Option<PathBuf>,
+    // WAV file contents by clip number, added to any clips from speech_clips_path. For when the clips
+    // aren't in files.
+    pub speech_clips: BTreeMap<u8, Vec<u8>>,
     pub diff_logging_enabled: bool,
+    pub rewind_snapshot_interval: u32,
+    pub rewind_max_snapshot_count: usize,
+    pub rewind_memory_budget: usize,
+    // None leaves memory in the same state every time, without trying to mimic the hardware's randomness.
+    pub power_on_state: Option<PowerOnState>,
+    pub power_on_seed: u64,
 }
 
 impl Config {
@@ -47,25 +76,77 @@ impl Config {
         let mut config = Config {
             starting_cpu_cycle: 0,
             ppu_clock: PpuClock::mesen_compatible(),
-            system_palette: SystemPalette::parse(include_str!("../palettes/2C02.pal")).unwrap(),
+            cpu_ppu_alignment: opt.cpu_ppu_alignment,
+            overclock: Overclock { extra_scanlines: opt.overclock_scanlines, position: opt.overclock_position },
+            ntsc_system_palette: SystemPalette::parse(include_str!("../palettes/2C02.pal")).unwrap(),
+            // The 2C07 generates each hue at a different phase of the color subcarrier than the 2C02.
+            pal_system_palette: SystemPalette::parse(include_str!("../palettes/2C07.pal")).unwrap(),
             target_frame_rate: opt.target_frame_rate,
+            region_override: opt.region,
             disable_audio: opt.disable_audio,
+            sample_rate: opt.sample_rate,
+            record_audio_path: opt.record_audio.clone(),
             stop_frame: opt.stop_frame,
             frame_dump: opt.frame_dump,
             cpu_step_formatting: opt.cpu_step_formatting,
+            decimal_mode_override: opt.decimal_mode,
             allow_saving: !opt.prevent_saving,
             scheduled_button_events: BTreeMap::new(),
             dip_switch: opt.dip_switch,
+            fds_bios_path: opt.fds_bios.clone(),
+            fds_bios: None,
+            speech_clips_path: opt.speech_clips.clone(),
+            speech_clips: BTreeMap::new(),
             diff_logging_enabled: opt.diff_logging_enabled(),
+            rewind_snapshot_interval: opt.rewind_snapshot_interval,
+            rewind_max_snapshot_count: opt.rewind_max_snapshot_count,
+            rewind_memory_budget: opt.rewind_memory_budget_mib * MEBIBYTE,
+            power_on_state: opt.power_on_state,
+            power_on_seed: opt.power_on_seed.unwrap_or_else(PowerOnValues::generate_seed),
         };
 
         config.parse_scheduled_button_events(&opt.scheduled_button_presses);
         config
     }
 
+    // For running without the command line or a GUI. Frames aren't paced, and nothing is played or saved,
+    // since audio and save RAM are left to the caller.
+    pub fn headless() -> Config {
+        Config {
+            starting_cpu_cycle: 0,
+            ppu_clock: PpuClock::mesen_compatible(),
+            cpu_ppu_alignment: CpuPpuAlignment::default(),
+            overclock: Overclock::default(),
+            ntsc_system_palette: SystemPalette::parse(include_str!("../palettes/2C02.pal")).unwrap(),
+            pal_system_palette: SystemPalette::parse(include_str!("../palettes/2C07.pal")).unwrap(),
+            target_frame_rate: TargetFrameRate::Unbounded,
+            region_override: None,
+            disable_audio: true,
+            sample_rate: SampleRate::default(),
+            record_audio_path: None,
+            stop_frame: None,
+            frame_dump: false,
+            cpu_step_formatting: CpuStepFormatting::Data,
+            decimal_mode_override: false,
+            allow_saving: false,
+            scheduled_button_events: BTreeMap::new(),
+            dip_switch: 0,
+            fds_bios_path: None,
+            fds_bios: None,
+            speech_clips_path: None,
+            speech_clips: BTreeMap::new(),
+            diff_logging_enabled: false,
+            rewind_snapshot_interval: 0,
+            rewind_max_snapshot_count: 0,
+            rewind_memory_budget: 0,
+            power_on_state: None,
+            power_on_seed: PowerOnValues::generate_seed(),
+        }
+    }
+
     pub fn gui(self, gui_type: GuiType) -> Box<dyn Gui> {
         match gui_type {
-            GuiType::NoGui => Box::new(NoGui) as Box<dyn Gui>,
+            GuiType::NoGui => Box::new(NoGui::new(self.stop_frame)) as Box<dyn Gui>,
             GuiType::Egui => Box::new(EguiGui::new(self)),
         }
     }
@@ -109,15 +190,43 @@ pub struct Opt {
     #[structopt(short, long, default_value = "egui")]
     pub gui: GuiType,
 
-    #[structopt(name = "targetframerate", long, default_value = "ntsc")]
+    #[structopt(name = "targetframerate", long, default_value = "console")]
     pub target_frame_rate: TargetFrameRate,
 
+    // Overrides the console region specified by the cartridge header.
+    #[structopt(name = "region", long)]
+    pub region: Option<Region>,
+
+    // Extra idle scanlines per frame, giving the CPU more time to reduce slowdown. Zero disables overclocking.
+    #[structopt(name = "overclockscanlines", long, default_value = "0")]
+    pub overclock_scanlines: u16,
+
+    // Where the extra overclocking scanlines go: postrender (before the vblank NMI) or vblank (after it).
+    #[structopt(name = "overclockposition", long, default_value = "postrender")]
+    pub overclock_position: OverclockPosition,
+
+    // Emulate a famiclone CPU that supports the 6502's decimal mode, which the 2A03 lacks.
+    #[structopt(name = "decimalmode", long)]
+    pub decimal_mode: bool,
+
+    // Which of the four CPU/PPU clock phases to start in: 0 through 3, or random. 0 matches Mesen.
+    #[structopt(name = "cpuppualignment", long, default_value = "0")]
+    pub cpu_ppu_alignment: CpuPpuAlignment,
+
     #[structopt(name = "stopframe", long)]
     pub stop_frame: Option<i64>,
 
     #[structopt(name = "disableaudio", long)]
     pub disable_audio: bool,
 
+    // The audio output rate: 44100, 48000, or 96000.
+    #[structopt(name = "samplerate", long, default_value = "44100")]
+    pub sample_rate: SampleRate,
+
+    // Records the mixed audio output to a WAV file. Works even when audio is disabled.
+    #[structopt(name = "recordaudio", long, parse(from_os_str))]
+    pub record_audio: Option<PathBuf>,
+
     #[structopt(name = "logcpuall", long)]
     pub log_cpu_all: bool,
 
@@ -193,8 +302,35 @@ pub struct Opt {
     #[structopt(name = "dipswitch", long, default_value = "0")]
     pub dip_switch: u8,
 
+    // The Famicom Disk System BIOS ROM (8KiB), required to run .fds images.
+    #[structopt(name = "fdsbios", long, parse(from_os_str))]
+    pub fds_bios: Option<PathBuf>,
+
+    // A directory of WAV files named by clip number (0.wav, 1.wav, etc.), for the voice clips of
+    // boards with a speech chip. The clips are stored inside the chip, so they aren't in ROM images.
+    #[structopt(name = "speechclips", long, parse(from_os_str))]
+    pub speech_clips: Option<PathBuf>,
+
     #[structopt(name = "assemble", long, parse(from_os_str))]
     pub assemble: Option<PathBuf>,
+
+    // Number of frames between rewind snapshots. Zero disables rewinding.
+    #[structopt(name = "rewindinterval", long, default_value = "10")]
+    pub rewind_snapshot_interval: u32,
+
+    #[structopt(name = "rewinddepth", long, default_value = "600")]
+    pub rewind_max_snapshot_count: usize,
+
+    #[structopt(name = "rewindmemory", long, default_value = "256")]
+    pub rewind_memory_budget_mib: usize,
+
+    // How RAM and mapper registers are filled at power-on: zero, ff, pattern, or random.
+    #[structopt(name = "poweronstate", long)]
+    pub power_on_state: Option<PowerOnState>,
+
+    // Makes random power-on states reproducible. The seed that was used is logged.
+    #[structopt(name = "poweronseed", long)]
+    pub power_on_seed: Option<u64>,
 }
 
 impl Opt {
@@ -203,8 +339,15 @@ impl Opt {
             rom_path,
             gui: GuiType::Egui,
             stop_frame: None,
-            target_frame_rate: TargetFrameRate::Value(FrameRate::NTSC),
+            target_frame_rate: TargetFrameRate::Console,
+            region: None,
+            cpu_ppu_alignment: CpuPpuAlignment::default(),
+            overclock_scanlines: 0,
+            overclock_position: OverclockPosition::PostRender,
+            decimal_mode: false,
             disable_audio: false,
+            sample_rate: SampleRate::default(),
+            record_audio: None,
             log_frames: false,
             log_cpu_all: false,
             log_ppu_all: false,
@@ -230,7 +373,14 @@ impl Opt {
             prevent_saving: false,
             scheduled_button_presses: Vec::new(),
             dip_switch: 0,
+            fds_bios: None,
+            speech_clips: None,
             assemble: None,
+            rewind_snapshot_interval: 10,
+            rewind_max_snapshot_count: 600,
+            rewind_memory_budget_mib: 256,
+            power_on_state: None,
+            power_on_seed: None,
         }
     }
 
@@ -242,7 +392,14 @@ impl Opt {
             gui: _,
             stop_frame: _,
             target_frame_rate: _,
+            region: _,
+            cpu_ppu_alignment: _,
+            overclock_scanlines: _,
+            overclock_position: _,
+            decimal_mode: _,
             disable_audio: _,
+            sample_rate: _,
+            record_audio: _,
             log_frames: _, // Only logs once per frame, and needed to view accurate frame rate.
             log_cpu_all,
             log_ppu_all,
@@ -268,7 +425,14 @@ impl Opt {
             prevent_saving: _,
             scheduled_button_presses: _,
             dip_switch: _,
+            fds_bios: _,
+            speech_clips: _,
             assemble: _,
+            rewind_snapshot_interval: _,
+            rewind_max_snapshot_count: _,
+            rewind_memory_budget_mib: _,
+            power_on_state: _,
+            power_on_seed: _,
         } = self.clone();
 
         log_cpu_all | log_ppu_all | log_apu_all | log_cpu_instructions | log_cpu_flow_control
@@ -295,9 +459,10 @@ impl FromStr for GuiType {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 pub enum CpuStepFormatting {
     NoData,
+    #[default]
     Data,
 }
 
@@ -2,11 +2,12 @@ use std::ops::{Index, IndexMut};
 
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
 
 use crate::memory::read_result::ReadResult;
 
 // https://wiki.nesdev.com/w/index.php/Controller_reading_code
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct Joypad {
     strobe_mode: StrobeMode,
     pending_strobe_mode: Option<StrobeMode>,
@@ -62,18 +63,22 @@ impl Joypad {
         self.pending_strobe_mode = Some(mode);
     }
 
+    pub fn selected_button(&self) -> Option<Button> {
+        self.selected_button
+    }
+
     pub fn set_button_status(&mut self, button: Button, status: ButtonStatus) {
         self.button_statuses[button] = status;
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum StrobeMode {
     Off,
     On,
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, FromPrimitive)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, FromPrimitive, Serialize, Deserialize)]
 pub enum Button {
     A,
     B,
@@ -98,7 +103,7 @@ impl Button {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct ButtonStatuses([ButtonStatus; 8]);
 
 impl ButtonStatuses {
@@ -120,7 +125,7 @@ impl IndexMut<Button> for ButtonStatuses {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum ButtonStatus {
     Unpressed,
     Pressed,
@@ -1,11 +1,13 @@
 use std::num::{NonZeroI8, NonZeroU8};
 
+use serde::{Deserialize, Serialize};
 use ux::u4;
 
 use crate::counter::irq_counter_info::IrqCounterInfo;
 pub use crate::counter::when_disabled_prevent::WhenDisabledPrevent;
 
 // A counter where the count can be set directly, and can't be force-reloaded.
+#[derive(Serialize, Deserialize)]
 pub struct DirectlySetCounter(Counter);
 
 impl DirectlySetCounter {
@@ -83,6 +85,7 @@ impl DirectlySetCounter {
 }
 
 // A counter where the count only be set by reloading through a reload value.
+#[derive(Serialize, Deserialize)]
 pub struct ReloadDrivenCounter {
     counter: Counter,
     forced_reload_timing: ForcedReloadTiming,
@@ -152,6 +155,7 @@ impl ReloadDrivenCounter {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 struct Counter {
     full_range: Range,
     current_range: Range,
@@ -444,7 +448,7 @@ impl CounterBuilder {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum AutoTriggerWhen {
     Wrapping,
     EndingOn(u16),
@@ -460,13 +464,13 @@ pub enum WhenTargetReached {
 }
     */
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum ForcedReloadTiming {
     Immediate,
     OnNextTick,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Prescaler {
     // Immutable settings determined at compile time
     multiple: NonZeroU8,
@@ -507,13 +511,13 @@ impl Prescaler {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum PrescalerTriggeredBy {
     AlreadyZero,
     WrappingToZero,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum PrescalerBehaviorOnForcedReload {
     DoNothing,
     ClearCount,
@@ -526,7 +530,7 @@ pub struct TickResult {
     pub triggered: bool,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Range {
     min: u16,
     max: u16,
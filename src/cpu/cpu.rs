@@ -2,6 +2,7 @@ use std::marker::ConstParamTy;
 
 use log::{info, log_enabled};
 use log::Level::Info;
+use serde::{Deserialize, Serialize};
 
 use crate::config::CpuStepFormatting;
 use crate::cpu::cpu_mode::{CpuModeState, InterruptType};
@@ -18,6 +19,7 @@ use crate::bus::{Bus, IRQ_VECTOR_HIGH, IRQ_VECTOR_LOW, NMI_VECTOR_HIGH, NMI_VECT
 use crate::memory::cpu::cpu_pinout::CpuPinout;
 use crate::memory::signal_level::SignalLevel;
 
+#[derive(Serialize, Deserialize)]
 pub struct Cpu {
     // Accumulator
     a: u8,
@@ -49,16 +51,21 @@ pub struct Cpu {
     operand: u8,
 
     // TODO: Remove
+    #[serde(skip)]
     formatted_step: String,
     original_program_counter: CpuAddress,
     value: u8,
 
+    #[serde(skip)]
     step_formatting: CpuStepFormatting,
+    // The 2A03 has the 6502's BCD circuitry disconnected, but some famiclones use a real 6502.
+    #[serde(skip)]
+    decimal_mode_enabled: bool,
 }
 
 impl Cpu {
     // From https://wiki.nesdev.org/w/index.php?title=CPU_power_up_state
-    pub fn new(step_formatting: CpuStepFormatting) -> Self {
+    pub fn new(step_formatting: CpuStepFormatting, decimal_mode_enabled: bool) -> Self {
         Self {
             a: 0,
             x: 0,
@@ -92,6 +99,7 @@ impl Cpu {
             value: 0,
 
             step_formatting,
+            decimal_mode_enabled,
         }
     }
 
@@ -107,6 +115,15 @@ impl Cpu {
         self.current_interrupt_vector = None;
     }
 
+    // Restores a saved CPU while keeping the current logging and decimal mode configuration.
+    pub fn load_state(&mut self, saved: Cpu) {
+        let step_formatting = self.step_formatting;
+        let decimal_mode_enabled = self.decimal_mode_enabled;
+        *self = saved;
+        self.step_formatting = step_formatting;
+        self.decimal_mode_enabled = decimal_mode_enabled;
+    }
+
     pub fn accumulator(&self) -> u8 {
         self.a
     }
@@ -674,6 +691,35 @@ impl Cpu {
     }
 
     fn adc(&mut self, value: u8) -> u8 {
+        if self.decimal_mode_enabled && self.status.decimal {
+            return self.decimal_adc(value);
+        }
+
+        self.binary_adc(value)
+    }
+
+    fn sbc(&mut self, value: u8) -> u8 {
+        let borrow = i16::from(!self.status.carry);
+        // The flags are the same as in binary mode, only the result is decimal adjusted.
+        let result = self.binary_adc(value ^ 0xFF);
+        if !(self.decimal_mode_enabled && self.status.decimal) {
+            return result;
+        }
+
+        let mut low = i16::from(self.a & 0x0F) - i16::from(value & 0x0F) - borrow;
+        if low < 0 {
+            low = ((low - 0x06) & 0x0F) - 0x10;
+        }
+
+        let mut result = i16::from(self.a & 0xF0) - i16::from(value & 0xF0) + low;
+        if result < 0 {
+            result -= 0x60;
+        }
+
+        result as u8
+    }
+
+    fn binary_adc(&mut self, value: u8) -> u8 {
         let carry = u16::from(self.status.carry);
         let result = (u16::from(self.a)) + (u16::from(value)) + carry;
         self.status.carry = result > 0xFF;
@@ -684,8 +730,28 @@ impl Cpu {
         result
     }
 
-    fn sbc(&mut self, value: u8) -> u8 {
-        self.adc(value ^ 0xFF)
+    // NMOS 6502 behavior, from http://www.6502.org/tutorials/decimal_mode.html#A
+    // Z comes from the binary sum, while N and V come from the sum before the high digit is adjusted.
+    fn decimal_adc(&mut self, value: u8) -> u8 {
+        let carry = u16::from(self.status.carry);
+        let binary_result = (u16::from(self.a) + u16::from(value) + carry) as u8;
+
+        let mut low = u16::from(self.a & 0x0F) + u16::from(value & 0x0F) + carry;
+        if low >= 0x0A {
+            low = ((low + 0x06) & 0x0F) + 0x10;
+        }
+
+        let mut result = u16::from(self.a & 0xF0) + u16::from(value & 0xF0) + low;
+        self.status.negative = is_neg(result as u8);
+        self.status.overflow =
+            (is_neg(self.a) == is_neg(value)) && (is_neg(self.a) != is_neg(result as u8));
+        self.status.zero = binary_result == 0;
+        if result >= 0xA0 {
+            result += 0x60;
+        }
+
+        self.status.carry = result >= 0x100;
+        result as u8
     }
 
     fn cmp(&mut self, value: u8) {
@@ -766,7 +832,7 @@ fn is_neg(value: u8) -> bool {
     (value >> 7) == 1
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy, Serialize, Deserialize)]
 pub enum NmiStatus {
     #[default]
     Inactive,
@@ -775,7 +841,7 @@ pub enum NmiStatus {
     Active,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy, Serialize, Deserialize)]
 pub enum IrqStatus {
     #[default]
     Inactive,
@@ -784,7 +850,7 @@ pub enum IrqStatus {
     Active,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy, Serialize, Deserialize)]
 pub enum ResetStatus {
     #[default]
     Inactive,
@@ -1,10 +1,12 @@
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 use crate::cpu::step::*;
 use crate::cpu::instruction::{Instruction, OpCode};
 use crate::memory::cpu::cpu_address::CpuAddress;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum CpuMode {
     StartNext,
     Instruction(OpCode, InstructionMode),
@@ -35,7 +37,7 @@ impl fmt::Display for CpuMode {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum InstructionMode {
     Normal,
     Oops,
@@ -44,8 +46,9 @@ pub enum InstructionMode {
 }
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuModeState {
+    #[serde(with = "static_steps")]
     steps: &'static [Step],
     step_index: usize,
     mode: CpuMode,
@@ -268,7 +271,7 @@ impl CpuModeState {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum InterruptType {
     Nmi,
     Reset,
@@ -1,10 +1,12 @@
 use std::marker::ConstParamTy;
 
 use log::info;
+use serde::{Deserialize, Serialize};
 use splitbits::combinebits;
 
 use crate::apu::apu_clock::CycleParity;
 
+#[derive(Serialize, Deserialize)]
 pub struct DmcDma {
     puts_until_disabled: Option<u8>,
     state: DmcDmaState,
@@ -142,7 +144,7 @@ impl DmcDma {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum DmcDmaState {
     Idle,
     WaitingForGet,
@@ -153,7 +155,7 @@ pub enum DmcDmaState {
     TryRead,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy, Serialize, Deserialize)]
 pub enum DmcDmaAction {
     #[default]
     DoNothing,
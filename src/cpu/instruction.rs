@@ -1,5 +1,6 @@
 use std::sync::LazyLock;
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use strum_macros::EnumString;
 
 use crate::cpu::step::*;
@@ -66,6 +67,19 @@ pub struct Instruction {
     steps: &'static [Step],
 }
 
+// Instructions are fully determined by their code point, so that's all that needs to be saved.
+impl Serialize for Instruction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.code_point)
+    }
+}
+
+impl<'de> Deserialize<'de> for Instruction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(Instruction::from_code_point)
+    }
+}
+
 impl Instruction {
     pub fn code_point(&self) -> u8 {
         self.code_point
@@ -152,7 +166,7 @@ impl Instruction {
 }
 
 #[allow(clippy::upper_case_acronyms)]
-#[derive(PartialEq, Eq, Clone, Copy, Debug, EnumString)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, EnumString, Serialize, Deserialize)]
 pub enum OpCode {
     // Logical/Arithmetic
     ORA,
@@ -1,8 +1,11 @@
 use std::marker::ConstParamTy;
 
+use serde::{Deserialize, Serialize};
+
 use crate::apu::apu_clock::CycleParity;
 use crate::memory::cpu::cpu_address::CpuAddress;
 
+#[derive(Serialize, Deserialize)]
 pub struct OamDma {
     state: OamDmaState,
     latest_action: OamDmaAction,
@@ -50,7 +53,7 @@ impl OamDma {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum OamDmaState {
     Idle,
     TryHalt,
@@ -93,7 +96,7 @@ impl OamDmaState {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy, Serialize, Deserialize)]
 pub enum OamDmaAction {
     #[default]
     DoNothing,
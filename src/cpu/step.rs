@@ -1,3 +1,8 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cpu::instruction::Instruction;
 use crate::cpu::step_action::Field;
 use crate::cpu::step_action::Field::*;
 use crate::cpu::step_action::{From, To};
@@ -313,15 +318,77 @@ pub const JMP_IND_STEPS: &[Step] = &[
     Read(                         From::PendingAddressTarget, &[CopyAddressToPC, StartNextInstruction, IncrementPC]),
 ];
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Step {
-    Read(From, &'static [StepAction]),
-    Write(To, &'static [StepAction]),
-    ReadField(Field, From, &'static [StepAction]),
-    WriteField(Field, To, &'static [StepAction]),
-    OamRead(From, &'static [StepAction]),
-    OamWrite(To, &'static [StepAction]),
-    DmcRead(From, &'static [StepAction]),
+    Read(From, #[serde(with = "static_actions")] &'static [StepAction]),
+    Write(To, #[serde(with = "static_actions")] &'static [StepAction]),
+    ReadField(Field, From, #[serde(with = "static_actions")] &'static [StepAction]),
+    WriteField(Field, To, #[serde(with = "static_actions")] &'static [StepAction]),
+    OamRead(From, #[serde(with = "static_actions")] &'static [StepAction]),
+    OamWrite(To, #[serde(with = "static_actions")] &'static [StepAction]),
+    DmcRead(From, #[serde(with = "static_actions")] &'static [StepAction]),
+}
+
+// Every static step sequence that the CPU can be executing.
+// Save states store step sequences by value, so this is needed to recover the static references upon load.
+static ALL_STEP_SEQUENCES: LazyLock<Vec<&'static [Step]>> = LazyLock::new(|| {
+    let mut sequences: Vec<&'static [Step]> =
+        vec![&[], RESET_STEPS, BRK_STEPS, &[BRANCH_TAKEN_STEP], &[READ_OP_CODE_STEP]];
+    for code_point in 0..=u8::MAX {
+        sequences.push(Instruction::from_code_point(code_point).steps());
+    }
+
+    sequences
+});
+
+// Every static list of actions that a step can have, including the DMA and OOPS steps.
+static ALL_STEP_ACTIONS: LazyLock<Vec<&'static [StepAction]>> = LazyLock::new(|| {
+    let special_steps = [OAM_READ_STEP, OAM_WRITE_STEP, DMC_READ_STEP, OOPS_STEP];
+    let mut all_actions: Vec<&'static [StepAction]> = vec![&[]];
+    for step in ALL_STEP_SEQUENCES.iter().flat_map(|sequence| sequence.iter()).chain(special_steps.iter()) {
+        all_actions.push(step.actions());
+    }
+
+    all_actions
+});
+
+pub mod static_steps {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::Error;
+
+    use crate::cpu::step::{Step, ALL_STEP_SEQUENCES};
+
+    pub fn serialize<S: Serializer>(steps: &&'static [Step], serializer: S) -> Result<S::Ok, S::Error> {
+        steps.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<&'static [Step], D::Error> {
+        let steps: Vec<Step> = Vec::deserialize(deserializer)?;
+        ALL_STEP_SEQUENCES.iter()
+            .find(|sequence| **sequence == &steps[..])
+            .copied()
+            .ok_or_else(|| D::Error::custom("Unknown CPU step sequence."))
+    }
+}
+
+mod static_actions {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::Error;
+
+    use crate::cpu::step::ALL_STEP_ACTIONS;
+    use crate::cpu::step_action::StepAction;
+
+    pub fn serialize<S: Serializer>(actions: &&'static [StepAction], serializer: S) -> Result<S::Ok, S::Error> {
+        actions.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<&'static [StepAction], D::Error> {
+        let actions: Vec<StepAction> = Vec::deserialize(deserializer)?;
+        ALL_STEP_ACTIONS.iter()
+            .find(|static_actions| **static_actions == &actions[..])
+            .copied()
+            .ok_or_else(|| D::Error::custom("Unknown CPU step actions."))
+    }
 }
 
 impl Step {
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::memory::cpu::cpu_address::CpuAddress;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum StepAction {
     IncrementPC,
     AddCarryToPC,
@@ -36,7 +38,7 @@ pub enum StepAction {
     ExecuteOpCode,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum From {
     OamDmaAddressTarget,
     DmcDmaAddressTarget,
@@ -52,7 +54,7 @@ pub enum From {
     InterruptVectorHigh,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum To {
     OamDmaAddressTarget,
 
@@ -70,7 +72,7 @@ impl To {
     pub const OAM_DATA: To = To::AddressTarget(CpuAddress::new(0x2004));
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Field {
     ProgramCounterLowByte,
     ProgramCounterHighByte,
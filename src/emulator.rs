@@ -0,0 +1,217 @@
+// An API for embedding REZNEZ in other programs. Unlike the CLI, nothing here touches the filesystem
+// or the audio device: the ROM comes in as bytes, and frames, audio, and save RAM go out through sinks.
+use std::collections::BTreeMap;
+
+use crate::apu::sample_rate::SampleRate;
+use crate::cartridge::header_db::HeaderDb;
+use crate::config::Config;
+use crate::controller::joypad::{Button, ButtonStatus};
+use crate::gui::gui::Events;
+use crate::master_clock::CpuPpuAlignment;
+use crate::nes::Nes;
+use crate::power_on::PowerOnState;
+use crate::ppu::render::frame::Frame;
+use crate::region::Region;
+
+pub trait VideoSink {
+    // Called once at the end of every frame.
+    fn frame_ready(&mut self, frame: &Frame);
+}
+
+pub trait AudioSink {
+    // Mono samples at the emulator's audio sample rate, everything produced during the latest frame.
+    fn samples_ready(&mut self, samples: &[f32]);
+}
+
+pub trait SaveRamSink {
+    // Called at the end of any frame in which the battery-backed RAM changed. See Nes::save_ram().
+    fn save_ram_changed(&mut self, save_ram: &[u8]);
+}
+
+// The audio sample rate, unless another one is specified.
+pub const AUDIO_SAMPLE_RATE: u32 = SampleRate::Hz44100.hz();
+
+pub struct EmulatorBuilder {
+    rom_name: String,
+    rom: Vec<u8>,
+    save_ram: Option<Vec<u8>>,
+    fds_bios: Option<Vec<u8>>,
+    speech_clips: BTreeMap<u8, Vec<u8>>,
+    region: Option<Region>,
+    power_on_state: Option<PowerOnState>,
+    power_on_seed: Option<u64>,
+    cpu_ppu_alignment: CpuPpuAlignment,
+    sample_rate: SampleRate,
+    video_sink: Option<Box<dyn VideoSink>>,
+    audio_sink: Option<Box<dyn AudioSink>>,
+    save_ram_sink: Option<Box<dyn SaveRamSink>>,
+}
+
+impl EmulatorBuilder {
+    // The name is only used for logging and display, since the ROM doesn't come from a file.
+    pub fn from_rom_bytes(rom_name: &str, rom: Vec<u8>) -> Self {
+        Self {
+            rom_name: rom_name.to_string(),
+            rom,
+            save_ram: None,
+            fds_bios: None,
+            speech_clips: BTreeMap::new(),
+            region: None,
+            power_on_state: None,
+            power_on_seed: None,
+            cpu_ppu_alignment: CpuPpuAlignment::default(),
+            sample_rate: SampleRate::default(),
+            video_sink: None,
+            audio_sink: None,
+            save_ram_sink: None,
+        }
+    }
+
+    // Previously persisted save RAM. Must be the same size as the cartridge's save RAM.
+    pub fn save_ram(mut self, save_ram: Vec<u8>) -> Self {
+        self.save_ram = Some(save_ram);
+        self
+    }
+
+    // Required to run FDS disk images.
+    pub fn fds_bios(mut self, fds_bios: Vec<u8>) -> Self {
+        self.fds_bios = Some(fds_bios);
+        self
+    }
+
+    // The contents of a WAV file, for boards with a speech chip. The clips are stored inside the chip,
+    // so they aren't in ROM images.
+    pub fn speech_clip(mut self, clip_number: u8, wav: Vec<u8>) -> Self {
+        self.speech_clips.insert(clip_number, wav);
+        self
+    }
+
+    // Overrides the region that the ROM specifies.
+    pub fn region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    pub fn power_on_state(mut self, power_on_state: PowerOnState, seed: u64) -> Self {
+        self.power_on_state = Some(power_on_state);
+        self.power_on_seed = Some(seed);
+        self
+    }
+
+    pub fn cpu_ppu_alignment(mut self, cpu_ppu_alignment: CpuPpuAlignment) -> Self {
+        self.cpu_ppu_alignment = cpu_ppu_alignment;
+        self
+    }
+
+    pub fn sample_rate(mut self, sample_rate: SampleRate) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    pub fn video_sink(mut self, video_sink: impl VideoSink + 'static) -> Self {
+        self.video_sink = Some(Box::new(video_sink));
+        self
+    }
+
+    pub fn audio_sink(mut self, audio_sink: impl AudioSink + 'static) -> Self {
+        self.audio_sink = Some(Box::new(audio_sink));
+        self
+    }
+
+    pub fn save_ram_sink(mut self, save_ram_sink: impl SaveRamSink + 'static) -> Self {
+        self.save_ram_sink = Some(Box::new(save_ram_sink));
+        self
+    }
+
+    pub fn build(self) -> Result<Emulator, String> {
+        // Audio and save RAM are delivered through the sinks instead of an audio device and a file.
+        let mut config = Config::headless();
+        config.region_override = self.region;
+        config.cpu_ppu_alignment = self.cpu_ppu_alignment;
+        config.power_on_state = self.power_on_state;
+        if let Some(seed) = self.power_on_seed {
+            config.power_on_seed = seed;
+        }
+
+        config.sample_rate = self.sample_rate;
+        config.fds_bios = self.fds_bios;
+        config.speech_clips = self.speech_clips;
+
+        let cartridge = Nes::load_cartridge_from_bytes(&self.rom_name, self.rom)?;
+        let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge)?;
+        if let Some(save_ram) = &self.save_ram {
+            nes.load_save_ram(save_ram)
+                .map_err(|err| format!("Failed to load save RAM for {}. {err}", self.rom_name))?;
+        }
+
+        let latest_save_ram = nes.save_ram();
+        Ok(Emulator {
+            nes,
+            video_sink: self.video_sink,
+            audio_sink: self.audio_sink,
+            save_ram_sink: self.save_ram_sink,
+            latest_save_ram,
+        })
+    }
+}
+
+pub struct Emulator {
+    nes: Nes,
+    video_sink: Option<Box<dyn VideoSink>>,
+    audio_sink: Option<Box<dyn AudioSink>>,
+    save_ram_sink: Option<Box<dyn SaveRamSink>>,
+    // The save RAM contents as of the last time the SaveRamSink was notified.
+    latest_save_ram: Vec<u8>,
+}
+
+impl Emulator {
+    pub fn nes(&self) -> &Nes {
+        &self.nes
+    }
+
+    pub fn nes_mut(&mut self) -> &mut Nes {
+        &mut self.nes
+    }
+
+    pub fn audio_sample_rate(&self) -> u32 {
+        self.nes.bus().apu.sample_rate().hz()
+    }
+
+    pub fn step_frame(&mut self) {
+        self.nes.step_frame();
+
+        if let Some(video_sink) = &mut self.video_sink {
+            video_sink.frame_ready(self.nes.frame());
+        }
+
+        // Samples are always drained so that they don't pile up when there is no sink.
+        let samples = self.nes.take_queued_audio();
+        if let Some(audio_sink) = &mut self.audio_sink {
+            audio_sink.samples_ready(&samples);
+        }
+
+        if let Some(save_ram_sink) = &mut self.save_ram_sink {
+            let save_ram = self.nes.save_ram();
+            if save_ram != self.latest_save_ram {
+                save_ram_sink.save_ram_changed(&save_ram);
+                self.latest_save_ram = save_ram;
+            }
+        }
+    }
+
+    pub fn set_joypad1_buttons(&mut self, button_statuses: BTreeMap<Button, ButtonStatus>) {
+        self.nes.process_gui_events(&Events { joypad1_button_statuses: button_statuses, ..Events::none() });
+    }
+
+    pub fn set_joypad2_buttons(&mut self, button_statuses: BTreeMap<Button, ButtonStatus>) {
+        self.nes.process_gui_events(&Events { joypad2_button_statuses: button_statuses, ..Events::none() });
+    }
+
+    pub fn reset(&mut self) {
+        self.nes.set_reset_signal();
+    }
+
+    pub fn save_ram(&self) -> Vec<u8> {
+        self.nes.save_ram()
+    }
+}
@@ -21,10 +21,12 @@ use winit_input_helper::WinitInputHelper;
 use crate::config::Config;
 use crate::controller::joypad::{Button, ButtonStatus};
 use crate::gui::gui::{Gui, Events};
+use crate::gui::rewind::Rewind;
 use crate::gui::window_renderer::{FlowControl, WindowRenderer};
 use crate::gui::window_renderers::primary_renderer::PrimaryRenderer;
 use crate::gui::world::World;
 use crate::nes::Nes;
+use crate::vs_system::VsInput;
 
 #[rustfmt::skip]
 static JOY_1_KEYBOARD_MAPPINGS: LazyLock<HashMap<KeyCode, Button>> = LazyLock::new(|| {
@@ -59,6 +61,14 @@ static JOY_2_KEYBOARD_MAPPINGS: LazyLock<HashMap<KeyCode, Button>> = LazyLock::n
     mappings
 });
 
+static VS_KEYBOARD_MAPPINGS: LazyLock<HashMap<KeyCode, VsInput>> = LazyLock::new(|| {
+    let mut mappings = HashMap::new();
+    mappings.insert(KeyCode::Digit5, VsInput::Coin1);
+    mappings.insert(KeyCode::Digit6, VsInput::Coin2);
+    mappings.insert(KeyCode::Digit9, VsInput::Service);
+    mappings
+});
+
 static JOY_1_JOYPAD_MAPPINGS: LazyLock<HashMap<u32, Button>> = LazyLock::new(|| {
     let mut mappings = HashMap::new();
     mappings.insert(65824, Button::A);
@@ -93,8 +103,9 @@ impl EguiGui {
         }
 
         let events = Events::none();
+        let rewind = Rewind::new(&config);
         Self {
-            world: World { nes: None, config, events },
+            world: World { nes: None, config, events, rewind },
             window_manager: WindowManager::new(),
             keyboard: WinitInputHelper::new(),
             gamepad_handler,
@@ -139,14 +150,8 @@ impl ApplicationHandler for EguiGui {
                 }
             }
             WindowEvent::RedrawRequested => {
-                if let Some(nes) = &mut self.world.nes {
-                    if self.keyboard.key_pressed(KeyCode::F1) {
-                        info!("{}", nes.bus().oam);
-                    }
-
-                    if self.keyboard.key_pressed(KeyCode::F12) {
-                        nes.set_reset_signal();
-                    }
+                if let Some(nes) = &self.world.nes && self.keyboard.key_pressed(KeyCode::F1) {
+                    info!("{}", nes.bus().oam);
                 }
 
                 if window_id == self.window_manager.primary_window_id
@@ -471,6 +476,7 @@ impl WindowManager {
 fn poll_button_events(input: &WinitInputHelper, gilrs: &mut gilrs::Gilrs, active_gamepad_id: Option<GamepadId>) -> Events {
     let mut joypad1_button_statuses = BTreeMap::new();
     let mut joypad2_button_statuses = BTreeMap::new();
+    let mut vs_input_statuses = BTreeMap::new();
 
     while let Some(gilrs::Event { id, event, .. }) = gilrs.next_event() {
         if Some(id) != active_gamepad_id {
@@ -508,11 +514,21 @@ fn poll_button_events(input: &WinitInputHelper, gilrs: &mut gilrs::Gilrs, active
         }
     }
 
+    for (&key, &vs_input) in VS_KEYBOARD_MAPPINGS.iter() {
+        if input.key_pressed(key) {
+            vs_input_statuses.insert(vs_input, ButtonStatus::Pressed);
+        } else if input.key_released(key) {
+            vs_input_statuses.insert(vs_input, ButtonStatus::Unpressed);
+        }
+    }
+
     Events {
         // Quit-handling is done by winit.
         should_quit: false,
         joypad1_button_statuses,
         joypad2_button_statuses,
+        reset: input.key_pressed(KeyCode::F12),
+        vs_input_statuses,
     }
 }
 
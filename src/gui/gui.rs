@@ -9,7 +9,9 @@ use log::{info, warn};
 
 use crate::config::{Config, Event};
 use crate::controller::joypad::{Button, ButtonStatus};
+use crate::gui::rewind::Rewind;
 use crate::nes::Nes;
+use crate::vs_system::VsInput;
 use crate::ppu::render::frame::Frame;
 use crate::ppu::render::frame_rate::TargetFrameRate;
 
@@ -19,22 +21,22 @@ pub trait Gui {
     fn run(&mut self, nes: Option<Nes>);
 }
 
-pub fn execute_frame<F>(nes: &mut Nes, config: &Config, mut events: Events, display_frame: F)
+pub fn execute_frame<F>(nes: &mut Nes, config: &Config, rewind: &mut Rewind, mut events: Events, display_frame: F)
 where
     F: FnOnce(&Frame, i64),
 {
     let frame_index = nes.bus().ppu_clock().frame();
     let start_time = SystemTime::now();
-    let target_frame_rate = config.target_frame_rate;
-    let intended_frame_end_time = start_time.add(frame_duration(target_frame_rate));
+    let intended_frame_end_time = start_time.add(frame_duration(nes, config.target_frame_rate));
 
     if let Some((event, button_status)) = config.scheduled_button_events.get(&frame_index) {
         match event {
             Event::Button(button) => _ = events.joypad1_button_statuses.insert(*button, *button_status),
-            Event::Reset => nes.set_reset_signal(),
+            Event::Reset => events.reset = true,
         }
     }
 
+    rewind.record_frame(nes, &events);
     nes.process_gui_events(&events);
     nes.step_frame();
     display_frame(nes.frame(), frame_index);
@@ -53,6 +55,26 @@ where
     }
 }
 
+// Runs emulation back by one frame, at the same pace that frames are run forward.
+// Returns false if there is no recorded history left to rewind into.
+pub fn rewind_frame<F>(nes: &mut Nes, config: &Config, rewind: &mut Rewind, display_frame: F) -> bool
+where
+    F: FnOnce(&Frame, i64),
+{
+    let start_time = SystemTime::now();
+    let intended_frame_end_time = start_time.add(frame_duration(nes, config.target_frame_rate));
+
+    let rewound = rewind.step_back(nes);
+    // The frame that was just rewound to hasn't been run yet, so the previous frame is the one on display.
+    let frame_index = nes.bus().ppu_clock().frame() - 1;
+    if rewound {
+        display_frame(nes.frame(), frame_index);
+    }
+
+    end_frame(frame_index, start_time, intended_frame_end_time);
+    rewound
+}
+
 fn dump_frame(frame: &Frame, frame_index: i64) {
     let mut frame = frame.clone();
     *frame.show_overscan_mut() = true;
@@ -94,17 +116,21 @@ fn end_frame(
     }
 }
 
-fn frame_duration(target_frame_rate: TargetFrameRate) -> Duration {
+fn frame_duration(nes: &Nes, target_frame_rate: TargetFrameRate) -> Duration {
     match target_frame_rate {
         TargetFrameRate::Value(frame_rate) => frame_rate.to_frame_duration(),
+        TargetFrameRate::Console => nes.region().frame_rate().to_frame_duration(),
         TargetFrameRate::Unbounded => Duration::ZERO,
     }
 }
 
+#[derive(Clone)]
 pub struct Events {
     pub should_quit: bool,
     pub joypad1_button_statuses: BTreeMap<Button, ButtonStatus>,
     pub joypad2_button_statuses: BTreeMap<Button, ButtonStatus>,
+    pub reset: bool,
+    pub vs_input_statuses: BTreeMap<VsInput, ButtonStatus>,
 }
 
 impl Events {
@@ -113,6 +139,8 @@ impl Events {
             should_quit: false,
             joypad1_button_statuses: BTreeMap::new(),
             joypad2_button_statuses: BTreeMap::new(),
+            reset: false,
+            vs_input_statuses: BTreeMap::new(),
         }
     }
 }
@@ -2,6 +2,7 @@ pub mod debug_screens;
 pub mod egui_gui;
 pub mod gui;
 pub mod no_gui;
+pub mod rewind;
 pub mod window_renderer;
 pub mod window_renderers;
 pub mod world;
@@ -1,13 +1,26 @@
 use crate::gui::gui::Gui;
 use crate::nes::Nes;
 
-pub struct NoGui;
+pub struct NoGui {
+    stop_frame: Option<i64>,
+}
+
+impl NoGui {
+    pub fn new(stop_frame: Option<i64>) -> Self {
+        Self { stop_frame }
+    }
+}
 
 impl Gui for NoGui {
     fn run(&mut self, nes: Option<Nes>) {
         let mut nes = nes.expect("ROM to be specified when nogui mode is specified.");
         loop {
+            let frame_index = nes.bus().ppu_clock().frame();
             nes.step_frame();
+            if Some(frame_index) == self.stop_frame {
+                nes.stop_audio_recording();
+                return;
+            }
         }
     }
 }
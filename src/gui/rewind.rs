@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use log::warn;
+
+use crate::config::Config;
+use crate::gui::gui::Events;
+use crate::nes::Nes;
+use crate::util::delta_compression;
+
+// Keeps snapshots of the recent past so that emulation can be run backwards.
+// A save state is taken every snapshot_interval frames. Only the newest snapshot is stored in full.
+// Every older snapshot is stored as a delta against the snapshot that followed it, so the oldest
+// snapshot can always be evicted without re-encoding any of the others.
+// The joypad events of every frame are recorded too, so that rewinding can re-simulate forward
+// from the nearest snapshot and land on exactly the requested frame.
+pub struct Rewind {
+    snapshot_interval: u32,
+    max_snapshot_count: usize,
+    memory_budget: usize,
+
+    // Oldest first.
+    snapshots: VecDeque<Snapshot>,
+    memory_used: usize,
+    // The events that were applied at the start of each frame since the oldest snapshot.
+    frame_events: BTreeMap<i64, Events>,
+}
+
+struct Snapshot {
+    // The frame that was about to start when the snapshot was taken.
+    frame: i64,
+    // A full save state for the newest snapshot, a delta against the next snapshot for all others.
+    data: Vec<u8>,
+}
+
+impl Rewind {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            snapshot_interval: config.rewind_snapshot_interval,
+            max_snapshot_count: config.rewind_max_snapshot_count,
+            memory_budget: config.rewind_memory_budget,
+
+            snapshots: VecDeque::new(),
+            memory_used: 0,
+            frame_events: BTreeMap::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.snapshot_interval > 0 && self.max_snapshot_count > 0
+    }
+
+    #[cfg(test)]
+    fn snapshot_count(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.memory_used = 0;
+        self.frame_events.clear();
+    }
+
+    // Must be called at the start of every frame that is run forward, with the events for that frame.
+    pub fn record_frame(&mut self, nes: &Nes, events: &Events) {
+        if !self.enabled() {
+            return;
+        }
+
+        let frame = nes.bus().ppu_clock().frame();
+        if self.snapshots.back().is_some_and(|newest| frame < newest.frame) {
+            // Emulation was moved backwards without going through the rewind buffer, so its history is invalid.
+            self.clear();
+        }
+
+        let snapshot_due = self.snapshots.back()
+            .is_none_or(|newest| frame >= newest.frame + i64::from(self.snapshot_interval));
+        if snapshot_due {
+            match nes.save_state() {
+                Ok(state) => self.push_snapshot(frame, state),
+                Err(err) => {
+                    warn!("Failed to take rewind snapshot. {err}");
+                    return;
+                }
+            }
+        }
+
+        if !self.snapshots.is_empty() {
+            self.frame_events.insert(frame, events.clone());
+        }
+    }
+
+    // Moves emulation back to the start of the previous frame, leaving the previous frame's output
+    // as the current frame. Returns false if the previous frame is older than the oldest snapshot.
+    pub fn step_back(&mut self, nes: &mut Nes) -> bool {
+        let target_frame = nes.bus().ppu_clock().frame() - 1;
+        if self.snapshots.front().is_none_or(|oldest| oldest.frame > target_frame) {
+            return false;
+        }
+
+        while self.snapshots.back().is_some_and(|newest| newest.frame > target_frame) {
+            if let Err(err) = self.pop_newest_snapshot() {
+                warn!("Rewind buffer is corrupt. Clearing it. {err}");
+                self.clear();
+                return false;
+            }
+        }
+
+        let newest = self.snapshots.back().unwrap();
+        if let Err(err) = nes.load_state(&newest.data) {
+            warn!("Failed to load rewind snapshot. Clearing the rewind buffer. {err}");
+            self.clear();
+            return false;
+        }
+
+        for frame in newest.frame..target_frame {
+            let events = self.frame_events.get(&frame).cloned().unwrap_or_else(Events::none);
+            nes.process_gui_events(&events);
+            nes.step_frame();
+        }
+
+        // The target frame and everything after it will be recorded again when emulation runs forward.
+        self.frame_events.split_off(&target_frame);
+        // Audio from the re-simulated frames would otherwise be played back.
+        nes.clear_queued_audio();
+        true
+    }
+
+    fn push_snapshot(&mut self, frame: i64, state: Vec<u8>) {
+        if let Some(previous) = self.snapshots.back_mut() {
+            let delta = delta_compression::encode(&state, &previous.data);
+            self.memory_used = self.memory_used - previous.data.len() + delta.len();
+            previous.data = delta;
+        }
+
+        self.memory_used += state.len();
+        self.snapshots.push_back(Snapshot { frame, data: state });
+
+        // The newest snapshot is always kept, even if it alone is over the memory budget.
+        while self.snapshots.len() > self.max_snapshot_count
+            || (self.memory_used > self.memory_budget && self.snapshots.len() > 1)
+        {
+            let oldest = self.snapshots.pop_front().unwrap();
+            self.memory_used -= oldest.data.len();
+        }
+
+        // Events from before the oldest snapshot can never be replayed.
+        let oldest_frame = self.snapshots.front().unwrap().frame;
+        self.frame_events = self.frame_events.split_off(&oldest_frame);
+    }
+
+    fn pop_newest_snapshot(&mut self) -> Result<(), String> {
+        let newest = self.snapshots.pop_back().unwrap();
+        self.memory_used -= newest.data.len();
+        if let Some(previous) = self.snapshots.back_mut() {
+            let state = delta_compression::decode(&newest.data, &previous.data)?;
+            self.memory_used = self.memory_used - previous.data.len() + state.len();
+            previous.data = state;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::cartridge::header_db::HeaderDb;
+    use crate::config::{GuiType, Opt};
+    use crate::ppu::render::frame_rate::TargetFrameRate;
+
+    use super::*;
+
+    #[test]
+    fn snapshot_taken_every_interval() {
+        assert_eq!(snapshot_count_after_frames(7, 600, 40), 6);
+    }
+
+    #[test]
+    fn oldest_snapshots_evicted() {
+        assert_eq!(snapshot_count_after_frames(2, 3, 10), 3);
+    }
+
+    fn snapshot_count_after_frames(snapshot_interval: u32, max_snapshot_count: usize, frame_count: usize) -> usize {
+        let opt = Opt {
+            gui: GuiType::NoGui,
+            target_frame_rate: TargetFrameRate::Unbounded,
+            disable_audio: true,
+            prevent_saving: true,
+            rewind_snapshot_interval: snapshot_interval,
+            rewind_max_snapshot_count: max_snapshot_count,
+            ..Opt::new(Some(PathBuf::from("tests/roms/mmc3_test/4-scanline_timing.nes")))
+        };
+        let config = Config::new(&opt);
+        let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+        let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+        nes.mute();
+
+        let mut rewind = Rewind::new(&config);
+        for _ in 0..frame_count {
+            rewind.record_frame(&nes, &Events::none());
+            nes.step_frame();
+        }
+
+        rewind.snapshot_count()
+    }
+}
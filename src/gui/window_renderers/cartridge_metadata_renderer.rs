@@ -27,7 +27,7 @@ impl WindowRenderer for CartridgeMetadataRenderer {
         // Explicitly spell out each field so that if a new one is added, a compile error occurs so it becomes apparent that the
         // field needs to be added to the GUI.
         let ResolvedMetadata { mapper_number, submapper_number, name_table_mirroring, has_persistent_memory,
-            full_hash: _, prg_rom_hash: _, chr_rom_hash: _, prg_rom_size, prg_work_ram_size, prg_save_ram_size,
+            full_hash: _, prg_rom_hash: _, chr_rom_hash: _, trainer_hash: _, prg_rom_size, prg_work_ram_size, prg_save_ram_size,
             chr_rom_size, chr_work_ram_size, chr_save_ram_size, console_type, region_timing_mode, miscellaneous_rom_count,
             default_expansion_device, vs } = resolver.resolve();
         let mut vs_hardware_type = None;
@@ -212,6 +212,13 @@ impl WindowRenderer for CartridgeMetadataRenderer {
                         ui.label(metadata.chr_rom_hash().map_or(String::new(), |crc| format!("{crc:X}")));
                     }
                     ui.end_row();
+
+                    ui.label("Trainer CRC");
+                    ui.label("");
+                    for metadata in metadata_sources {
+                        ui.label(metadata.trainer_hash().map_or(String::new(), |crc| format!("{crc:X}")));
+                    }
+                    ui.end_row();
                 });
         });
 
@@ -57,6 +57,7 @@ impl WindowRenderer for CartridgeQueryRenderer {
                         ui.label("Full Hash");
                         ui.label("PRG ROM Hash");
                         ui.label("CHR ROM Hash");
+                        ui.label("Trainer Hash");
                         ui.end_row();
 
                         for (path, metadata) in &self.metadata_by_path {
@@ -68,6 +69,7 @@ impl WindowRenderer for CartridgeQueryRenderer {
                                 full_hash,
                                 prg_rom_hash,
                                 chr_rom_hash,
+                                trainer_hash,
                                 prg_rom_size,
                                 prg_work_ram_size,
                                 prg_save_ram_size,
@@ -135,6 +137,7 @@ impl WindowRenderer for CartridgeQueryRenderer {
                             ui.label(format!("{full_hash:X}"));
                             ui.label(format!("{prg_rom_hash:X}"));
                             ui.label(format!("{chr_rom_hash:X}"));
+                            ui.label(trainer_hash.map_or(String::new(), |hash| format!("{hash:X}")));
                             ui.end_row();
                         }
                     });
@@ -8,7 +8,7 @@ pub struct ControlsRenderer;
 
 impl ControlsRenderer {
     const WIDTH: usize = 220;
-    const HEIGHT: usize = 260;
+    const HEIGHT: usize = 380;
 }
 
 impl WindowRenderer for ControlsRenderer {
@@ -72,6 +72,24 @@ impl WindowRenderer for ControlsRenderer {
                     ui.end_row();
                 });
 
+            ui.add_space(10.0);
+            ui.label("Vs. System:");
+            egui::Grid::new("vs_system_controls")
+                .num_columns(2)
+                .spacing([40.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Insert coin 1");
+                    ui.label("5");
+                    ui.end_row();
+                    ui.label("Insert coin 2");
+                    ui.label("6");
+                    ui.end_row();
+                    ui.label("Service");
+                    ui.label("9");
+                    ui.end_row();
+                });
+
             ui.add_space(10.0);
             ui.label("Shortcuts:");
             egui::Grid::new("shortcuts")
@@ -88,6 +106,12 @@ impl WindowRenderer for ControlsRenderer {
                     ui.label("Reload ROM");
                     ui.label("F12");
                     ui.end_row();
+                    ui.label("Rewind (hold)");
+                    ui.label("Backspace");
+                    ui.end_row();
+                    ui.label("Step back (paused)");
+                    ui.label("Comma");
+                    ui.end_row();
                 });
         });
 
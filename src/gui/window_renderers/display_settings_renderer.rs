@@ -3,6 +3,7 @@ use pixels::Pixels;
 
 use crate::gui::window_renderer::{FlowControl, WindowRenderer};
 use crate::gui::world::World;
+use crate::ppu::ppu_clock::OverclockPosition;
 
 pub struct DisplaySettingsRenderer;
 
@@ -30,6 +31,21 @@ impl WindowRenderer for DisplaySettingsRenderer {
                     .show(ui, |ui| {
                         ui.checkbox(nes.frame_mut().show_overscan_mut(), "Show overscan");
                         ui.end_row();
+                        ui.checkbox(nes.frame_mut().show_all_sprites_mut(), "Remove sprite limit (reduces flicker)");
+                        ui.end_row();
+
+                        let mut overclock = nes.overclock();
+                        ui.add(egui::Slider::new(&mut overclock.extra_scanlines, 0..=1000)
+                            .text("Overclock scanlines (reduces slowdown)"));
+                        ui.end_row();
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut overclock.position, OverclockPosition::PostRender, "Post-render");
+                            ui.radio_value(&mut overclock.position, OverclockPosition::Vblank, "Vblank");
+                        });
+                        ui.end_row();
+                        if overclock != nes.overclock() {
+                            nes.set_overclock(overclock);
+                        }
                     });
             } else {
                 ui.label("Load a ROM to change display settings.");
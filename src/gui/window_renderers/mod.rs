@@ -6,6 +6,7 @@ pub mod display_settings_renderer;
 pub mod layers_renderer;
 pub mod memory_viewer_renderer;
 pub mod name_table_renderer;
+pub mod nsf_player_renderer;
 pub mod pattern_source_renderer;
 pub mod pattern_table_renderer;
 pub mod primary_renderer;
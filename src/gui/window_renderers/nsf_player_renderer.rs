@@ -0,0 +1,100 @@
+use egui::{Button, Context, ScrollArea, Ui};
+use pixels::Pixels;
+use winit::dpi::{PhysicalPosition, Position};
+
+use crate::gui::window_renderer::{FlowControl, WindowRenderer};
+use crate::gui::window_renderers::audio_visualizer::AudioVisualizer;
+use crate::gui::world::World;
+
+pub struct NsfPlayerRenderer;
+
+impl NsfPlayerRenderer {
+    const WIDTH: usize = 360;
+    const HEIGHT: usize = 420;
+}
+
+impl WindowRenderer for NsfPlayerRenderer {
+    fn name(&self) -> String {
+        "NSF Player".to_string()
+    }
+
+    fn ui(&mut self, _ctx: &Context, ui: &mut Ui, world: &mut World) -> FlowControl {
+        let mut result = FlowControl::CONTINUE;
+        let Some(nes) = &mut world.nes else {
+            return result;
+        };
+        let Some(player) = nes.nsf_player() else {
+            return result;
+        };
+
+        let track_count = player.track_count();
+        let current_track = player.track();
+        let playing = player.is_playing();
+        let mut track_to_play = None;
+        let mut stop = false;
+
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            ui.heading(player.title());
+            egui::Grid::new("nsf_info")
+                .num_columns(2)
+                .spacing([40.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("Artist");
+                    ui.label(player.artist());
+                    ui.end_row();
+                    ui.label("Copyright");
+                    ui.label(player.copyright());
+                    ui.end_row();
+                });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.add_enabled(current_track > 0, Button::new("Previous")).clicked() {
+                    track_to_play = Some(current_track - 1);
+                }
+                if ui.add_enabled(current_track + 1 < track_count, Button::new("Next")).clicked() {
+                    track_to_play = Some(current_track + 1);
+                }
+                if ui.add_enabled(playing, Button::new("Stop")).clicked() {
+                    stop = true;
+                }
+                if ui.button("Audio Visualizer").clicked() {
+                    result = FlowControl::spawn_window((
+                        Box::new(AudioVisualizer::new()),
+                        Position::Physical(PhysicalPosition { x: 600, y: 200 }),
+                        2,
+                    ));
+                }
+            });
+            ui.separator();
+
+            ScrollArea::vertical().show(ui, |ui| {
+                for (track, label) in (0..track_count).zip(player.track_labels()) {
+                    if ui.selectable_label(playing && track == current_track, label).clicked() {
+                        track_to_play = Some(track);
+                    }
+                }
+            });
+        });
+
+        if let Some(track) = track_to_play {
+            nes.play_nsf_track(track);
+        } else if stop {
+            nes.stop_nsf();
+        }
+
+        result
+    }
+
+    fn render(&mut self, _world: &mut World, _pixels: &mut Pixels) {
+        // Do nothing yet.
+    }
+
+    fn width(&self) -> usize {
+        Self::WIDTH
+    }
+
+    fn height(&self) -> usize {
+        Self::HEIGHT
+    }
+}
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use egui::containers::menu;
 use egui::{Align2, Button, CentralPanel, Color32, Context, Frame as EguiFrame, Image, Key, KeyboardShortcut, Modifiers, Ui, include_image, vec2};
-use egui_phosphor::regular::{BUG, FOLDER_OPEN, SLIDERS_HORIZONTAL, INFO};
+use egui_phosphor::regular::{BUG, FLOPPY_DISK, FOLDER_OPEN, MUSIC_NOTES, SLIDERS_HORIZONTAL, INFO};
 use egui_file::FileDialog;
 use log::error;
 use pixels::Pixels;
@@ -10,7 +10,7 @@ pub use winit::dpi::{PhysicalPosition, Position};
 
 use crate::cartridge::header_db::HeaderDb;
 use crate::config::Config;
-use crate::gui::gui::{execute_frame, Events};
+use crate::gui::gui::{execute_frame, rewind_frame, Events};
 pub use crate::gui::window_renderer::{FlowControl, WindowRenderer};
 use crate::nes::Nes;
 use crate::gui::window_renderers::audio_visualizer::AudioVisualizer;
@@ -21,6 +21,7 @@ use crate::gui::window_renderers::display_settings_renderer::DisplaySettingsRend
 use crate::gui::window_renderers::layers_renderer::LayersRenderer;
 use crate::gui::window_renderers::memory_viewer_renderer::MemoryViewerRenderer;
 use crate::gui::window_renderers::name_table_renderer::NameTableRenderer;
+use crate::gui::window_renderers::nsf_player_renderer::NsfPlayerRenderer;
 use crate::gui::window_renderers::pattern_source_renderer::PatternSourceRenderer;
 use crate::gui::window_renderers::pattern_table_renderer::PatternTableRenderer;
 use crate::gui::window_renderers::sprites_renderer::SpritesRenderer;
@@ -33,12 +34,19 @@ const MENU_HOVER_BLUE: Color32 = Color32::from_rgb(70, 90, 140);
 const PAUSED_VERMILION_RED: Color32 = Color32::from_rgb(250, 60, 60);
 const OPEN_ROM_SHORTCUT: KeyboardShortcut =
     KeyboardShortcut::new(Modifiers::COMMAND, Key::O);
+// Held down to run backwards.
+const REWIND_KEY: Key = Key::Backspace;
+const STEP_BACK_SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::NONE, Key::Comma);
 
 pub struct PrimaryRenderer {
     pub paused: bool,
+    rewinding: bool,
+    step_back_requested: bool,
     file_dialog: FileDialog,
     load_error: Option<String>,
     cartridge_query_dialog: FileDialog,
+    record_audio_dialog: FileDialog,
 }
 
 fn menu_hover_style(style: &mut egui::Style) {
@@ -61,7 +69,7 @@ impl PrimaryRenderer {
         let nes_file_filter = Box::new(|path: &Path| {
             path.extension()
                 .and_then(|extension| extension.to_str())
-                .is_some_and(|extension| extension.eq_ignore_ascii_case("nes"))
+                .is_some_and(|extension| ["nes", "fds", "nsf", "nsfe", "unf", "unif"].iter().any(|ext| extension.eq_ignore_ascii_case(ext)))
         });
 
         let file_dialog = FileDialog::open_file()
@@ -69,12 +77,18 @@ impl PrimaryRenderer {
             .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0));
         let cartridge_query_dialog = FileDialog::select_folder()
             .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0));
+        let record_audio_dialog = FileDialog::save_file()
+            .default_filename("recording.wav")
+            .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0));
 
         Self {
             paused: false,
+            rewinding: false,
+            step_back_requested: false,
             file_dialog,
             load_error: None,
             cartridge_query_dialog,
+            record_audio_dialog,
         }
     }
 
@@ -93,10 +107,23 @@ impl WindowRenderer for PrimaryRenderer {
         let mut result = FlowControl::CONTINUE;
         let mut menu_open = false;
         let rom_loaded = world.nes.is_some();
+        let disk = world.nes.as_ref()
+            .and_then(|nes| nes.bus().disk.as_ref())
+            .map(|disk| (disk.side_count(), disk.inserted_side()));
+        let nsf_loaded = world.nes.as_ref().is_some_and(|nes| nes.nsf_player().is_some());
+        let recording_audio = world.nes.as_ref().is_some_and(Nes::is_recording_audio);
+        let mut stop_audio_recording = false;
+        let mut eject_disk = false;
+        let mut side_to_insert = None;
 
         if ctx.input_mut(|input| input.consume_shortcut(&OPEN_ROM_SHORTCUT)) {
             self.open_rom_dialog();
         }
+
+        self.rewinding = rom_loaded && ctx.input(|input| input.key_down(REWIND_KEY));
+        if rom_loaded && self.paused && ctx.input_mut(|input| input.consume_shortcut(&STEP_BACK_SHORTCUT)) {
+            self.step_back_requested = true;
+        }
         egui::Panel::top("menubar_container").show_inside(ui, |ui| {
             egui::MenuBar::new()
                 .style(menu_hover_style)
@@ -115,10 +142,56 @@ impl WindowRenderer for PrimaryRenderer {
                             ui.close();
                             self.cartridge_query_dialog.open();
                         }
+
+                        ui.add_enabled_ui(rom_loaded, |ui| {
+                            let label = if recording_audio { "Stop Recording Audio" } else { "Record Audio" };
+                            if ui.button(label).clicked() {
+                                ui.close();
+                                if recording_audio {
+                                    stop_audio_recording = true;
+                                } else {
+                                    self.record_audio_dialog.open();
+                                }
+                            }
+                        });
                     });
 
                     menu_open |= file_menu.inner.is_some();
 
+                    if let Some((side_count, inserted_side)) = disk {
+                        let disk_menu = ui.menu_button(format!("{FLOPPY_DISK} Disk"), |ui| {
+                            if ui.add_enabled(inserted_side.is_some(), Button::new("Eject")).clicked() {
+                                ui.close();
+                                eject_disk = true;
+                            }
+
+                            for side in 0..side_count {
+                                let label = format!("Insert Disk {} Side {}", side / 2 + 1, if side % 2 == 0 { 'A' } else { 'B' });
+                                if ui.add(Button::new(label).selected(inserted_side == Some(side))).clicked() {
+                                    ui.close();
+                                    side_to_insert = Some(side);
+                                }
+                            }
+                        });
+
+                        menu_open |= disk_menu.inner.is_some();
+                    }
+
+                    if nsf_loaded {
+                        let nsf_menu = ui.menu_button(format!("{MUSIC_NOTES} NSF"), |ui| {
+                            if ui.button("Player").clicked() {
+                                ui.close();
+                                result = FlowControl::spawn_window((
+                                    Box::new(NsfPlayerRenderer) as Box<dyn WindowRenderer>,
+                                    Position::Physical(PhysicalPosition { x: 850, y: 360 }),
+                                    2,
+                                ));
+                            }
+                        });
+
+                        menu_open |= nsf_menu.inner.is_some();
+                    }
+
                     let settings_menu = ui.menu_button(format!("{SLIDERS_HORIZONTAL} Settings"), |ui| {
                         ui.add_enabled_ui(rom_loaded, |ui| {
                             if ui.button("Display").clicked() {
@@ -232,6 +305,20 @@ impl WindowRenderer for PrimaryRenderer {
                 });
             });
 
+        if let Some(nes) = &mut world.nes {
+            if eject_disk {
+                nes.eject_disk();
+            }
+
+            if let Some(side) = side_to_insert {
+                nes.insert_disk_side(side);
+            }
+
+            if stop_audio_recording {
+                nes.stop_audio_recording();
+            }
+        }
+
         if menu_open && rom_loaded {
             self.paused = true;
         }
@@ -248,6 +335,7 @@ impl WindowRenderer for PrimaryRenderer {
 
         self.file_dialog.show(ctx);
         self.cartridge_query_dialog.show(ctx);
+        self.record_audio_dialog.show(ctx);
 
         if let Some(load_error) = &self.load_error {
             let mut choose_another_file = false;
@@ -270,7 +358,16 @@ impl WindowRenderer for PrimaryRenderer {
                 let header_db = HeaderDb::load();
                 match load_nes(&header_db, &world.config, rom_path) {
                     Ok(nes) => {
+                        if nes.nsf_player().is_some() {
+                            result = FlowControl::spawn_window((
+                                Box::new(NsfPlayerRenderer) as Box<dyn WindowRenderer>,
+                                Position::Physical(PhysicalPosition { x: 850, y: 360 }),
+                                2,
+                            ));
+                        }
+
                         world.nes = Some(nes);
+                        world.rewind.clear();
                     }
                     Err(err) => {
                         error!("Failed to load ROM {}. {err}", rom_path.to_string_lossy());
@@ -282,6 +379,14 @@ impl WindowRenderer for PrimaryRenderer {
             }
         }
 
+        if self.record_audio_dialog.selected()
+            && let Some(path) = self.record_audio_dialog.path()
+            && let Some(nes) = &mut world.nes
+            && let Err(err) = nes.start_audio_recording(path)
+        {
+            error!("Failed to start recording audio. {err}");
+        }
+
         if self.cartridge_query_dialog.selected() {
             result = FlowControl::spawn_window((
                 Box::new(CartridgeQueryRenderer::new(self.cartridge_query_dialog.directory())) as Box<dyn WindowRenderer>,
@@ -293,7 +398,8 @@ impl WindowRenderer for PrimaryRenderer {
     }
 
     fn render(&mut self, world: &mut World, pixels: &mut Pixels) {
-        if self.paused {
+        let step_back = std::mem::take(&mut self.step_back_requested);
+        if self.paused && !self.rewinding && !step_back {
             return;
         }
 
@@ -302,12 +408,17 @@ impl WindowRenderer for PrimaryRenderer {
         };
 
         if let Some(nes) = &mut world.nes {
-            execute_frame(
-                nes,
-                &world.config,
-                std::mem::replace(&mut world.events, Events::none()),
-                display_frame,
-            );
+            if self.rewinding || step_back {
+                rewind_frame(nes, &world.config, &mut world.rewind, display_frame);
+            } else {
+                execute_frame(
+                    nes,
+                    &world.config,
+                    &mut world.rewind,
+                    std::mem::replace(&mut world.events, Events::none()),
+                    display_frame,
+                );
+            }
         }
     }
 
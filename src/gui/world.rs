@@ -1,8 +1,10 @@
 use crate::{config::Config, nes::Nes};
 use crate::gui::gui::Events;
+use crate::gui::rewind::Rewind;
 
 pub struct World {
     pub nes: Option<Nes>,
     pub config: Config,
     pub events: Events,
+    pub rewind: Rewind,
 }
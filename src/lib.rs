@@ -20,11 +20,17 @@ pub mod config;
 pub mod controller;
 pub mod counter;
 pub mod cpu;
+pub mod emulator;
 pub mod gui;
 pub mod logging;
 pub mod mapper;
 pub mod master_clock;
 pub mod memory;
 pub mod nes;
+pub mod nsf_player;
+pub mod power_on;
 pub mod ppu;
+pub mod region;
+pub mod save_state;
 pub mod util;
+pub mod vs_system;
@@ -333,7 +333,7 @@ impl Formatter for MesenFormatter {
         }
 
         let mut scanline = nes.bus().ppu_clock().scanline() as i16;
-        if scanline == 261 {
+        if nes.bus().ppu_clock().is_on_prerender_scanline() {
             scanline = -1;
         }
 
@@ -27,15 +27,19 @@ mod mapper;
 mod master_clock;
 mod memory;
 pub mod nes;
+mod nsf_player;
+mod power_on;
 mod ppu;
+mod region;
+mod save_state;
 mod util;
+mod vs_system;
 
 use std::panic;
 use std::sync::{Arc, Mutex};
 
 use structopt::StructOpt;
 
-use crate::cartridge::cartridge_metadata::{ConsoleType, TimingMode};
 use crate::cartridge::header_db::HeaderDb;
 use crate::config::{Config, Opt};
 use crate::logging::logger;
@@ -62,13 +66,15 @@ fn main() {
         let cartridge = Nes::load_cartridge(&path)
             .map_err(|err| format!("Failed to start REZNEZ. {err}"))
             .unwrap();
-        let nes = Nes::new(&HeaderDb::load(), &config, &cartridge)
+        let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge)
             .map_err(|err| format!("Failed to start REZNEZ. {err}"))
             .unwrap();
-        assert_eq!(nes.resolved_metadata().console_type, ConsoleType::NesFamiconDendy);
-        assert_eq!(nes.resolved_metadata().miscellaneous_rom_count, 0, "Miscellaneous ROM sections not yet supported.");
-        assert!(matches!(nes.resolved_metadata().region_timing_mode, TimingMode::Ntsc | TimingMode::MultiRegion));
-        assert!(nes.resolved_metadata().vs.is_none());
+        // Only started once the ROM has loaded, so that a failed load doesn't leave an empty recording behind.
+        if let Some(record_audio_path) = &config.record_audio_path {
+            nes.start_audio_recording(record_audio_path)
+                .map_err(|err| format!("Failed to start REZNEZ. {err}"))
+                .unwrap();
+        }
 
         nes
     });
@@ -1,3 +1,4 @@
+pub use serde::{Deserialize, Serialize};
 pub use splitbits::{splitbits, splitbits_named, combinebits, splitbits_then_combine};
 
 pub(in crate::mapper) use crate::bus::Bus;
@@ -25,9 +26,11 @@ pub(in crate::mapper) use crate::ppu::name_table::name_table_mirroring::{NameTab
 pub(in crate::mapper) use crate::ppu::pattern_table_side::PatternTableSide;
 pub(in crate::mapper) use crate::util::unit::KIBIBYTE;
 
+use serde::de::DeserializeOwned;
+
 use crate::memory::ppu::chr_memory::PpuPeek;
 
-pub trait Mapper {
+pub trait Mapper: MapperState {
     // Should be const, but that's not yet allowed by Rust.
     // Every mapper must define a Layout.
     fn layout(&self) -> Layout;
@@ -45,6 +48,9 @@ pub trait Mapper {
         ReadResult::OPEN_BUS
     }
 
+    // Only the NSF player replaces the interrupt vectors ($FFFA-$FFFF), pointing them at its own driver code.
+    fn peek_interrupt_vector(&self, _addr: CpuAddress) -> Option<u8> { None }
+
     // Most mappers don't need to modify the MapperParams before ROM execution begins, but this
     // provides a relief valve for the rare settings that can't be expressed in a Layout.
     fn init_mapper_params(&self, _bus: &mut Bus) {}
@@ -60,8 +66,14 @@ pub trait Mapper {
     fn on_ppu_address_change(&mut self, _bus: &mut Bus, _address: PpuAddress) {}
     // Most mappers don't have bus conflicts.
     fn has_bus_conflicts(&self) -> bool { false }
+    // Most mappers fetch sprite patterns from the same CHR banks as the background.
+    fn has_sprite_specific_chr_banking(&self) -> bool { false }
     // Used for debug screens.
     fn irq_counter_info(&self) -> Option<IrqCounterInfo> { None }
+    // Most mappers don't have expansion audio. On the same scale as the APU's mixed output.
+    fn expansion_audio_sample(&self) -> f32 { 0.0 }
+    // Only a few mapper chips contain their own RAM (which is battery-backed on some boards).
+    fn internal_ram_size(&self) -> u32 { 0 }
 
     // Hack? Only used by MMC5 for overriding. Should be a better way to do this.
     fn ppu_peek(&self, bus: &Bus, address: PpuAddress) -> PpuPeek {
@@ -73,6 +85,24 @@ pub trait Mapper {
     }
 }
 
+// Saves and restores the mapper-specific state (bank registers, IRQ counters, etc.) for save states.
+// Implemented for every mapper that derives Serialize and Deserialize.
+pub trait MapperState {
+    fn save_state(&self) -> Result<Vec<u8>, String>;
+    fn load_state(&mut self, state: &[u8]) -> Result<(), String>;
+}
+
+impl<T: Serialize + DeserializeOwned> MapperState for T {
+    fn save_state(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(self).map_err(|err| format!("Failed to save mapper state. {err}"))
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<(), String> {
+        *self = bincode::deserialize(state).map_err(|err| format!("Failed to load mapper state. {err}"))?;
+        Ok(())
+    }
+}
+
 // This should be in mapper_list.rs instead, but we can't write the supported() method there.
 pub enum LookupResult {
     Supported(Box<dyn Mapper>),
@@ -5,6 +5,7 @@ use crate::cartridge::resolved_metadata::{MetadataResolver, ResolvedMetadata};
 use crate::cartridge::cartridge::Cartridge;
 use crate::mapper::mapper::{Mapper, LookupResult};
 use crate::mapper::mappers as m;
+use crate::mapper::mappers::mapper005::Mmc5Revision;
 use crate::mapper::mappers::mmc1::board::Mmc1BoardError;
 
 pub static MAPPERS_WITHOUT_SUBMAPPER_0: LazyLock<BTreeSet<u16>> = LazyLock::new(|| {
@@ -22,6 +23,11 @@ pub fn lookup_mapper(metadata_resolver: &MetadataResolver, cartridge: &Cartridge
     let sub_number = metadata.submapper_number;
     let cartridge_name = cartridge.name();
 
+    // NSF files are given mapper 31's number, but they are played through a mapper with NSF driver code.
+    if let Some(nsf) = cartridge.nsf() {
+        return Ok(Box::new(m::nsf_mapper::NsfMapper::new(nsf.initial_banks())));
+    }
+
     match try_lookup_mapper(&metadata) {
         LookupResult::Supported(supported_mapper) => Ok(supported_mapper),
         LookupResult::UnassignedMapper =>
@@ -91,7 +97,9 @@ pub fn try_lookup_mapper(metadata: &ResolvedMetadata) -> LookupResult {
         (4, Some(99)) => m::mapper004_rev_a::mapper004_rev_a().supported(),
 
         // MMC5
-        (5, None) => m::mapper005::Mapper005::new().supported(),
+        (5, None) => m::mapper005::Mapper005::new(Mmc5Revision::Mmc5).supported(),
+        // MMC5A doesn't have a submapper assigned to it, despite having extra registers.
+        (5, Some(99)) => m::mapper005::Mapper005::new(Mmc5Revision::Mmc5A).supported(),
         (6, _) => TodoMapper,
 
         // AxROM submappers
@@ -128,17 +136,22 @@ pub fn try_lookup_mapper(metadata: &ResolvedMetadata) -> LookupResult {
         (17, _) => TodoMapper,
         // Jaleco SS 88006
         (18, None) => m::mapper018::Mapper018::default().supported(),
-        // Namco 129 and Namco 163.
-        // (Expansion Audio isn't supported yet, so all submappers are the same for now.)
+        // Namco 129 and Namco 163. Submappers 3 through 5 specify how loud the expansion audio is.
         (19, None | Some(0)) => UnspecifiedSubmapper,
-        // Duplicate of submapper 2.
-        (19, Some(1)) => m::mapper019::Mapper019::new().supported(),
-        (19, Some(2)) => m::mapper019::Mapper019::new().supported(),
-        (19, Some(3)) => m::mapper019::Mapper019::new().supported(),
-        (19, Some(4)) => m::mapper019::Mapper019::new().supported(),
-        (19, Some(5)) => m::mapper019::Mapper019::new().supported(),
-        // Only used for testing Famicom Disk System images, so it's not an actual iNES mapper.
-        (20, _) => UnassignedMapper,
+        // Deprecated. Boards with battery-backed internal RAM. How loud the expansion audio is wasn't
+        // recorded, so use the middle of the known levels rather than silencing it.
+        (19, Some(1)) => m::mapper019::Mapper019::new(Some(16.5)).supported(),
+        // No expansion audio.
+        (19, Some(2)) => m::mapper019::Mapper019::new(None).supported(),
+        // 11.0 to 13.0 dB louder than the APU.
+        (19, Some(3)) => m::mapper019::Mapper019::new(Some(12.0)).supported(),
+        // 16.0 to 17.0 dB louder than the APU.
+        (19, Some(4)) => m::mapper019::Mapper019::new(Some(16.5)).supported(),
+        // 18.0 to 19.5 dB louder than the APU.
+        (19, Some(5)) => m::mapper019::Mapper019::new(Some(18.75)).supported(),
+        // Famicom Disk System. Only used for disk images, so it's not an actual iNES mapper.
+        (20, None) => m::mapper020::Mapper020::new().supported(),
+        (20, Some(_)) => UnassignedMapper,
 
         // Some VRC4 submappers
         (21, None | Some(0)) => UnspecifiedSubmapper,
@@ -159,7 +172,8 @@ pub fn try_lookup_mapper(metadata: &ResolvedMetadata) -> LookupResult {
         // VRC2b
         (23, Some(3)) => m::mapper023_3::mapper023_3().supported(),
 
-        (24, _) => TodoMapper,
+        // VRC6a
+        (24, None) => m::mapper024::mapper024().supported(),
 
         // Some VRC2 and VRC4 submappers
         (25, None | Some(0)) => UnspecifiedSubmapper,
@@ -170,7 +184,8 @@ pub fn try_lookup_mapper(metadata: &ResolvedMetadata) -> LookupResult {
         // VRC2c
         (25, Some(3)) => m::mapper025_3::mapper025_3().supported(),
 
-        (26, None) => TodoMapper,
+        // VRC6b
+        (26, None) => m::mapper026::mapper026().supported(),
         // Duplicate of 23, most likely.
         (27, None) => m::mapper023_1::mapper023_1().supported(),
         // Action 53
@@ -270,7 +285,10 @@ pub fn try_lookup_mapper(metadata: &ResolvedMetadata) -> LookupResult {
         (66, None) => m::mapper066::Mapper066.supported(),
         // Sunsoft-3
         (67, None) => m::mapper067::Mapper067::new().supported(),
-        (68, None) => m::mapper068::Mapper068.supported(),
+        // Sunsoft-4
+        (68, None) => m::mapper068::Mapper068::new(false).supported(),
+        // Nantettatte!! Baseball
+        (68, Some(1)) => m::mapper068::Mapper068::new(true).supported(),
         // Sunsoft FME-7
         (69, None) => m::mapper069::Mapper069::new().supported(),
         // Family Trainer and others
@@ -285,7 +303,8 @@ pub fn try_lookup_mapper(metadata: &ResolvedMetadata) -> LookupResult {
         // FIXME: Implement specific submapper.
         (71, Some(1)) => m::mapper071::Mapper071.supported(),
 
-        (72, _) => TodoMapper,
+        // Jaleco JF-17
+        (72, None) => m::mapper072::MAPPER072.supported(),
         // VRC3
         (73, None) => m::mapper073::Mapper073::new().supported(),
         // Waixing MMC3 clone with CHR RAM redirects
@@ -345,7 +364,8 @@ pub fn try_lookup_mapper(metadata: &ResolvedMetadata) -> LookupResult {
         // Super Fighter III
         (91, Some(1)) => m::mapper091_1::Mapper091_1::new().supported(),
 
-        (92, _) => TodoMapper,
+        // Jaleco JF-19
+        (92, None) => m::mapper092::MAPPER092.supported(),
         // Sunsoft-2 IC on the Sunsoft-3R board
         (93, None) => m::mapper093::Mapper093.supported(),
         // HVC-UN1ROM
@@ -355,7 +375,8 @@ pub fn try_lookup_mapper(metadata: &ResolvedMetadata) -> LookupResult {
         // Irem TAM-S1 (Kaiketsu Yanchamaru)
         (97, None) => m::mapper097::Mapper097.supported(),
         (98, _) => UnassignedMapper,
-        (99, _) => TodoMapper,
+        // Vs. UniSystem
+        (99, None) => m::mapper099::Mapper099.supported(),
         (100, _) => TodoMapper,
         // JF-10 misdump (only Urusei Yatsura - Lum no Wedding Bell)
         (101, None) => m::mapper101::MAPPER101.supported(),
@@ -30,6 +30,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // AxROM
+#[derive(Serialize, Deserialize)]
 pub struct Axrom {
     has_bus_conflicts: bool,
 }
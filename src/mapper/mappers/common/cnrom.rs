@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // CNROM
+#[derive(Serialize, Deserialize)]
 pub struct Cnrom {
     has_bus_conflicts: bool,
 }
@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // CNROM with copy protection
+#[derive(Serialize, Deserialize)]
 pub struct CnromWithChrDisable {
     correct_chip_select_value: u8,
 }
@@ -13,6 +13,7 @@ pub const IRQ_COUNTER: DirectlySetCounter = CounterBuilder::new()
     .build_directly_set_counter();
 
 // Cony itself is not a mapper, but it forms the basis of the mapper 83 submappers.
+#[derive(Serialize, Deserialize)]
 pub struct Cony {
     irq_counter: DirectlySetCounter,
     next_irq_enabled_value: bool,
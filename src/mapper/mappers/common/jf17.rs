@@ -0,0 +1,100 @@
+use crate::mapper::mapper::*;
+use crate::apu::speech_chip::SpeechChip;
+
+const SWITCHABLE_FIRST_LAYOUT: Layout = Layout::builder()
+    .prg_rom_max_size(256 * KIBIBYTE)
+    .prg_layout(&[
+        PrgWindow::new(0x6000, 0x7FFF,  8 * KIBIBYTE, Prg::ABSENT),
+        PrgWindow::new(0x8000, 0xBFFF, 16 * KIBIBYTE, Prg::ROM).switchable(P),
+        PrgWindow::new(0xC000, 0xFFFF, 16 * KIBIBYTE, Prg::ROM).fixed_number(-1),
+    ])
+    .chr_rom_max_size(128 * KIBIBYTE)
+    .chr_layout(&[
+        ChrWindow::new(0x0000, 0x1FFF, 8 * KIBIBYTE, Chr::ROM).switchable(C),
+    ])
+    .fixed_name_table_mirroring()
+    .build();
+
+const SWITCHABLE_LAST_LAYOUT: Layout = Layout::builder()
+    .prg_rom_max_size(256 * KIBIBYTE)
+    .prg_layout(&[
+        PrgWindow::new(0x6000, 0x7FFF,  8 * KIBIBYTE, Prg::ABSENT),
+        PrgWindow::new(0x8000, 0xBFFF, 16 * KIBIBYTE, Prg::ROM).fixed_number(0),
+        PrgWindow::new(0xC000, 0xFFFF, 16 * KIBIBYTE, Prg::ROM).switchable(P),
+    ])
+    .chr_rom_max_size(128 * KIBIBYTE)
+    .chr_layout(&[
+        ChrWindow::new(0x0000, 0x1FFF, 8 * KIBIBYTE, Chr::ROM).switchable(C),
+    ])
+    .fixed_name_table_mirroring()
+    .build();
+
+// Jaleco JF-17 and JF-19 (and similar boards). The only difference between them is which PRG
+// window is switchable. Some games also drive a uPD7756 speech chip from the same register.
+#[derive(Serialize, Deserialize)]
+pub struct Jf17 {
+    switchable_prg_window: SwitchablePrgWindow,
+    prg_latch_high: bool,
+    chr_latch_high: bool,
+}
+
+impl Mapper for Jf17 {
+    fn init_mapper_params(&self, bus: &mut Bus) {
+        bus.speech_chip = Some(SpeechChip::new(bus.master_clock.region()));
+    }
+
+    fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
+        if let Some(speech_chip) = &mut bus.speech_chip {
+            speech_chip.step();
+        }
+    }
+
+    fn has_bus_conflicts(&self) -> bool {
+        true
+    }
+
+    fn write_register(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
+        match *addr {
+            0x0000..=0x401F => unreachable!(),
+            0x4020..=0x7FFF => { /* Do nothing. */ }
+            0x8000..=0xFFFF => {
+                let fields = splitbits!(value, "pcsrbbbb");
+                // The bank number is only copied into a bank register when its latch bit goes high.
+                if fields.p && !self.prg_latch_high {
+                    bus.set_prg_register(P, fields.b);
+                }
+
+                if fields.c && !self.chr_latch_high {
+                    bus.set_chr_register(C, fields.b);
+                }
+
+                self.prg_latch_high = fields.p;
+                self.chr_latch_high = fields.c;
+
+                // The speech clip number comes from the low address lines rather than the data.
+                if let Some(speech_chip) = &mut bus.speech_chip {
+                    speech_chip.set_pins(*addr as u8 & 0x1F, fields.r, fields.s);
+                }
+            }
+        }
+    }
+
+    fn layout(&self) -> Layout {
+        match self.switchable_prg_window {
+            SwitchablePrgWindow::First => SWITCHABLE_FIRST_LAYOUT,
+            SwitchablePrgWindow::Last => SWITCHABLE_LAST_LAYOUT,
+        }
+    }
+}
+
+impl Jf17 {
+    pub const fn new(switchable_prg_window: SwitchablePrgWindow) -> Jf17 {
+        Jf17 { switchable_prg_window, prg_latch_high: false, chr_latch_high: false }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum SwitchablePrgWindow {
+    First,
+    Last,
+}
@@ -25,6 +25,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // MMC3 for scrambled register addresses and indices
+#[derive(Serialize, Deserialize)]
 pub struct Mapper114 {
     mmc3: mmc3::Mapper004Mmc3,
     scrambled_addrs: BTreeMap<u16, u16>,
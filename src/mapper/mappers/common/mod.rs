@@ -4,6 +4,8 @@ pub mod uxrom;
 pub mod cnrom;
 // Mapper 7
 pub mod axrom;
+// Mapper 72 and 92
+pub mod jf17;
 // Mapper 83 and 264
 pub mod cony;
 pub mod mapper114;
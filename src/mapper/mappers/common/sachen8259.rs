@@ -87,6 +87,20 @@ impl Mapper for Sachen8259 {
     }
 }
 
+// The Layout and CHR bank bit arrangement are fixed by the board, so they aren't saved.
+impl MapperState for Sachen8259 {
+    fn save_state(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(&(self.chr_inner_banks, self.register_value))
+            .map_err(|err| format!("Failed to save mapper state. {err}"))
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<(), String> {
+        (self.chr_inner_banks, self.register_value) = bincode::deserialize(state)
+            .map_err(|err| format!("Failed to load mapper state. {err}"))?;
+        Ok(())
+    }
+}
+
 impl Sachen8259 {
     pub const fn new(layout: Layout, board: Sachen8259Board) -> Self {
         // The CHR bank low bits are actually the respective PPU address line bits.
@@ -130,6 +144,7 @@ impl Sachen8259 {
     }
 }
 
+#[derive(Clone, Copy, Serialize, Deserialize)]
 enum RegisterValue {
     ChrSelect(ChrBankRegisterId),
     ChrOuterBank,
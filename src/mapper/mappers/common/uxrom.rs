@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // UxROM (common usages)
+#[derive(Serialize, Deserialize)]
 pub struct Uxrom {
     has_bus_conflicts: bool,
 }
@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // NROM
 // The simplest mapper. Defined entirely by its Layout, it doesn't actually have any custom logic.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper000;
 
 impl Mapper for Mapper000 {
@@ -53,6 +53,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // SxROM (MMC1, MMC1B)
+#[derive(Serialize, Deserialize)]
 pub struct Mapper001_0 {
     board: Board,
     shift_register: ShiftRegister,
@@ -35,7 +35,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // SEROM. MMC1 that doesn't support PRG bank switching.
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper001_5 {
     shift_register: ShiftRegister,
 }
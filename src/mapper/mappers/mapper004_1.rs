@@ -48,7 +48,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // MMC6. Similar to MMC3 with Rev A IRQs, but with Work RAM protection.
-// TODO: Support VS System (and its 4-screen mirroring).
+#[derive(Serialize, Deserialize)]
 pub struct Mapper004_1 {
     selected_register_id: RegId,
     irq_state: Mmc3IrqState,
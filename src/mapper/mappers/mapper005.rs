@@ -6,7 +6,10 @@ use crate::memory::ppu::chr_memory::{PeekSource, PpuPeek};
 use crate::memory::ppu::ppu_address::PpuAddressSection;
 use crate::memory::regions::small_page::SmallPage;
 use crate::memory::window::PrgSource;
+use crate::mapper::mappers::mmc5::audio::Mmc5Audio;
 use crate::mapper::mappers::mmc5::frame_state::FrameState;
+use crate::mapper::mappers::mmc5::mmc5a::Mmc5aRegisters;
+use crate::mapper::mappers::mmc5::vertical_split::VerticalSplit;
 use crate::ppu::constants::NAME_TABLE_SIZE;
 use crate::ppu::name_table::name_table_quadrant::NameTableQuadrant;
 use crate::ppu::sprite::sprite_height::SpriteHeight;
@@ -115,9 +118,8 @@ const EXT_RAM_PAGE_ID: usize = 0;
 const FILL_MODE_TILE_PAGE_ID: usize = 1;
 const EXT_RAM_PEEK_SOURCE: PeekSource = PeekSource::MapperCustom { page_id: EXT_RAM_PAGE_ID as u8 };
 
-// MMC5
-// TODO: Expansion Audio
-// TODO: MMC5A registers
+// MMC5 and MMC5A
+#[derive(Serialize, Deserialize)]
 pub struct Mapper005 {
     ram_enabled_1: bool,
     ram_enabled_2: bool,
@@ -136,6 +138,14 @@ pub struct Mapper005 {
     substitutions_enabled: bool,
     name_table_index: u16,
     upper_chr_bank_bits: u8,
+
+    vertical_split: VerticalSplit,
+    // The split region tile that the current background tile fetches are for, if any.
+    split_tile: Option<SplitTile>,
+
+    audio: Mmc5Audio,
+    // Only present on the MMC5A.
+    mmc5a: Option<Mmc5aRegisters>,
 }
 
 impl Mapper for Mapper005 {
@@ -145,6 +155,12 @@ impl Mapper for Mapper005 {
     }
 
     fn ppu_peek(&self, bus: &Bus, address: PpuAddress) -> PpuPeek {
+        // Whether a tile is in the split region is decided when its name table entry is fetched.
+        let split_tile = if address.is_in_name_table_proper() { self.next_split_tile() } else { self.split_tile };
+        if let Some(split_tile) = split_tile && self.frame_state.in_frame() {
+            return self.peek_split_tile(bus, address, split_tile);
+        }
+
         let should_substitute = self.substitutions_enabled
             && self.extended_ram_mode == ExtendedRamMode::ExtendedAttributes
             && !self.frame_state.sprite_fetching();
@@ -173,17 +189,29 @@ impl Mapper for Mapper005 {
         }
     }
 
-    fn on_cpu_read(&mut self, bus: &mut Bus, addr: CpuAddress, _value: u8) {
+    fn on_cpu_read(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
         match *addr {
+            0x5010 => {
+                self.audio.acknowledge_pcm_irq();
+                self.update_irq(bus);
+            }
             0x5204 => {
-                bus.cpu_pinout.acknowledge_mapper_irq();
                 self.frame_state.acknowledge_irq();
+                self.update_irq(bus);
+            }
+            0x5209 if let Some(mmc5a) = &mut self.mmc5a => {
+                mmc5a.acknowledge_timer_irq();
+                self.update_irq(bus);
+            }
+            0x8000..=0xBFFF => {
+                self.audio.on_pcm_area_read(value);
+                self.update_irq(bus);
             }
             // NMI vector low and high
             0xFFFA | 0xFFFB => {
-                bus.cpu_pinout.acknowledge_mapper_irq();
                 self.frame_state.acknowledge_irq();
                 self.frame_state.force_end_frame();
+                self.update_irq(bus);
             }
             _ => { /* Do nothing. */ }
         }
@@ -205,6 +233,11 @@ impl Mapper for Mapper005 {
     }
 
     fn on_ppu_read(&mut self, bus: &mut Bus, addr: PpuAddress, _value: u8) {
+        if addr.is_in_name_table_proper() {
+            // Latch the split status for the rest of this tile's fetches.
+            self.split_tile = self.next_split_tile();
+        }
+
         self.frame_state.sync_frame_status(addr);
 
         // Syncing the frame status may have switched in or out of special background banking mode.
@@ -219,15 +252,36 @@ impl Mapper for Mapper005 {
         }
     }
 
-    fn on_end_of_cpu_cycle(&mut self, _bus: &mut Bus) {
+    fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
         self.frame_state.maybe_end_frame();
+        self.audio.step();
+        if let Some(mmc5a) = &mut self.mmc5a {
+            mmc5a.step();
+            if mmc5a.timer_irq_pending() {
+                bus.cpu_pinout.assert_mapper_irq();
+            }
+        }
+    }
+
+    fn expansion_audio_sample(&self) -> f32 {
+        self.audio.sample()
+    }
+
+    // 8x16 sprites are fetched from their own set of CHR banks.
+    fn has_sprite_specific_chr_banking(&self) -> bool {
+        self.sprite_height == SpriteHeight::Tall
     }
 
     fn peek_register(&self, bus: &Bus, addr: CpuAddress) -> ReadResult {
         match *addr {
+            0x5010 => ReadResult::partial(self.audio.pcm_status(), 0b1000_0000),
+            0x5015 => ReadResult::partial(self.audio.length_counter_status(), 0b0000_0011),
             0x5204 => ReadResult::full(self.frame_state.to_status_byte()),
             0x5205 => ReadResult::full((u16::from(self.multiplicand) * u16::from(self.multiplier)) as u8),
             0x5206 => ReadResult::full(((u16::from(self.multiplicand) * u16::from(self.multiplier)) >> 8) as u8),
+            0x5208 if let Some(mmc5a) = &self.mmc5a =>
+                ReadResult::partial(mmc5a.pin_status(self.frame_state.irq_pending()), 0b1100_0000),
+            0x5209 if let Some(mmc5a) = &self.mmc5a => ReadResult::partial(mmc5a.timer_status(), 0b1000_0000),
             0x5C00..=0x5FFF => ReadResult::full(Self::peek_ext_rom(bus, *addr - 0x5C00)),
             _ => ReadResult::OPEN_BUS,
         }
@@ -237,7 +291,13 @@ impl Mapper for Mapper005 {
         match *addr {
             0x0000..=0x401F => unreachable!(),
             0x4020..=0x4FFF => { /* Do nothing. */ }
-            0x5000..=0x5015 => { /* TODO: MMC5 audio */ }
+            0x5000..=0x5015 => {
+                self.audio.write_register(*addr, value);
+                // The PCM IRQ may have just been enabled or disabled.
+                if *addr == 0x5010 {
+                    self.update_irq(bus);
+                }
+            }
             0x5016..=0x50FF => { /* Do nothing. */ }
             0x5100 => bus.set_prg_layout(value & 0b11),
             0x5101 => self.set_chr_layout(bus, value),
@@ -287,14 +347,20 @@ impl Mapper for Mapper005 {
             0x512C..=0x512F => { /* Do nothing. */ }
             0x5130 => self.upper_chr_bank_bits = value & 0b11,
             0x5131..=0x51FF => { /* Do nothing. */ }
-            0x5200 => self.enable_vertical_split_mode(value),
-            0x5201 => todo!("Vertical split scroll"),
-            0x5202 => todo!("Vertical split bank"),
+            0x5200 => self.vertical_split.set_mode(value),
+            0x5201 => self.vertical_split.set_scroll(value),
+            0x5202 => self.vertical_split.set_bank(value),
             0x5203 => self.frame_state.set_target_irq_scanline(value),
             0x5204 => self.enable_irq(bus, value),
             0x5205 => self.multiplicand = value,
             0x5206 => self.multiplier = value,
-            0x5207..=0x5BFF => { /* Do nothing. */ }
+            0x5207 if let Some(mmc5a) = &mut self.mmc5a => mmc5a.set_pin_modes(value),
+            0x5208 if let Some(mmc5a) = &mut self.mmc5a => mmc5a.set_pin_data(value),
+            0x5209 if let Some(mmc5a) = &mut self.mmc5a => mmc5a.set_timer_low_byte(value),
+            0x520A if let Some(mmc5a) = &mut self.mmc5a => mmc5a.set_timer_high_byte(value),
+            0x5207..=0x57FF => { /* Do nothing. */ }
+            // The MMC5A decodes this area as a chip select for an external device, but no board connects one.
+            0x5800..=0x5BFF => { /* Do nothing. */ }
             // TODO: ReadWriteStatus
             0x5C00..=0x5FFF => Self::write_ext_rom(bus, *addr - 0x5C00, value),
             0x6000..=0xFFFF => { /* Do nothing. */ }
@@ -311,7 +377,7 @@ impl Mapper for Mapper005 {
 }
 
 impl Mapper005 {
-    pub fn new() -> Self {
+    pub fn new(revision: Mmc5Revision) -> Self {
          Self {
             ram_enabled_1: false,
             ram_enabled_2: false,
@@ -330,6 +396,15 @@ impl Mapper005 {
             substitutions_enabled: false,
             name_table_index: 0,
             upper_chr_bank_bits: 0b0000_0000,
+
+            vertical_split: VerticalSplit::default(),
+            split_tile: None,
+
+            audio: Mmc5Audio::default(),
+            mmc5a: match revision {
+                Mmc5Revision::Mmc5 => None,
+                Mmc5Revision::Mmc5A => Some(Mmc5aRegisters::default()),
+            },
         }
     }
 
@@ -418,23 +493,54 @@ impl Mapper005 {
         self.update_chr_layout(bus);
     }
 
-    // Write 0x5200
-    #[allow(clippy::unused_self)]
-    fn enable_vertical_split_mode(&mut self, value: u8) {
-        let fields = splitbits!(value, "es.ccccc");
-        if fields.e {
-            todo!("Vertical split mode");
-        }
-    }
-
     // Write 0x5204
     fn enable_irq(&mut self, bus: &mut Bus, value: u8) {
         let irq_enabled = value >> 7 == 1;
         self.frame_state.set_irq_enabled(irq_enabled);
-        if !irq_enabled {
-            bus.cpu_pinout.acknowledge_mapper_irq();
-        } else if self.frame_state.irq_pending() {
+        self.update_irq(bus);
+    }
+
+    // The scanline IRQ, the PCM IRQ, and the MMC5A timer IRQ all share the same IRQ line.
+    fn update_irq(&self, bus: &mut Bus) {
+        let timer_irq_pending = self.mmc5a.as_ref().is_some_and(Mmc5aRegisters::timer_irq_pending);
+        if self.frame_state.irq_pending() || self.audio.pcm_irq_pending() || timer_irq_pending {
             bus.cpu_pinout.assert_mapper_irq();
+        } else {
+            bus.cpu_pinout.acknowledge_mapper_irq();
+        }
+    }
+
+    // The split region only replaces the background when ExRAM is being used for name tables.
+    fn next_split_tile(&self) -> Option<SplitTile> {
+        if !matches!(self.extended_ram_mode, ExtendedRamMode::WriteOnly | ExtendedRamMode::ExtendedAttributes) {
+            return None;
+        }
+
+        let (column, scanline) = self.frame_state.next_background_tile()?;
+        if !self.vertical_split.contains(column) {
+            return None;
+        }
+
+        Some(SplitTile { column, pixel_row: self.vertical_split.pixel_row(scanline) })
+    }
+
+    // Split tiles come from ExRAM and the split CHR bank, ignoring the PPU's own scroll.
+    fn peek_split_tile(&self, bus: &Bus, address: PpuAddress, tile: SplitTile) -> PpuPeek {
+        let ext_ram = bus.mapper_custom_pages[EXT_RAM_PAGE_ID].to_raw_ref();
+        let column = usize::from(tile.column);
+        let tile_row = usize::from(tile.pixel_row / 8);
+        if address.is_in_pattern_table() {
+            let row_in_tile = u32::from(tile.pixel_row % 8);
+            let index_in_bank = u32::from(address.to_u16() & 0x0FF8) | row_in_tile;
+            bus.chr_memory().peek_raw(4 * KIBIBYTE * u32::from(self.vertical_split.bank()) + index_in_bank)
+        } else if address.is_in_attribute_table() {
+            let attribute_byte = ext_ram[NAME_TABLE_SIZE as usize + 8 * (tile_row / 4) + column / 4];
+            let palette = (attribute_byte >> (4 * ((tile_row / 2) % 2) + 2 * ((column / 2) % 2))) & 0b11;
+            // The PPU selects the quadrant using its own scroll, so the same palette is used for all 4 corners.
+            let palette_byte = palette << 6 | palette << 4 | palette << 2 | palette;
+            PpuPeek::new(palette_byte, EXT_RAM_PEEK_SOURCE)
+        } else {
+            PpuPeek::new(ext_ram[32 * tile_row + column], EXT_RAM_PEEK_SOURCE)
         }
     }
 
@@ -465,7 +571,19 @@ impl Mapper005 {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Mmc5Revision {
+    Mmc5,
+    // Adds the CL3/SL3 pins and the CPU cycle timer.
+    Mmc5A,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct SplitTile {
+    column: u8,
+    pixel_row: u8,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 enum ExtendedRamMode {
     WriteOnly,
     ExtendedAttributes,
@@ -473,7 +591,7 @@ enum ExtendedRamMode {
     ReadOnly,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 enum ChrWindowMode {
     One8K = 0,
     Two4K = 1,
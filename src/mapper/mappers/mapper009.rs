@@ -24,6 +24,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // MMC2 (PNROM and PEEOROM boards)
+#[derive(Serialize, Deserialize)]
 pub struct Mapper009;
 
 impl Mapper for Mapper009 {
@@ -54,6 +55,11 @@ impl Mapper for Mapper009 {
         bus.set_chr_meta_register(meta_id, bank_register_id);
     }
 
+    // The CHR latches are switched by the sprite pattern fetches themselves.
+    fn has_sprite_specific_chr_banking(&self) -> bool {
+        true
+    }
+
     fn layout(&self) -> Layout {
         LAYOUT
     }
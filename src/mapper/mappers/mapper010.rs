@@ -21,6 +21,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // MMC4 (FxROM) - Similar to MMC2, but with Work RAM, bigger PRG ROM windows, and different bank-switching.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper010;
 
 impl Mapper for Mapper010 {
@@ -51,6 +52,11 @@ impl Mapper for Mapper010 {
         bus.set_chr_meta_register(meta_id, bank_register_id);
     }
 
+    // The CHR latches are switched by the sprite pattern fetches themselves.
+    fn has_sprite_specific_chr_banking(&self) -> bool {
+        true
+    }
+
     fn layout(&self) -> Layout {
         LAYOUT
     }
@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Color Dreams. Same as GxROM except with different register locations.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper011;
 
 impl Mapper for Mapper011 {
@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // CPROM
+#[derive(Serialize, Deserialize)]
 pub struct Mapper013;
 
 impl Mapper for Mapper013 {
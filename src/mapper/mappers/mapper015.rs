@@ -47,6 +47,7 @@ const LAYOUT: Layout = Layout::builder()
 // K-1029 and K-1030P (multicart)
 // See https://www.nesdev.org/w/index.php?title=INES_Mapper_015&oldid=3854 for documentation, the
 // latest version of that page is incomprehensible.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper015;
 
 impl Mapper for Mapper015 {
@@ -28,7 +28,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // FCG-1 ASIC
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper016_4 {
     irq_pending: bool,
     irq_counter_enabled: bool,
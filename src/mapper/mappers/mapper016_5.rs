@@ -30,7 +30,7 @@ const LAYOUT: Layout = Layout::builder()
 // LZ93D50 ASIC
 // FIXME: Dragon Ball Z - Kyoushuu! Saiya Jin (J) freezes after joypad input. Possibly
 // EEPROM-related since it's supposed to be mapper 159, not 16.
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper016_5 {
     irq_pending: bool,
     irq_counter_enabled: bool,
@@ -1,4 +1,5 @@
 use crate::mapper::mapper::*;
+use crate::apu::speech_chip::SpeechChip;
 use crate::bus::Bus;
 
 const LAYOUT: Layout = Layout::builder()
@@ -31,11 +32,10 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Jaleco SS 88006
-// TODO: Expansion Audio
 // TODO: PRG RAM chip enable/disable (remove work_ram_write_enabled)
 // TODO: Verify work_ram_write_enabled = false at power-on.
 // TODO: Replace the custom IRQ counter with a ReloadDrivenCounter.
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper018 {
     work_ram_write_enabled: bool,
 
@@ -46,7 +46,15 @@ pub struct Mapper018 {
 }
 
 impl Mapper for Mapper018 {
+    fn init_mapper_params(&self, bus: &mut Bus) {
+        bus.speech_chip = Some(SpeechChip::new(bus.master_clock.region()));
+    }
+
     fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
+        if let Some(speech_chip) = &mut bus.speech_chip {
+            speech_chip.step();
+        }
+
         // Disch: When enabled, the IRQ counter counts down every CPU cycle.
         //        When it wraps, an IRQ is generated.
         if self.irq_enabled {
@@ -113,7 +121,12 @@ impl Mapper for Mapper018 {
                 bus.cpu_pinout.acknowledge_mapper_irq();
             }
             0xF002 => bus.set_name_table_mirroring(value as u8 & 0b11),
-            0xF003 => todo!("Expansion audio."),
+            0xF003 => {
+                let fields = splitbits!(value as u8, ".cccccsr");
+                if let Some(speech_chip) = &mut bus.speech_chip {
+                    speech_chip.set_pins(fields.c, fields.r, fields.s);
+                }
+            }
             _ => unreachable!(),
         }
     }
@@ -1,4 +1,5 @@
 use crate::mapper::mapper::*;
+use crate::mapper::mappers::namco163::audio::Namco163Audio;
 use crate::memory::register_ids::read_write_status::WriteStatusRegisterId;
 use crate::memory::register_ids::source::ChrSourceRegisterId;
 use crate::memory::regions::ciram::CiramSide;
@@ -44,21 +45,29 @@ const IRQ_COUNTER: DirectlySetCounter = CounterBuilder::new()
     .when_disabled_prevent(WhenDisabledPrevent::CountingAndTriggering)
     .build_directly_set_counter();
 
+// The size of the RAM inside the mapper chip, shared by the audio channels and the CPU.
+const INTERNAL_RAM_SIZE: u32 = 128;
+
 // Namco 129 and Namco 163
 // Needs testing, its IRQ was horribly broken when I found it, but might be fixed now.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper019 {
     irq_counter: DirectlySetCounter,
 
     allow_ciram_in_low_chr: bool,
     allow_ciram_in_high_chr: bool,
+
+    internal_ram_address: u8,
+    internal_ram_auto_increment: bool,
+    audio: Namco163Audio,
 }
 
 impl Mapper for Mapper019 {
-    fn peek_register(&self, _bus: &Bus, addr: CpuAddress) -> ReadResult {
+    fn peek_register(&self, bus: &Bus, addr: CpuAddress) -> ReadResult {
         match *addr {
             0x0000..=0x401F | 0x6000..=0xFFFF => unreachable!(),
             0x4020..=0x47FF => ReadResult::OPEN_BUS,
-            0x4800..=0x4FFF => /* TODO: Expansion Audio */ ReadResult::full(0),
+            0x4800..=0x4FFF => ReadResult::full(bus.mapper_internal_ram[u32::from(self.internal_ram_address)]),
             0x5000..=0x57FF => ReadResult::full(self.irq_counter.count_low_byte()),
             0x5800..=0x5FFF => ReadResult::full(self.irq_counter.count_high_byte()),
         }
@@ -68,7 +77,10 @@ impl Mapper for Mapper019 {
         match *addr {
             0x0000..=0x401F => unreachable!(),
             0x4020..=0x47FF => { /* Do nothing. */ }
-            0x4800..=0x4FFF => { /* TODO: Expansion Audio. */ }
+            0x4800..=0x4FFF => {
+                bus.mapper_internal_ram[u32::from(self.internal_ram_address)] = value;
+                self.increment_internal_ram_address();
+            }
             0x5000..=0x57FF => {
                 bus.cpu_pinout.acknowledge_mapper_irq();
                 self.irq_counter.set_count_low_byte(value);
@@ -100,8 +112,9 @@ impl Mapper for Mapper019 {
             0xD800..=0xDFFF => set_chr_register(bus, true,                         NTS3, N, WS11, value),
             0xE000..=0xE7FF => {
                 // TODO: Pin 22 logic
-                // TODO: Disable sound
-                bus.set_prg_register(P, value & 0b0011_1111);
+                let (sound_disabled, prg_bank) = splitbits_named!(value, ".spppppp");
+                self.audio.set_disabled(sound_disabled);
+                bus.set_prg_register(P, prg_bank);
             }
             0xE800..=0xEFFF => {
                 let fields = splitbits!(value, "hlpp pppp");
@@ -114,6 +127,8 @@ impl Mapper for Mapper019 {
                 bus.set_prg_register(R, value & 0b0011_1111);
             }
             0xF800..=0xFFFF => {
+                (self.internal_ram_auto_increment, self.internal_ram_address) = splitbits_named!(value, "iaaaaaaa");
+
                 let fields = splitbits!(value, "ppppabcd");
                 if fields.p == 0b0100 {
                     bus.set_writes_enabled(WS0, fields.a);
@@ -131,10 +146,26 @@ impl Mapper for Mapper019 {
         }
     }
 
+    fn on_cpu_read(&mut self, _bus: &mut Bus, addr: CpuAddress, _value: u8) {
+        if matches!(*addr, 0x4800..=0x4FFF) {
+            self.increment_internal_ram_address();
+        }
+    }
+
     fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
         if self.irq_counter.tick().triggered {
             bus.cpu_pinout.assert_mapper_irq();
         }
+
+        self.audio.step(&mut bus.mapper_internal_ram);
+    }
+
+    fn expansion_audio_sample(&self) -> f32 {
+        self.audio.sample()
+    }
+
+    fn internal_ram_size(&self) -> u32 {
+        INTERNAL_RAM_SIZE
     }
 
     fn irq_counter_info(&self) -> Option<IrqCounterInfo> {
@@ -166,11 +197,21 @@ fn set_chr_register(
 }
 
 impl Mapper019 {
-    pub fn new() -> Self {
+    // The audio level is how many decibels louder than the APU the expansion audio is. None for boards without audio.
+    pub fn new(audio_level_db: Option<f32>) -> Self {
         Self {
             irq_counter: IRQ_COUNTER,
             allow_ciram_in_low_chr: true,
             allow_ciram_in_high_chr: true,
+            internal_ram_address: 0,
+            internal_ram_auto_increment: false,
+            audio: Namco163Audio::new(audio_level_db),
+        }
+    }
+
+    fn increment_internal_ram_address(&mut self) {
+        if self.internal_ram_auto_increment {
+            self.internal_ram_address = (self.internal_ram_address + 1) % INTERNAL_RAM_SIZE as u8;
         }
     }
 }
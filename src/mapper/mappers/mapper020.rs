@@ -0,0 +1,286 @@
+use crate::mapper::mapper::*;
+
+const LAYOUT: Layout = Layout::builder()
+    // The BIOS.
+    .prg_rom_max_size(8 * KIBIBYTE)
+    .prg_layout(&[
+        PrgWindow::new(0x6000, 0x7FFF, 8 * KIBIBYTE, Prg::RAM_OR_ABSENT).fixed_number(0),
+        PrgWindow::new(0x8000, 0x9FFF, 8 * KIBIBYTE, Prg::RAM_OR_ABSENT).fixed_number(1),
+        PrgWindow::new(0xA000, 0xBFFF, 8 * KIBIBYTE, Prg::RAM_OR_ABSENT).fixed_number(2),
+        PrgWindow::new(0xC000, 0xDFFF, 8 * KIBIBYTE, Prg::RAM_OR_ABSENT).fixed_number(3),
+        PrgWindow::new(0xE000, 0xFFFF, 8 * KIBIBYTE, Prg::ROM).fixed_number(0),
+    ])
+    .chr_rom_max_size(0)
+    .chr_layout(&[
+        ChrWindow::new(0x0000, 0x1FFF, 8 * KIBIBYTE, Chr::RAM),
+    ])
+    .name_table_mirrorings(&[
+        NameTableMirroring::VERTICAL,
+        NameTableMirroring::HORIZONTAL,
+    ])
+    .build();
+
+// How long the motor takes to bring the head back to the start of the disk.
+const HEAD_RETURN_CPU_CYCLES: u32 = 50_000;
+// The drive transfers data at 96.4kHz, so a byte passes under the head about every 149 CPU cycles.
+const BYTE_TRANSFER_CPU_CYCLES: u32 = 149;
+
+// Famicom Disk System RAM adapter (RP2C33). Only used for disk images, never for iNES ROMs.
+// See https://www.nesdev.org/wiki/Family_Computer_Disk_System
+// TODO: Expansion Audio ($4040-$4092).
+#[derive(Serialize, Deserialize, Default)]
+pub struct Mapper020 {
+    disk_registers_enabled: bool,
+
+    timer_reload_value: u16,
+    timer_counter: u16,
+    timer_repeats: bool,
+    timer_enabled: bool,
+    timer_irq_pending: bool,
+
+    disk_irq_enabled: bool,
+    disk_irq_pending: bool,
+    motor_on: bool,
+    transfer_reset: bool,
+    read_mode: bool,
+    crc_control: bool,
+    drive_ready: bool,
+    byte_transferred: bool,
+    read_data: u8,
+    write_data: u8,
+    external_connector: u8,
+
+    head_position: u32,
+    end_of_head: bool,
+    scanning_disk: bool,
+    gap_ended: bool,
+    previous_crc_control: bool,
+    crc: u16,
+    delay: u32,
+}
+
+impl Mapper for Mapper020 {
+    fn peek_register(&self, bus: &Bus, addr: CpuAddress) -> ReadResult {
+        let disk_inserted = bus.disk.as_ref().is_some_and(|disk| disk.inserted_side().is_some());
+        match *addr {
+            0x0000..=0x401F | 0x6000..=0xFFFF => unreachable!(),
+            // The CRC error and end of head bits always read as zero.
+            0x4030 => ReadResult::full(combinebits!(self.byte_transferred, self.timer_irq_pending, "000000ab")),
+            0x4031 => ReadResult::full(self.read_data),
+            0x4032 => {
+                let no_disk = !disk_inserted;
+                let not_ready = no_disk || !self.scanning_disk;
+                // Write protection is only reported when there is no disk, so disks are always writable.
+                let write_protected = no_disk;
+                ReadResult::partial(combinebits!(write_protected, not_ready, no_disk, "00000abc"), 0b0000_0111)
+            }
+            // Nothing is connected to the expansion port, so each input reads back the matching output.
+            // Notably bit 7 reads back high, meaning the battery is good.
+            0x4033 => ReadResult::full(self.external_connector),
+            0x4020..=0x402F | 0x4034..=0x5FFF => ReadResult::OPEN_BUS,
+        }
+    }
+
+    fn on_cpu_read(&mut self, bus: &mut Bus, addr: CpuAddress, _value: u8) {
+        match *addr {
+            0x4030 => {
+                self.byte_transferred = false;
+                self.timer_irq_pending = false;
+                self.disk_irq_pending = false;
+            }
+            0x4031 => {
+                self.byte_transferred = false;
+                self.disk_irq_pending = false;
+            }
+            _ => return,
+        }
+
+        self.update_irq(bus);
+    }
+
+    fn write_register(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
+        match *addr {
+            0x0000..=0x401F => unreachable!(),
+            0x4020 => self.timer_reload_value = (self.timer_reload_value & 0xFF00) | u16::from(value),
+            0x4021 => self.timer_reload_value = (self.timer_reload_value & 0x00FF) | (u16::from(value) << 8),
+            0x4022 => {
+                (self.timer_enabled, self.timer_repeats) = splitbits_named!(value, "......er");
+                self.timer_enabled &= self.disk_registers_enabled;
+                if self.timer_enabled {
+                    self.timer_counter = self.timer_reload_value;
+                } else {
+                    self.timer_irq_pending = false;
+                }
+            }
+            0x4023 => {
+                // TODO: Bit 1 enables the sound registers, once Expansion Audio is supported.
+                self.disk_registers_enabled = value & 1 == 1;
+                if !self.disk_registers_enabled {
+                    self.timer_enabled = false;
+                    self.timer_irq_pending = false;
+                    self.disk_irq_pending = false;
+                }
+            }
+            0x4024 if self.disk_registers_enabled => {
+                self.write_data = value;
+                self.byte_transferred = false;
+                self.disk_irq_pending = false;
+            }
+            0x4025 if self.disk_registers_enabled => {
+                let fields = splitbits!(value, "id.cmwrt");
+                self.disk_irq_enabled = fields.i;
+                self.drive_ready = fields.d;
+                self.crc_control = fields.c;
+                bus.set_name_table_mirroring(fields.m as u8);
+                self.read_mode = fields.w;
+                self.transfer_reset = fields.r;
+                self.motor_on = fields.t;
+                self.disk_irq_pending = false;
+            }
+            0x4026 if self.disk_registers_enabled => self.external_connector = value,
+            0x4024..=0x4026 => { /* Disk registers are disabled. */ }
+            0x4027..=0x5FFF => { /* Do nothing. */ }
+            0x6000..=0xFFFF => { /* RAM and BIOS ROM. */ }
+        }
+
+        self.update_irq(bus);
+    }
+
+    fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
+        self.step_timer();
+        self.step_drive(bus);
+        self.update_irq(bus);
+    }
+
+    fn layout(&self) -> Layout {
+        LAYOUT
+    }
+}
+
+impl Mapper020 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn step_timer(&mut self) {
+        if !self.timer_enabled || !self.disk_registers_enabled {
+            return;
+        }
+
+        if self.timer_counter == 0 {
+            self.timer_irq_pending = true;
+            self.timer_counter = self.timer_reload_value;
+            self.timer_enabled = self.timer_repeats;
+        } else {
+            self.timer_counter -= 1;
+        }
+    }
+
+    fn step_drive(&mut self, bus: &mut Bus) {
+        let Some(disk) = &mut bus.disk else {
+            return;
+        };
+
+        disk.step();
+        if disk.inserted_side().is_none() || !self.motor_on {
+            self.end_of_head = true;
+            self.scanning_disk = false;
+            return;
+        }
+
+        if self.transfer_reset && !self.scanning_disk {
+            return;
+        }
+
+        if self.end_of_head {
+            self.end_of_head = false;
+            self.delay = HEAD_RETURN_CPU_CYCLES;
+            self.head_position = 0;
+            self.gap_ended = false;
+            return;
+        }
+
+        if self.delay > 0 {
+            self.delay -= 1;
+            return;
+        }
+
+        self.scanning_disk = true;
+        let mut transfer_irq = self.disk_irq_enabled;
+        if self.read_mode {
+            let value = disk.read(self.head_position);
+            if !self.drive_ready {
+                self.gap_ended = false;
+                self.crc = 0;
+            } else if value != 0 && !self.gap_ended {
+                // The start mark isn't passed along as data.
+                self.gap_ended = true;
+                transfer_irq = false;
+                self.crc = update_crc(0, value);
+            } else if self.gap_ended {
+                self.read_data = value;
+                self.byte_transferred = true;
+                self.crc = update_crc(self.crc, value);
+            }
+        } else {
+            if !self.crc_control {
+                self.byte_transferred = true;
+            }
+
+            let value = if !self.drive_ready {
+                self.crc = 0;
+                0
+            } else if self.crc_control {
+                transfer_irq = false;
+                // Finish the CRC calculation, then write it out one byte at a time.
+                if !self.previous_crc_control {
+                    self.crc = update_crc(update_crc(self.crc, 0), 0);
+                }
+
+                let [low, high] = self.crc.to_le_bytes();
+                self.crc = u16::from(high);
+                low
+            } else {
+                self.crc = update_crc(self.crc, self.write_data);
+                self.write_data
+            };
+
+            disk.write(self.head_position, value);
+            self.gap_ended = false;
+        }
+
+        self.previous_crc_control = self.crc_control;
+        if transfer_irq && self.byte_transferred {
+            self.disk_irq_pending = true;
+        }
+
+        self.head_position += 1;
+        if self.head_position >= disk.side_length() {
+            self.motor_on = false;
+        } else {
+            self.delay = BYTE_TRANSFER_CPU_CYCLES;
+        }
+    }
+
+    fn update_irq(&self, bus: &mut Bus) {
+        if self.timer_irq_pending || self.disk_irq_pending {
+            bus.cpu_pinout.assert_mapper_irq();
+        } else {
+            bus.cpu_pinout.acknowledge_mapper_irq();
+        }
+    }
+}
+
+// CRC-16/KERMIT, with the bits of each byte entering least significant first.
+fn update_crc(mut crc: u16, value: u8) -> u16 {
+    for i in 0..8 {
+        let bit = (value >> i) & 1 == 1;
+        let carry = crc & 1 == 1;
+        crc = (crc >> 1) | (u16::from(bit) << 15);
+        if carry {
+            crc ^= 0x8408;
+        }
+    }
+
+    crc
+}
@@ -0,0 +1,6 @@
+use crate::mapper::mappers::vrc::vrc6::Vrc6;
+
+// VRC6a - Akumajou Densetsu
+pub fn mapper024() -> Vrc6 {
+    Vrc6::new(false)
+}
@@ -0,0 +1,6 @@
+use crate::mapper::mappers::vrc::vrc6::Vrc6;
+
+// VRC6b - Madara, Esper Dream 2. A0 and A1 are swapped relative to VRC6a.
+pub fn mapper026() -> Vrc6 {
+    Vrc6::new(true)
+}
@@ -107,7 +107,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Action 53
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper028 {
     selected_register: Register,
 }
@@ -167,7 +167,7 @@ impl Mapper for Mapper028 {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 enum Register {
     #[default]
     ChrBank,
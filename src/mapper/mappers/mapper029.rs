@@ -17,6 +17,7 @@ const LAYOUT: Layout = Layout::builder()
 // Homebrew: Sealie Computing RET-CUFROM revD
 // TODO: Reprogramming logic.
 // FIXME: Untested!
+#[derive(Serialize, Deserialize)]
 pub struct Mapper029;
 
 impl Mapper for Mapper029 {
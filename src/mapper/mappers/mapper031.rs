@@ -22,6 +22,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // NSF Music Compilations
+#[derive(Serialize, Deserialize)]
 pub struct Mapper031;
 
 impl Mapper for Mapper031 {
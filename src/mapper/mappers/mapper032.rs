@@ -34,6 +34,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Irem's G-101
+#[derive(Serialize, Deserialize)]
 pub struct Mapper032;
 
 impl Mapper for Mapper032 {
@@ -25,6 +25,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Taito's TC0190
+#[derive(Serialize, Deserialize)]
 pub struct Mapper033;
 
 impl Mapper for Mapper033 {
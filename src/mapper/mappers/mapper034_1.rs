@@ -18,6 +18,7 @@ const LAYOUT: Layout = Layout::builder()
 
 
 // NINA-01
+#[derive(Serialize, Deserialize)]
 pub struct Mapper034_1;
 
 impl Mapper for Mapper034_1 {
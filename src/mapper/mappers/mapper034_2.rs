@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // BNROM (BxROM): Irem I-IM and NES-BNROM boards
+#[derive(Serialize, Deserialize)]
 pub struct Mapper034_2;
 
 impl Mapper for Mapper034_2 {
@@ -17,10 +17,13 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // TXC 01-22000-400
+#[derive(Serialize, Deserialize)]
 pub struct Mapper036 {
     invert_mode: bool,
     increment_mode: bool,
+    #[serde(with = "crate::util::serde_util::ux")]
     rr: u2,
+    #[serde(with = "crate::util::serde_util::ux")]
     pp: u2,
 }
 
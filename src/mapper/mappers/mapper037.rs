@@ -46,6 +46,7 @@ pub const LAYOUT: Layout = Layout::builder()
 
 // Super Mario Bros. + Tetris + Nintendo World Cup
 // FIXME: Graphical glitches on Nintendo World Cup.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper037 {
     mmc3: mmc3::Mapper004Mmc3,
 }
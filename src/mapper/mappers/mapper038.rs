@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Bit Corp.'s Crime Busters
 // TODO: Oversize support
+#[derive(Serialize, Deserialize)]
 pub struct Mapper038;
 
 impl Mapper for Mapper038 {
@@ -2,6 +2,7 @@ use crate::mapper::mapper::*;
 use crate::mapper::mappers::mapper241::Mapper241;
 
 // Identical to mapper 241?
+#[derive(Serialize, Deserialize)]
 pub struct Mapper039 {
     mapper241: Mapper241,
 }
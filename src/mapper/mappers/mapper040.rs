@@ -33,6 +33,7 @@ const IRQ_COUNTER: ReloadDrivenCounter = CounterBuilder::new()
 // NTDEC 2722 and NTDEC 2752 PCB and imitations.
 // Used for conversions of the Japanese version of Super Mario Bros. 2
 // TODO: Test this mapper. The IRQ was broken last time checked, but potential fix was added, just not tested.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper040 {
     irq_counter: ReloadDrivenCounter,
 }
@@ -19,6 +19,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Caltron 6-in-1
 // TODO: Properly model bus conflicts.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper041;
 
 impl Mapper for Mapper041 {
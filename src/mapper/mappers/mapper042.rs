@@ -113,6 +113,18 @@ impl Mapper for Mapper042 {
     }
 }
 
+// The Layout is fixed by the board, so only the IRQ counter needs to be saved.
+impl MapperState for Mapper042 {
+    fn save_state(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(&self.irq_counter).map_err(|err| format!("Failed to save mapper state. {err}"))
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<(), String> {
+        self.irq_counter = bincode::deserialize(state).map_err(|err| format!("Failed to load mapper state. {err}"))?;
+        Ok(())
+    }
+}
+
 pub fn chr_board(metadata: &ResolvedMetadata) -> ChrBoard {
     const CHR_RAM_SIZE: u32 = 8 * KIBIBYTE;
 
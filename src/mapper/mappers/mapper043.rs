@@ -33,6 +33,7 @@ const IRQ_COUNTER: ReloadDrivenCounter = CounterBuilder::new()
 
 // TONY-I and YS-612 (FDS games in cartridge form).
 // TODO: Untested. Need test ROM.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper043 {
     irq_counter: ReloadDrivenCounter,
 }
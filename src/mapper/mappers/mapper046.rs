@@ -15,7 +15,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Rumble Station (Color Dreams).
 // NOTE: Untested.
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper046 {
     prg_high_bits: u8,
     chr_high_bits: u8,
@@ -25,6 +25,7 @@ pub const LAYOUT: Layout = Layout::builder()
     .name_table_mirrorings(mmc3::NAME_TABLE_MIRRORINGS)
     .build();
 
+#[derive(Serialize, Deserialize)]
 pub struct Mapper047 {
     mmc3: mmc3::Mapper004Mmc3,
 }
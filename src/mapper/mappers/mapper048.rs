@@ -28,6 +28,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Taito's TC0690
+#[derive(Serialize, Deserialize)]
 pub struct Mapper048 {
     irq_state: Mmc3IrqState,
 }
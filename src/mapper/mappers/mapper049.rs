@@ -41,6 +41,7 @@ const MODES: [Mode; 2] = [Mode::BigPrgWindow, Mode::NormalMmc3];
 
 // Super HIK 4-in-1
 // FIXME: CHR banking is partially broken for games 1 and 3, and game 0 renders black. The wiki may be incorrect.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper049 {
     mmc3: mmc3::Mapper004Mmc3,
     mode: Mode,
@@ -90,7 +91,7 @@ impl Mapper049 {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 enum Mode {
     BigPrgWindow,
     NormalMmc3,
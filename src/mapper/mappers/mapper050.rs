@@ -30,6 +30,7 @@ const IRQ_COUNTER: ReloadDrivenCounter = CounterBuilder::new()
     .build_reload_driven_counter();
 
 // N-32 conversion of Super Mario Bros. 2 (J). PCB code 761214.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper050 {
     irq_counter: ReloadDrivenCounter,
 }
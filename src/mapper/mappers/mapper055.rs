@@ -21,6 +21,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // BTL-MARIO1-MALEE2
+#[derive(Serialize, Deserialize)]
 pub struct Mapper055;
 
 impl Mapper for Mapper055 {
@@ -36,6 +36,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Unlicensed reproduction of Super Mario Bros. 3
+#[derive(Serialize, Deserialize)]
 pub struct Mapper056 {
     irq_counter: ReloadDrivenCounter,
     selected_prg_bank: Option<PrgBankRegisterId>,
@@ -25,7 +25,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // GK
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper057 {
     inner_bank_left: u8,
     inner_bank_right: u8,
@@ -24,6 +24,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // NROM-/CNROM-based multicarts
+#[derive(Serialize, Deserialize)]
 pub struct Mapper058;
 
 impl Mapper for Mapper058 {
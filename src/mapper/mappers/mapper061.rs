@@ -23,6 +23,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // NTDEC 0324 and GS-2017
+#[derive(Serialize, Deserialize)]
 pub struct Mapper061;
 
 impl Mapper for Mapper061 {
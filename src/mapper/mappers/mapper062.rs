@@ -22,6 +22,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Super 700-in-1
+#[derive(Serialize, Deserialize)]
 pub struct Mapper062;
 
 impl Mapper for Mapper062 {
@@ -23,6 +23,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // TH2291-3 and CH-011
 // TODO: Untested. Test ROM needed.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper063_0;
 
 impl Mapper for Mapper063_0 {
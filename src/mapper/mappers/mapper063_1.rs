@@ -26,6 +26,7 @@ const LAYOUT: Layout = Layout::builder()
 // Same as submapper 1, except there's one less PRG bank bit, and the RAM status bit is moved over
 // to take its place.
 // TODO: Untested. Test ROM needed.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper063_1;
 
 impl Mapper for Mapper063_1 {
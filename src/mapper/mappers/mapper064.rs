@@ -71,6 +71,7 @@ const BANK_NUMBER_REGISTER_IDS: [Option<RegId>; 16] =
     ];
 
 // RAMBO-1 (Similar to MMC3)
+#[derive(Serialize, Deserialize)]
 pub struct Mapper064 {
     selected_register_id: RegId,
 
@@ -243,13 +244,13 @@ impl Mapper064 {
     }
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Serialize, Deserialize)]
 enum IrqCounterReloadMode {
     Scanline,
     CpuCycle,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 enum RegId {
     CHR(ChrBankRegisterId),
     PRG(PrgBankRegisterId),
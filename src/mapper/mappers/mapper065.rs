@@ -50,6 +50,7 @@ const IRQ_COUNTER: ReloadDrivenCounter = CounterBuilder::new()
 const CHR_REGISTER_IDS: [ChrBankRegisterId; 8] = [C, D, E, F, G, H, I, J];
 
 // Irem's H3001
+#[derive(Serialize, Deserialize)]
 pub struct Mapper065 {
     irq_counter: ReloadDrivenCounter,
 }
@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // GxROM
+#[derive(Serialize, Deserialize)]
 pub struct Mapper066;
 
 impl Mapper for Mapper066 {
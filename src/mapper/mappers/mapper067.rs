@@ -35,6 +35,7 @@ const IRQ_COUNTER: DirectlySetCounter = CounterBuilder::new()
     .build_directly_set_counter();
 
 // Sunsoft-3
+#[derive(Serialize, Deserialize)]
 pub struct Mapper067 {
     irq_counter: DirectlySetCounter,
     irq_load_low: bool,
@@ -6,48 +6,88 @@ const LAYOUT: Layout = Layout::builder()
     .prg_layout(&[
         PrgWindow::new(0x6000, 0x7FFF,  8 * KIBIBYTE, Prg::RAM_OR_ABSENT).read_write_status(RS0, WS0),
         PrgWindow::new(0x8000, 0xBFFF, 16 * KIBIBYTE, Prg::ROM).switchable(P),
-        // TODO: The fixed number here may have to change once external ROM is supported.
         PrgWindow::new(0xC000, 0xFFFF, 16 * KIBIBYTE, Prg::ROM).fixed_number(-1),
     ])
     .chr_rom_max_size(256 * KIBIBYTE)
     .override_chr_rom_inner_bank_size(2 * KIBIBYTE)
-    .chr_layout(&[
-        ChrWindow::new(0x0000, 0x07FF, 2 * KIBIBYTE, Chr::ROM).switchable(C),
-        ChrWindow::new(0x0800, 0x0FFF, 2 * KIBIBYTE, Chr::ROM).switchable(D),
-        ChrWindow::new(0x1000, 0x17FF, 2 * KIBIBYTE, Chr::ROM).switchable(E),
-        ChrWindow::new(0x1800, 0x1FFF, 2 * KIBIBYTE, Chr::ROM).switchable(F),
-        ChrWindow::new(0x2000, 0x23FF, 1 * KIBIBYTE, Chr::with_switchable_source(NTS0)).meta_switchable(MR0),
-        ChrWindow::new(0x2400, 0x27FF, 1 * KIBIBYTE, Chr::with_switchable_source(NTS1)).meta_switchable(MR1),
-        ChrWindow::new(0x2800, 0x2BFF, 1 * KIBIBYTE, Chr::with_switchable_source(NTS2)).meta_switchable(MR2),
-        ChrWindow::new(0x2C00, 0x2FFF, 1 * KIBIBYTE, Chr::with_switchable_source(NTS3)).meta_switchable(MR3),
-    ])
+    .chr_layout(CHR_WINDOWS)
     // Vertical mirroring
     .override_chr_meta_register(MR0, NT0)
     .override_chr_meta_register(MR1, NT1)
     .override_chr_meta_register(MR2, NT0)
     .override_chr_meta_register(MR3, NT1)
     .complicated_name_table_mirroring()
-    // TODO: Verify that this hooks up properly with NTS0-NTS3
-    // TODO: Verify that these values are correct.
-    .cartridge_selection_name_table_mirrorings([
-        Some(NameTableMirroring::VERTICAL),
-        Some(NameTableMirroring::HORIZONTAL),
-        Some(NameTableMirroring::ONE_SCREEN_LEFT_BANK),
-        Some(NameTableMirroring::ONE_SCREEN_RIGHT_BANK),
+    .cartridge_selection_name_table_mirrorings(CARTRIDGE_SELECTION_NAME_TABLE_MIRRORINGS)
+    .build();
+
+// The external option ROM (from the miscellaneous ROM section) comes after the 128KiB internal ROM.
+const EXTERNAL_ROM_LAYOUT: Layout = Layout::builder()
+    .prg_rom_max_size(256 * KIBIBYTE)
+    .miscellaneous_rom_extends_prg_rom()
+    .prg_layout(&[
+        PrgWindow::new(0x6000, 0x7FFF,  8 * KIBIBYTE, Prg::RAM_OR_ABSENT).read_write_status(RS0, WS0),
+        PrgWindow::new(0x8000, 0xBFFF, 16 * KIBIBYTE, Prg::ROM).switchable(P),
+        // The last bank of the internal ROM.
+        PrgWindow::new(0xC000, 0xFFFF, 16 * KIBIBYTE, Prg::ROM).fixed_number(7),
     ])
+    .chr_rom_max_size(256 * KIBIBYTE)
+    .override_chr_rom_inner_bank_size(2 * KIBIBYTE)
+    .chr_layout(CHR_WINDOWS)
+    // Vertical mirroring
+    .override_chr_meta_register(MR0, NT0)
+    .override_chr_meta_register(MR1, NT1)
+    .override_chr_meta_register(MR2, NT0)
+    .override_chr_meta_register(MR3, NT1)
+    .complicated_name_table_mirroring()
+    .cartridge_selection_name_table_mirrorings(CARTRIDGE_SELECTION_NAME_TABLE_MIRRORINGS)
     .build();
 
+const CHR_WINDOWS: &[ChrWindow] = &[
+    ChrWindow::new(0x0000, 0x07FF, 2 * KIBIBYTE, Chr::ROM).switchable(C),
+    ChrWindow::new(0x0800, 0x0FFF, 2 * KIBIBYTE, Chr::ROM).switchable(D),
+    ChrWindow::new(0x1000, 0x17FF, 2 * KIBIBYTE, Chr::ROM).switchable(E),
+    ChrWindow::new(0x1800, 0x1FFF, 2 * KIBIBYTE, Chr::ROM).switchable(F),
+    ChrWindow::new(0x2000, 0x23FF, 1 * KIBIBYTE, Chr::with_switchable_source(NTS0)).meta_switchable(MR0),
+    ChrWindow::new(0x2400, 0x27FF, 1 * KIBIBYTE, Chr::with_switchable_source(NTS1)).meta_switchable(MR1),
+    ChrWindow::new(0x2800, 0x2BFF, 1 * KIBIBYTE, Chr::with_switchable_source(NTS2)).meta_switchable(MR2),
+    ChrWindow::new(0x2C00, 0x2FFF, 1 * KIBIBYTE, Chr::with_switchable_source(NTS3)).meta_switchable(MR3),
+];
+
+// TODO: Verify that this hooks up properly with NTS0-NTS3
+// TODO: Verify that these values are correct.
+const CARTRIDGE_SELECTION_NAME_TABLE_MIRRORINGS: [Option<NameTableMirroring>; 4] = [
+    Some(NameTableMirroring::VERTICAL),
+    Some(NameTableMirroring::HORIZONTAL),
+    Some(NameTableMirroring::ONE_SCREEN_LEFT_BANK),
+    Some(NameTableMirroring::ONE_SCREEN_RIGHT_BANK),
+];
+
+// The licensing IC disables the external ROM unless $6000-$7FFF is written to this often.
+const LICENSE_TIMEOUT_CPU_CYCLES: u32 = 107_520;
+
 // Sunsoft-4
-// TODO: Support Nantettatte!! Baseball/external ROM/licensing IC
+// Submapper 1 is Nantettatte!! Baseball, which has a slot for an external option ROM.
 // FIXME: Broken
-pub struct Mapper068;
+#[derive(Serialize, Deserialize)]
+pub struct Mapper068 {
+    has_external_rom: bool,
+    prg_bank: u8,
+    license_cycles_remaining: u32,
+}
 
 impl Mapper for Mapper068 {
     fn write_register(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
         match *addr {
             0x0000..=0x401F => unreachable!(),
             0x4020..=0x5FFF => { /* No regs here. */ }
-            0x6000..=0x7FFF => { /* TODO: Licensing IC. */ }
+            0x6000..=0x7FFF if self.has_external_rom => {
+                let was_licensed = self.license_cycles_remaining > 0;
+                self.license_cycles_remaining = LICENSE_TIMEOUT_CPU_CYCLES;
+                if !was_licensed {
+                    self.update_prg_bank(bus);
+                }
+            }
+            0x6000..=0x7FFF => { /* PRG RAM. */ }
             0x8000..=0x8FFF => bus.set_chr_register(C, value),
             0x9000..=0x9FFF => bus.set_chr_register(D, value),
             0xA000..=0xAFFF => bus.set_chr_register(E, value),
@@ -90,11 +130,44 @@ impl Mapper for Mapper068 {
                 let fields = splitbits!(value, "...e pppp");
                 bus.set_reads_enabled(RS0, fields.e);
                 bus.set_writes_enabled(WS0, fields.e);
+                self.prg_bank = fields.p;
+                self.update_prg_bank(bus);
+            }
+        }
+    }
+
+    fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
+        if self.license_cycles_remaining > 0 {
+            self.license_cycles_remaining -= 1;
+            if self.license_cycles_remaining == 0 {
+                self.update_prg_bank(bus);
             }
         }
     }
 
     fn layout(&self) -> Layout {
-        LAYOUT
+        if self.has_external_rom {
+            EXTERNAL_ROM_LAYOUT
+        } else {
+            LAYOUT
+        }
+    }
+}
+
+impl Mapper068 {
+    pub fn new(has_external_rom: bool) -> Self {
+        Self { has_external_rom, prg_bank: 0, license_cycles_remaining: 0 }
+    }
+
+    fn update_prg_bank(&self, bus: &mut Bus) {
+        let bank = if !self.has_external_rom {
+            self.prg_bank
+        } else if self.prg_bank & 0b1000 == 0 && self.license_cycles_remaining > 0 {
+            // Clearing bit 3 selects the external ROM, which follows the 8 internal banks.
+            0b1000 | (self.prg_bank & 0b0111)
+        } else {
+            self.prg_bank & 0b0111
+        };
+        bus.set_prg_register(P, bank);
     }
 }
@@ -1,4 +1,5 @@
 use crate::mapper::mapper::*;
+use crate::mapper::mappers::sunsoft5b::audio::Sunsoft5bAudio;
 use crate::memory::window::PrgSource;
 
 const LAYOUT: Layout = Layout::builder()
@@ -42,10 +43,15 @@ const CHR_REGISTER_IDS: [ChrBankRegisterId; 8] = [C, D, E, F, G, H, I, J];
 // P0 is used by the ROM/RAM window, which gets special treatment.
 const PRG_ROM_REGISTER_IDS: [PrgBankRegisterId; 3] = [Q, R, S];
 
-// Sunsoft FME-7
+// Sunsoft FME-7, and the Sunsoft 5B which adds expansion audio. Boards without audio ignore the
+// audio registers, so all boards are treated as having it.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper069 {
     irq_counter: DirectlySetCounter,
     command: Command,
+    audio_register: u8,
+    audio_writes_enabled: bool,
+    audio: Sunsoft5bAudio,
 }
 
 impl Mapper for Mapper069 {
@@ -53,6 +59,8 @@ impl Mapper for Mapper069 {
         if self.irq_counter.tick().triggered {
             bus.cpu_pinout.assert_mapper_irq();
         }
+
+        self.audio.step();
     }
 
     fn write_register(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
@@ -100,10 +108,24 @@ impl Mapper for Mapper069 {
                         self.irq_counter.set_count_high_byte(value),
                 }
             }
-            0xC000..=0xFFFF => { /* Do nothing. */ }
+            0xC000..=0xDFFF => {
+                // Selecting a register with any of the top bits set disables audio writes.
+                let (disable_writes, register) = splitbits_named!(value, "ddddrrrr");
+                self.audio_register = register;
+                self.audio_writes_enabled = disable_writes == 0;
+            }
+            0xE000..=0xFFFF => {
+                if self.audio_writes_enabled {
+                    self.audio.write_register(self.audio_register, value);
+                }
+            }
         }
     }
 
+    fn expansion_audio_sample(&self) -> f32 {
+        self.audio.sample()
+    }
+
     fn irq_counter_info(&self) -> Option<IrqCounterInfo> {
         Some(self.irq_counter.to_irq_counter_info())
     }
@@ -119,10 +141,14 @@ impl Mapper069 {
             irq_counter: IRQ_COUNTER,
             // TODO: Verify that this is the correct startup value.
             command: Command::ChrRomBank(C),
+            audio_register: 0,
+            audio_writes_enabled: true,
+            audio: Sunsoft5bAudio::new(),
         }
     }
 }
 
+#[derive(Serialize, Deserialize)]
 enum Command {
     ChrRomBank(ChrBankRegisterId),
     PrgRomRamBank,
@@ -16,6 +16,7 @@ const LAYOUT: Layout = Layout::builder()
     .fixed_name_table_mirroring()
     .build();
 
+#[derive(Serialize, Deserialize)]
 pub struct Mapper070;
 
 impl Mapper for Mapper070 {
@@ -18,6 +18,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Similar to UxROM.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper071;
 
 impl Mapper for Mapper071 {
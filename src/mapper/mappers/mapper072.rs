@@ -0,0 +1,3 @@
+use crate::mapper::mappers::common::jf17::{Jf17, SwitchablePrgWindow};
+
+pub const MAPPER072: Jf17 = Jf17::new(SwitchablePrgWindow::First);
@@ -30,6 +30,7 @@ const IRQ_COUNTER: ReloadDrivenCounter = CounterBuilder::new()
     .build_reload_driven_counter();
 
 // VRC3
+#[derive(Serialize, Deserialize)]
 pub struct Mapper073 {
     low_irq_counter: ReloadDrivenCounter,
     high_irq_counter: ReloadDrivenCounter,
@@ -117,7 +118,7 @@ impl Mapper073 {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 enum IrqMode {
     SixteenBit,
     EightBit,
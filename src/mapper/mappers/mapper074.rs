@@ -40,6 +40,7 @@ const CHR_SOURCE_IDS: [ChrSourceRegisterId; 6] = [CS0, CS1, CS2, CS3, CS4, CS5];
 
 // Waixing MMC3 clone with CHR RAM redirects
 // TODO: Test. The only ROM file is non-NTSC.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper074 {
     mmc3: mmc3::Mapper004Mmc3,
 }
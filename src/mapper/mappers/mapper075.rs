@@ -21,8 +21,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // VRC1
-// TODO: Support VS System (and its 4-screen mirroring).
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper075 {
     chr_left_high_bit: u8,
     chr_right_high_bit: u8,
@@ -39,10 +38,9 @@ impl Mapper for Mapper075 {
 
                 self.chr_right_high_bit = fields.r << 4;
                 self.chr_left_high_bit = fields.l << 4;
-                if matches!(bus.name_table_mirroring(), NameTableMirroring::VERTICAL | NameTableMirroring::HORIZONTAL) {
+                // Hard-coded 4-screen mirroring (used by Vs. System games) cannot be overridden.
+                if !bus.name_table_mirroring().is_four_screen() {
                     bus.set_name_table_mirroring(fields.m);
-                } else {
-                    todo!("Handle four screen mirroring");
                 }
             }
             0xA000..=0xAFFF => bus.set_prg_register(Q, value & 0b0000_1111),
@@ -25,6 +25,7 @@ const BANK_NUMBER_REGISTER_IDS: [Option<RegId>; 8] =
 
 // NAMCOT-3446
 // Similar to Namcot 108, but with only large CHR windows and more PRG and CHR.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper076 {
     selected_register_id: RegId,
 }
@@ -72,7 +73,7 @@ impl Mapper076 {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 enum RegId {
     CHR(ChrBankRegisterId),
     PRG(PrgBankRegisterId),
@@ -24,6 +24,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Irem (Napoleon Senki)
+#[derive(Serialize, Deserialize)]
 pub struct Mapper077;
 
 impl Mapper for Mapper077 {
@@ -18,6 +18,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Uchuusen - Cosmo Carrier
+#[derive(Serialize, Deserialize)]
 pub struct Mapper078_1;
 
 impl Mapper for Mapper078_1 {
@@ -19,6 +19,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Holy Diver
 // Identical to submapper 1 except the nametable mirrorings.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper078_3;
 
 impl Mapper for Mapper078_3 {
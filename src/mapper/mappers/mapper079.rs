@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // NINA-03, NINA-06, and Sachen 3015
+#[derive(Serialize, Deserialize)]
 pub struct Mapper079;
 
 impl Mapper for Mapper079 {
@@ -30,6 +30,7 @@ pub const CHR_LAYOUT: &[ChrWindow] = &[
 ];
 
 // Taito's X1-005
+#[derive(Serialize, Deserialize)]
 pub struct Mapper080;
 
 impl Mapper for Mapper080 {
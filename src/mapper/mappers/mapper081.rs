@@ -16,6 +16,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // NTDEC N715021 (Super Gun)
 // TODO: Untested. Need test ROM.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper081;
 
 impl Mapper for Mapper081 {
@@ -40,6 +40,7 @@ const LAYOUT: Layout = Layout::builder()
 // Taito X1-017
 // TODO: Read back 0 instead of open bus in all cases.
 // TODO: Implement IRQ (even though it's not used in any commercial games).
+#[derive(Serialize, Deserialize)]
 pub struct Mapper082;
 
 impl Mapper for Mapper082 {
@@ -48,6 +48,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Cony with 1 KiB CHR-ROM banking, no PRG work ram (ROM at 0x6000 instead), and no outer banks.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper083_0 {
     cony: Cony,
 }
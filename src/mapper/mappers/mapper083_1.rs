@@ -45,6 +45,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Cony with 2 KiB CHR-ROM banking, no PRG work ram (ROM at 0x6000 instead), and with no outer banks.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper083_1 {
     cony: Cony,
 }
@@ -52,6 +52,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Cony with 1 KiB CHR-ROM banking , and switchable PRG work ram, and PRG and CHR outer banks.
 // FIXME: Flickering pixels on one scanline during the battle sequences. Too far into the game to make a test frame.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper083_2 {
     cony: Cony,
 }
@@ -1,5 +1,6 @@
 use crate::mapper::mapper::*;
 use crate::mapper::mappers::vrc::vrc_irq_state::VrcIrqState;
+use crate::mapper::mappers::vrc::vrc7_audio::Vrc7Audio;
 
 const LAYOUT: Layout = Layout::builder()
     .prg_rom_max_size(512 * KIBIBYTE)
@@ -31,14 +32,16 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Konami VRC7b
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper085_1 {
     irq_state: VrcIrqState,
+    audio: Vrc7Audio,
 }
 
 impl Mapper for Mapper085_1 {
     fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
         self.irq_state.step(bus);
+        self.audio.step();
     }
 
     fn write_register(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
@@ -47,6 +50,8 @@ impl Mapper for Mapper085_1 {
             0x8000 => bus.set_prg_register(P, value & 0b0011_1111),
             0x8008 => bus.set_prg_register(Q, value & 0b0011_1111),
             0x9000 => bus.set_prg_register(R, value & 0b0011_1111),
+            0x9010 => self.audio.select_register(value),
+            0x9030 => self.audio.write_selected_register(value),
             0xA000 => bus.set_chr_register(C, value),
             0xA008 => bus.set_chr_register(D, value),
             0xB000 => bus.set_chr_register(E, value),
@@ -56,8 +61,9 @@ impl Mapper for Mapper085_1 {
             0xD000 => bus.set_chr_register(I, value),
             0xD008 => bus.set_chr_register(J, value),
             0xE000 => {
-                let fields = splitbits!(value, "w.....mm");
+                let fields = splitbits!(value, "ws....mm");
                 bus.set_writes_enabled(WS0, fields.w);
+                self.audio.set_silenced(fields.s);
                 bus.set_name_table_mirroring(fields.m);
             }
             0xE008 => self.irq_state.set_reload_value(value),
@@ -68,6 +74,10 @@ impl Mapper for Mapper085_1 {
         }
     }
 
+    fn expansion_audio_sample(&self) -> f32 {
+        self.audio.sample()
+    }
+
     fn layout(&self) -> Layout {
         LAYOUT
     }
@@ -1,5 +1,6 @@
 use crate::mapper::mapper::*;
 use crate::mapper::mappers::vrc::vrc_irq_state::VrcIrqState;
+use crate::mapper::mappers::vrc::vrc7_audio::Vrc7Audio;
 use crate::bus::Bus;
 
 const LAYOUT: Layout = Layout::builder()
@@ -32,15 +33,16 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Konami VRC7a
-// TODO: Expansion audio.
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper085_2 {
     irq_state: VrcIrqState,
+    audio: Vrc7Audio,
 }
 
 impl Mapper for Mapper085_2 {
     fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
         self.irq_state.step(bus);
+        self.audio.step();
     }
 
     fn write_register(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
@@ -49,7 +51,8 @@ impl Mapper for Mapper085_2 {
             0x8000 => bus.set_prg_register(P, value & 0b0011_1111),
             0x8010 => bus.set_prg_register(Q, value & 0b0011_1111),
             0x9000 => bus.set_prg_register(R, value & 0b0011_1111),
-            0x9010 | 0x9030 => { /* TODO: Expansion Audio */ }
+            0x9010 => self.audio.select_register(value),
+            0x9030 => self.audio.write_selected_register(value),
             0xA000 => bus.set_chr_register(C, value),
             0xA010 => bus.set_chr_register(D, value),
             0xB000 => bus.set_chr_register(E, value),
@@ -59,9 +62,9 @@ impl Mapper for Mapper085_2 {
             0xD000 => bus.set_chr_register(I, value),
             0xD010 => bus.set_chr_register(J, value),
             0xE000 => {
-                // TODO: Silence expansion audio
                 let fields = splitbits!(value, "ws....mm");
                 bus.set_writes_enabled(WS0, fields.w);
+                self.audio.set_silenced(fields.s);
                 bus.set_name_table_mirroring(fields.m);
             }
             0xE010 => self.irq_state.set_reload_value(value),
@@ -72,6 +75,10 @@ impl Mapper for Mapper085_2 {
         }
     }
 
+    fn expansion_audio_sample(&self) -> f32 {
+        self.audio.sample()
+    }
+
     fn layout(&self) -> Layout {
         LAYOUT
     }
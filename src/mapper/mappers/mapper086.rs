@@ -1,4 +1,5 @@
 use crate::mapper::mapper::*;
+use crate::apu::speech_chip::SpeechChip;
 
 const LAYOUT: Layout = Layout::builder()
     .prg_rom_max_size(256 * KIBIBYTE)
@@ -14,9 +15,20 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Jaleco's JF-13
+#[derive(Serialize, Deserialize)]
 pub struct Mapper086;
 
 impl Mapper for Mapper086 {
+    fn init_mapper_params(&self, bus: &mut Bus) {
+        bus.speech_chip = Some(SpeechChip::new(bus.master_clock.region()));
+    }
+
+    fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
+        if let Some(speech_chip) = &mut bus.speech_chip {
+            speech_chip.step();
+        }
+    }
+
     fn write_register(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
         match *addr {
             0x0000..=0x401F => unreachable!(),
@@ -25,7 +37,12 @@ impl Mapper for Mapper086 {
                 bus.set_chr_register(C, banks.c);
                 bus.set_prg_register(P, banks.p);
             }
-            0x7000..=0x7FFF => { /* TODO: Audio control. */ }
+            0x7000..=0x7FFF => {
+                let fields = splitbits!(value, "..srcccc");
+                if let Some(speech_chip) = &mut bus.speech_chip {
+                    speech_chip.set_pins(fields.c, fields.r, fields.s);
+                }
+            }
             _ => { /* Do nothing. */ }
         }
     }
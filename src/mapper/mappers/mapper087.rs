@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Similar to CNROM.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper087;
 
 impl Mapper for Mapper087 {
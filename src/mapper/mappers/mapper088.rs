@@ -30,6 +30,7 @@ const BANK_NUMBER_REGISTER_IDS: [RegId; 8] = [CHR(C), CHR(D), CHR(E), CHR(F), CH
 
 // Similar to Mapper206, but allows up to 128KiB of CHR,
 // and selects the second half of CHR for C2, C3, C4, and C5 for over-sized CHR.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper088 {
     selected_register_id: RegId,
 }
@@ -67,7 +68,7 @@ impl Mapper088 {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 enum RegId {
     CHR(ChrBankRegisterId),
     PRG(PrgBankRegisterId),
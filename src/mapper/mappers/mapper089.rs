@@ -18,6 +18,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Sunsoft (Tenka no Goikenban: Mito Koumon (J))
+#[derive(Serialize, Deserialize)]
 pub struct Mapper089;
 
 impl Mapper for Mapper089 {
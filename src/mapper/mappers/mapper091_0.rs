@@ -35,6 +35,7 @@ const IRQ_COUNTER: ReloadDrivenCounter = CounterBuilder::new()
     .build_reload_driven_counter();
 
 // J.Y. Company JY830623C and YY840238C
+#[derive(Serialize, Deserialize)]
 pub struct Mapper091_0 {
     irq_counter: ReloadDrivenCounter,
     transition_detector: PatternTableTransitionDetector,
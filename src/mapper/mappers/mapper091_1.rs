@@ -39,6 +39,7 @@ const IRQ_COUNTER: ReloadDrivenCounter = CounterBuilder::new()
     .build_reload_driven_counter();
 
 // J.Y. Company JY830623C and YY840238C
+#[derive(Serialize, Deserialize)]
 pub struct Mapper091_1 {
     irq_counter: ReloadDrivenCounter,
 }
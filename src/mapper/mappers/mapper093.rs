@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Sunsoft-2 IC on the Sunsoft-3R board
+#[derive(Serialize, Deserialize)]
 pub struct Mapper093;
 
 impl Mapper for Mapper093 {
@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // UxROM, but the register is shifted by two bits.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper094;
 
 impl Mapper for Mapper094 {
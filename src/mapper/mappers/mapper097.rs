@@ -18,6 +18,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Irem TAM-S1 (Kaiketsu Yanchamaru)
+#[derive(Serialize, Deserialize)]
 pub struct Mapper097;
 
 impl Mapper for Mapper097 {
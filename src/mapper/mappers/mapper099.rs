@@ -0,0 +1,43 @@
+use crate::mapper::mapper::*;
+
+const LAYOUT: Layout = Layout::builder()
+    // Only Vs. Gumshoe has more than 32KiB, with its extra bank switched in place of the first.
+    .prg_rom_max_size(40 * KIBIBYTE)
+    .prg_layout(&[
+        PrgWindow::new(0x6000, 0x7FFF, 8 * KIBIBYTE, Prg::RAM_OR_ABSENT),
+        PrgWindow::new(0x8000, 0x9FFF, 8 * KIBIBYTE, Prg::ROM).switchable(P),
+        PrgWindow::new(0xA000, 0xBFFF, 8 * KIBIBYTE, Prg::ROM).fixed_number(1),
+        PrgWindow::new(0xC000, 0xDFFF, 8 * KIBIBYTE, Prg::ROM).fixed_number(2),
+        PrgWindow::new(0xE000, 0xFFFF, 8 * KIBIBYTE, Prg::ROM).fixed_number(3),
+    ])
+    .chr_rom_max_size(16 * KIBIBYTE)
+    .chr_layout(&[
+        ChrWindow::new(0x0000, 0x1FFF, 8 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(C),
+    ])
+    .fixed_name_table_mirroring()
+    .build();
+
+// Vs. UniSystem. Banks are switched by the same write that strobes the controllers.
+#[derive(Serialize, Deserialize)]
+pub struct Mapper099;
+
+impl Mapper for Mapper099 {
+    fn write_register(&mut self, _bus: &mut Bus, addr: CpuAddress, _value: u8) {
+        match *addr {
+            0x0000..=0x401F => unreachable!(),
+            0x4020..=0xFFFF => { /* Do nothing. */ }
+        }
+    }
+
+    fn on_cpu_write(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
+        if *addr == 0x4016 {
+            let bank = splitbits_named!(min=u8, value, ".....b..");
+            bus.set_chr_register(C, bank);
+            bus.set_prg_register(P, if bank == 0 { 0u8 } else { 4 });
+        }
+    }
+
+    fn layout(&self) -> Layout {
+        LAYOUT
+    }
+}
@@ -27,6 +27,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Doki Doki Panic (pirate port of the FDS version)
 // FIXME: Sub-8KiB bank size doesn't work yet, so this mapper is broken.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper103;
 
 impl Mapper for Mapper103 {
@@ -16,7 +16,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // PEGASUS 5 IN 1 (Golden Five)
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper104 {
     lock_outer_bank: bool,
 }
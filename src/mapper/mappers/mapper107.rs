@@ -16,6 +16,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Magic Dragon
+#[derive(Serialize, Deserialize)]
 pub struct Mapper107;
 
 impl Mapper for Mapper107 {
@@ -16,6 +16,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Cheapocabra or GTROM
+#[derive(Serialize, Deserialize)]
 pub struct Mapper111;
 
 impl Mapper for Mapper111 {
@@ -30,6 +30,7 @@ const BANK_REGISTER_IDS: [RegId; 8] = [PRG(P), PRG(Q), CHR(C), CHR(D), CHR(E), C
 // Huang Di and San Guo Zhi - Qun Xiong Zheng Ba
 // Similar to mapper 206.
 // FIXME: Currently jams, possibly due to broken DMC implementation.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper112 {
     selected_register_id: RegId,
 }
@@ -60,7 +61,7 @@ impl Mapper112 {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 enum RegId {
     PRG(PrgBankRegisterId),
     CHR(ChrBankRegisterId),
@@ -17,6 +17,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // NTD-8 (extended PRG and CHR from NINA-03 and NINA-06)
+#[derive(Serialize, Deserialize)]
 pub struct Mapper113;
 
 impl Mapper for Mapper113 {
@@ -38,6 +38,7 @@ const IRQ_COUNTER: ReloadDrivenCounter = CounterBuilder::new()
     .build_reload_driven_counter();
 
 // Future Media
+#[derive(Serialize, Deserialize)]
 pub struct Mapper117 {
     irq_counter: ReloadDrivenCounter,
     transition_detector: PatternTableTransitionDetector,
@@ -15,6 +15,7 @@ pub const LAYOUT: Layout = Layout::builder()
     .build();
 
 // TxSROM
+#[derive(Serialize, Deserialize)]
 pub struct Mapper118 {
     mmc3: mmc3::Mapper004Mmc3,
 }
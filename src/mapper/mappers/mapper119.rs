@@ -33,6 +33,7 @@ pub const LAYOUT: Layout = Layout::builder()
 const ROM_RAM_REGISTER_IDS: [ChrSourceRegisterId; 6] = [CS0, CS1, CS2, CS3, CS4, CS5];
 
 // TQROM
+#[derive(Serialize, Deserialize)]
 pub struct Mapper119 {
     mmc3: mmc3::Mapper004Mmc3,
 }
@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Whirlwind Manu LH15 (FDS Conversions)
 // TODO: Test (no test ROM readily available)
+#[derive(Serialize, Deserialize)]
 pub struct Mapper120;
 
 impl Mapper for Mapper120 {
@@ -36,6 +36,7 @@ const PROTECTION_VALUES: [u8; 4] = [0x83, 0x83, 0x40, 0x00];
 // FIXME: Coins can't be collected in Sonic & Knuckles
 // TODO: Support A9713
 // TODO: Support special banking for 512KiB CHR games.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper121 {
     mmc3: mmc3::Mapper004Mmc3,
     board: Board,
@@ -154,7 +155,7 @@ impl Mapper121 {
     }
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Serialize, Deserialize)]
 pub enum Board {
     A9711,
     A9713,
@@ -26,6 +26,7 @@ const LAYOUT: Layout = Layout::builder()
 const SCRAMBLE: [u8; 8] = [0, 3, 1, 5, 6, 7, 2, 4];
 
 // Kǎshèng H2288
+#[derive(Serialize, Deserialize)]
 pub struct Mapper123 {
     mmc3: mmc3::Mapper004Mmc3,
     mode: Mode,
@@ -72,6 +73,7 @@ impl Mapper123 {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 enum Mode {
     Mmc3,
     Nrom { layout_index: u8 },
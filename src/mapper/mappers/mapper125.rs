@@ -18,6 +18,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Monty on the Run (Whirlwind Manu's FDS conversion)
 // FIXME: Untested due to lack of test ROM
+#[derive(Serialize, Deserialize)]
 pub struct Mapper125;
 
 impl Mapper for Mapper125 {
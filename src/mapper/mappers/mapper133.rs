@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Sachen 3009
+#[derive(Serialize, Deserialize)]
 pub struct Mapper133;
 
 impl Mapper for Mapper133 {
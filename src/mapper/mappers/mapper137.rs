@@ -37,6 +37,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Sachen 8259D
+#[derive(Serialize, Deserialize)]
 pub struct Mapper137 {
     selected_reg: Register,
 }
@@ -98,6 +99,7 @@ impl Mapper137 {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 enum Register {
     ChrLow(ChrBankRegisterId),
     ChrHigh,
@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Same as GNROM, except the writable port is moved to 0x6000 and more CHR banks are allowed.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper140;
 
 impl Mapper for Mapper140 {
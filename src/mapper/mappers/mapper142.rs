@@ -22,6 +22,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Kaiser KS202 (UNL-KS7032)
 // Similar to VRC3.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper142 {
     irq_counter: ReloadDrivenCounter,
     selected_prg_bank: Option<PrgBankRegisterId>,
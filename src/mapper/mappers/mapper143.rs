@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // NROM circuit board with simple copy protection
+#[derive(Serialize, Deserialize)]
 pub struct Mapper143;
 
 impl Mapper for Mapper143 {
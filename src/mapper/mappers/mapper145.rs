@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // SA-72007 - Sidewinder
+#[derive(Serialize, Deserialize)]
 pub struct Mapper145;
 
 impl Mapper for Mapper145 {
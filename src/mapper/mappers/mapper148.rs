@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Sachen SA-008-A and Tengen 800008
+#[derive(Serialize, Deserialize)]
 pub struct Mapper148;
 
 impl Mapper for Mapper148 {
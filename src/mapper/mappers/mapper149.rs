@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // SA-0036 - Taiwan Mahjong 16
+#[derive(Serialize, Deserialize)]
 pub struct Mapper149;
 
 impl Mapper for Mapper149 {
@@ -26,9 +26,10 @@ const LAYOUT: Layout = Layout::builder()
 
 // Sachen SA-015 and SA-630
 // Uses the dip switch to modify the CPU data bus behavior on register reads and writes.
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper150 {
     selected_reg_type: RegisterType,
+    #[serde(with = "crate::util::serde_util::ux_array")]
     regs: [u3; 8],
 }
 
@@ -79,7 +80,7 @@ impl Mapper for Mapper150 {
     }
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
 pub enum RegisterType {
     #[default]
     Dummy0,
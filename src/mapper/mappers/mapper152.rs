@@ -28,6 +28,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Similar to Mapper070, but with one screen mirroring control.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper152;
 
 impl Mapper for Mapper152 {
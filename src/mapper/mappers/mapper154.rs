@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // NAMCOT-3453. Same as Mapper088, except adds a name table mirroring selection bit.
 // FIXME: Devil Man scanline flickering.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper154 {
     mapper088: Mapper088,
 }
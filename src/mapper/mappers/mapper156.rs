@@ -34,6 +34,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // DAOU ROM Controller DIS23C01 DAOU 245
+#[derive(Serialize, Deserialize)]
 pub struct Mapper156;
 
 impl Mapper for Mapper156 {
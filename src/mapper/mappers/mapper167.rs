@@ -41,7 +41,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Subor
 // TODO: Testing. Need to support non-NTSC.
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper167 {
     left_bits: u8,
     right_bits: u8,
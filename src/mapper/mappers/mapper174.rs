@@ -24,6 +24,7 @@ const LAYOUT: Layout = Layout::builder()
 // NTDec 5-in-1
 // TODO: Test. Need a test ROM.
 // TODO: Retain CPU-internal RAM contents upon RESET.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper174;
 
 impl Mapper for Mapper174 {
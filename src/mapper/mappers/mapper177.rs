@@ -17,6 +17,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // BxROM with WorkRam and mirroring control.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper177;
 
 impl Mapper for Mapper177 {
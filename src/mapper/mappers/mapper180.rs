@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // UNROM, but the fixed bank and the switchable bank are swapped.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper180;
 
 impl Mapper for Mapper180 {
@@ -32,6 +32,7 @@ const LAYOUT: Layout = Layout::builder()
     ])
     .build();
 
+#[derive(Serialize, Deserialize)]
 pub struct Mapper183 {
     vrc4e: vrc4::Vrc4,
 }
@@ -15,6 +15,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Sunsoft-1
+#[derive(Serialize, Deserialize)]
 pub struct Mapper184;
 
 impl Mapper for Mapper184 {
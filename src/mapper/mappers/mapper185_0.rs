@@ -14,7 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // CNROM with CHR disable
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper185_0 {
     ppu_data_read_count: u8,
 }
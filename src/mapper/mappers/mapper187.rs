@@ -28,6 +28,7 @@ pub const LAYOUT: Layout = Layout::builder()
 // TODO: Test 512KiB CHR right side pattern table.
 // TODO: Correct values for 0x5000 protection read?
 // TODO: Figure out why Mesen has an interrupt at Cycle:658782 of Street Fighter Zero 2 but REZNEZ doesn't.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper187 {
     mmc3: mmc3::Mapper004Mmc3,
     prg_layout_mode: PrgLayoutMode,
@@ -96,7 +97,7 @@ impl Mapper187 {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 enum PrgLayoutMode {
     Mmc3,
     Nrom(u8),
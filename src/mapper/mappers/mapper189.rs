@@ -17,6 +17,7 @@ pub const LAYOUT: Layout = Layout::builder()
     .build();
 
 // TXC-PT8154
+#[derive(Serialize, Deserialize)]
 pub struct Mapper189 {
     mmc3: mmc3::Mapper004Mmc3,
 }
@@ -26,6 +26,7 @@ const LAYOUT: Layout = Layout::builder()
 const CHR_IDS: [ChrBankRegisterId; 4] = [C, D, E, F];
 
 // Magic Kid Googoo by Zemina
+#[derive(Serialize, Deserialize)]
 pub struct Mapper190;
 
 impl Mapper for Mapper190 {
@@ -24,6 +24,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // NTDEC's TC-112
+#[derive(Serialize, Deserialize)]
 pub struct Mapper193;
 
 impl Mapper for Mapper193 {
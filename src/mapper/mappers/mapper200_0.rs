@@ -19,6 +19,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // NROM-128 multicarts with 16 PRG/CHR banks
+#[derive(Serialize, Deserialize)]
 pub struct Mapper200_0;
 
 impl Mapper for Mapper200_0 {
@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // NROM-256 multicarts
+#[derive(Serialize, Deserialize)]
 pub struct Mapper201;
 
 impl Mapper for Mapper201 {
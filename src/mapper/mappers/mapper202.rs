@@ -26,6 +26,7 @@ const PRG16: u8 = 0;
 const PRG32: u8 = 1;
 
 // 150-in-1 pirate cart
+#[derive(Serialize, Deserialize)]
 pub struct Mapper202;
 
 impl Mapper for Mapper202 {
@@ -16,6 +16,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // 35-in-1
+#[derive(Serialize, Deserialize)]
 pub struct Mapper203;
 
 impl Mapper for Mapper203 {
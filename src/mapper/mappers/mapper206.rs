@@ -26,6 +26,7 @@ const BANK_NUMBER_REGISTER_IDS: [RegId; 8] = [CHR(C), CHR(D), CHR(E), CHR(F), CH
 
 // DxROM, Tengen MIMIC-1, Namco 118
 // A much simpler predecessor to MMC3.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper206 {
     selected_register_id: RegId,
 }
@@ -59,7 +60,7 @@ impl Mapper206 {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 enum RegId {
     CHR(ChrBankRegisterId),
     PRG(PrgBankRegisterId),
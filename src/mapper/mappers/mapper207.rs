@@ -11,6 +11,7 @@ pub const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Taito's X1-005 (alternate name table mirrorings)
+#[derive(Serialize, Deserialize)]
 pub struct Mapper207 {
     mapper080: mapper080::Mapper080,
 }
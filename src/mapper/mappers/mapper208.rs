@@ -47,6 +47,7 @@ const PROTECTION_TABLE: [u8; 256] = [
 ];
 
 // Street Fighter IV
+#[derive(Serialize, Deserialize)]
 pub struct Mapper208 {
     mmc3: mmc3::Mapper004Mmc3,
     protection_registers: [u8; 4],
@@ -179,6 +179,7 @@ const ONE: NonZeroI8 = NonZeroI8::new(1).unwrap();
 const NEGATIVE_ONE: NonZeroI8 = NonZeroI8::new(-1).unwrap();
 
 // Standard J.Y. Company ASIC (512KiB outer bank size)
+#[derive(Serialize, Deserialize)]
 pub struct Mapper209 {
     irq_counter: DirectlySetCounter,
     irq_ticked_by: IrqTickedBy,
@@ -431,6 +432,11 @@ impl Mapper for Mapper209 {
         bus.set_chr_meta_register(meta_id, bank_register_id);
     }
 
+    // The CHR latches are switched by the sprite pattern fetches themselves.
+    fn has_sprite_specific_chr_banking(&self) -> bool {
+        true
+    }
+
     fn layout(&self) -> Layout {
         LAYOUT
     }
@@ -460,7 +466,7 @@ impl Mapper209 {
     }
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Serialize, Deserialize)]
 enum IrqTickedBy {
     CpuCycle,
     ChangedToRightSidePatternTable,
@@ -468,7 +474,7 @@ enum IrqTickedBy {
     CpuWrite,
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Serialize, Deserialize)]
 enum RomNameTableMode {
     Disabled,
     SelectionsEnabled,
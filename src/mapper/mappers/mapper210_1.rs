@@ -25,6 +25,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Namco 175
+#[derive(Serialize, Deserialize)]
 pub struct Mapper210_1;
 
 impl Mapper for Mapper210_1 {
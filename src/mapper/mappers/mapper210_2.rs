@@ -30,6 +30,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Namco 340
 // TODO: Untested! Need relevant ROMs to test against (everything is mapper 19 instead).
+#[derive(Serialize, Deserialize)]
 pub struct Mapper210_2;
 
 impl Mapper for Mapper210_2 {
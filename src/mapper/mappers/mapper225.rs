@@ -26,8 +26,9 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // ET-4310 and K-1010
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper225 {
+    #[serde(with = "crate::util::serde_util::ux_array")]
     ram: [u4; 4],
 }
 
@@ -23,6 +23,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // 76-in-1 and other multicarts
+#[derive(Serialize, Deserialize)]
 pub struct Mapper226;
 
 impl Mapper for Mapper226 {
@@ -27,6 +27,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Active Enterprises
 // TODO: Outer bank 2 (chip 2) doesn't exist and should read back as open bus. Currently it is just a mirroring of chip 3.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper228;
 
 impl Mapper for Mapper228 {
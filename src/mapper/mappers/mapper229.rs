@@ -23,6 +23,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // BMC 31-IN-1
 // Untested. Need test ROM.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper229;
 
 impl Mapper for Mapper229 {
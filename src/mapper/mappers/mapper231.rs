@@ -18,6 +18,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // 20-in-1
+#[derive(Serialize, Deserialize)]
 pub struct Mapper231;
 
 impl Mapper for Mapper231 {
@@ -16,6 +16,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Camerica/Codemasters/Quattro
+#[derive(Serialize, Deserialize)]
 pub struct Mapper232;
 
 impl Mapper for Mapper232 {
@@ -28,6 +28,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Weird Super 42-in-1
 // Untested. Confused documentation. Super 42-in-1 is too big for the mapper as documented.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper233;
 
 impl Mapper for Mapper233 {
@@ -19,7 +19,7 @@ const LAYOUT: Layout = Layout::builder()
 const MODES: [Mode; 2] = [Mode::Cnrom, Mode::Nina03];
 
 // Maxi 15 multicart
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper234 {
     mode: Mode,
     rom_side: u8,
@@ -89,7 +89,7 @@ impl Mapper234 {
     }
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
 enum Mode {
     #[default]
     Cnrom,
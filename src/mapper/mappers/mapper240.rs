@@ -13,6 +13,7 @@ const LAYOUT: Layout = Layout::builder()
     .fixed_name_table_mirroring()
     .build();
 
+#[derive(Serialize, Deserialize)]
 pub struct Mapper240;
 
 impl Mapper for Mapper240 {
@@ -14,6 +14,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // BxROM with WorkRam
+#[derive(Serialize, Deserialize)]
 pub struct Mapper241;
 
 impl Mapper for Mapper241 {
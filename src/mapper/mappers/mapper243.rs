@@ -23,7 +23,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // Sachen SA-020A
 // FIXME: The wiki documentation is incorrect on this, leading to a garbled title page. Research the correct behavior.
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 pub struct Mapper243 {
     reg_number: usize,
     regs: [u8; 8],
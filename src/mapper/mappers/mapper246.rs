@@ -26,6 +26,7 @@ const LAYOUT: Layout = Layout::builder()
 // G0151-1
 // TODO: "When reading from CPU address $FFE4-$FFE7, $FFEC-$FFEF, $FFF4-$FFF7, or $FFFC-$FFFF,
 //        PRG A17 is forced high, as if register $6003 were OR'd with $10."
+#[derive(Serialize, Deserialize)]
 pub struct Mapper246;
 
 impl Mapper for Mapper246 {
@@ -49,6 +49,7 @@ const LAYOUT: Layout = Layout::builder()
     .build();
 
 // Very similar to other Cony mappers, but different enough that sharing most code isn't worth it.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper264 {
     irq_counter: DirectlySetCounter,
     next_irq_enabled_value: bool,
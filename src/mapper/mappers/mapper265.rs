@@ -34,6 +34,7 @@ const LAYOUT: Layout = Layout::builder()
 
 // T-262 multicarts
 // TODO: Test me. Test ROM needed.
+#[derive(Serialize, Deserialize)]
 pub struct Mapper265 {
     address_latch_locked: bool,
 }
@@ -1,9 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::cartridge::resolved_metadata::ResolvedMetadata;
 use crate::util::unit::KIBIBYTE;
 
 #[allow(non_camel_case_types)]
 #[allow(clippy::upper_case_acronyms)]
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum Board {
     Unknown,
 
@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 const EMPTY_SHIFT_REGISTER: u8 = 0b0001_0000;
 
+#[derive(Serialize, Deserialize)]
 pub struct ShiftRegister {
     value: u8,
 }
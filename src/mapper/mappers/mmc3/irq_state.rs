@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::mapper::mapper::IrqCounterInfo;
 use crate::bus::Bus;
 use crate::memory::ppu::ppu_address::PpuAddress;
@@ -5,6 +7,7 @@ use crate::ppu::pattern_table_side::PatternTableSide;
 use crate::counter::counter::{AutoTriggerWhen, ReloadDrivenCounter, CounterBuilder, ForcedReloadTiming, PrescalerBehaviorOnForcedReload, PrescalerTriggeredBy, WhenDisabledPrevent};
 use crate::util::pattern_table_transition_detector::{PatternTableTransitionDetector, AllowedAddresses};
 
+#[derive(Serialize, Deserialize)]
 pub struct Mmc3IrqState {
     counter: ReloadDrivenCounter,
     suppressor: Suppressor,
@@ -124,6 +127,7 @@ impl Mmc3IrqState {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 struct Suppressor {
     reload_value: u8,
     cycles_remaining: u8,
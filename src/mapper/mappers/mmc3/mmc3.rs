@@ -58,7 +58,7 @@ pub const NAME_TABLE_MIRRORINGS: &[NameTableMirroring] = &[
 use RegId::{CHR, PRG};
 pub const BANK_NUMBER_REGISTER_IDS: [RegId; 8] = [CHR(C), CHR(D), CHR(E), CHR(F), CHR(G), CHR(H), PRG(P), PRG(Q)];
 
-// TODO: Support VS System (and its 4-screen mirroring).
+#[derive(Serialize, Deserialize)]
 pub struct Mapper004Mmc3 {
     selected_register_id: RegId,
     irq_state: Mmc3IrqState,
@@ -130,7 +130,7 @@ impl Mapper004Mmc3 {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum RegId {
     CHR(ChrBankRegisterId),
     PRG(PrgBankRegisterId),
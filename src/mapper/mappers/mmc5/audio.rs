@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use splitbits::{splitbits_named, splitbits_named_ux};
+use ux::u4;
+
+use crate::apu::envelope::Envelope;
+use crate::apu::frequency_timer::FrequencyTimer;
+use crate::apu::length_counter::LengthCounter;
+use crate::apu::pulse_channel::Sequencer;
+
+// The MMC5 pulses are as loud as the APU pulses, which are roughly linear at 0.00752 per step.
+const PULSE_VOLUME_STEP: f32 = 0.00752;
+// The 8-bit PCM channel spans about the same range as the APU's 7-bit DMC channel.
+const PCM_VOLUME_STEP: f32 = 0.00225;
+// The MMC5 has its own frame timer instead of the APU's frame counter. Envelopes and length
+// counters are both clocked at about 240Hz (on NTSC).
+const CPU_CYCLES_PER_FRAME_TICK: u16 = 7457;
+
+// Two pulse channels that lack the APU's sweep units, and an 8-bit PCM channel.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Mmc5Audio {
+    pulse_1: Mmc5Pulse,
+    pulse_2: Mmc5Pulse,
+    pcm: Pcm,
+    // The pulse timers are only clocked on every other CPU cycle, like the APU's.
+    odd_cpu_cycle: bool,
+    cycles_until_frame_tick: u16,
+}
+
+impl Mmc5Audio {
+    pub fn step(&mut self) {
+        self.odd_cpu_cycle = !self.odd_cpu_cycle;
+        if self.odd_cpu_cycle {
+            self.pulse_1.tick_timer();
+            self.pulse_2.tick_timer();
+        }
+
+        if self.cycles_until_frame_tick > 0 {
+            self.cycles_until_frame_tick -= 1;
+        } else {
+            self.cycles_until_frame_tick = CPU_CYCLES_PER_FRAME_TICK - 1;
+            self.pulse_1.tick_frame();
+            self.pulse_2.tick_frame();
+        }
+
+        self.pulse_1.length_counter.apply_pending_values();
+        self.pulse_2.length_counter.apply_pending_values();
+    }
+
+    pub fn sample(&self) -> f32 {
+        let pulse_total = u8::from(self.pulse_1.sample_volume()) + u8::from(self.pulse_2.sample_volume());
+        PULSE_VOLUME_STEP * f32::from(pulse_total) + PCM_VOLUME_STEP * f32::from(self.pcm.output)
+    }
+
+    // Read $5010. Only the top bit is driven.
+    pub fn pcm_status(&self) -> u8 {
+        u8::from(self.pcm.irq_pending_if_enabled) << 7
+    }
+
+    // Read $5015. Only the bottom two bits are driven.
+    pub fn length_counter_status(&self) -> u8 {
+        u8::from(!self.pulse_2.length_counter.is_zero()) << 1 | u8::from(!self.pulse_1.length_counter.is_zero())
+    }
+
+    pub fn pcm_irq_pending(&self) -> bool {
+        self.pcm.irq_enabled && self.pcm.irq_pending_if_enabled
+    }
+
+    pub fn acknowledge_pcm_irq(&mut self) {
+        self.pcm.irq_pending_if_enabled = false;
+    }
+
+    // In read mode, the PCM channel snoops on CPU reads from $8000-$BFFF. Zero triggers an IRQ instead of being output.
+    pub fn on_pcm_area_read(&mut self, value: u8) {
+        if !self.pcm.read_mode {
+            return;
+        }
+
+        if value == 0 {
+            self.pcm.irq_pending_if_enabled = true;
+        } else {
+            self.pcm.output = value;
+        }
+    }
+
+    pub fn write_register(&mut self, addr: u16, value: u8) {
+        match addr {
+            0x5000 => self.pulse_1.set_control(value),
+            0x5002 => self.pulse_1.set_period_low(value),
+            0x5003 => self.pulse_1.set_length_and_period_high(value),
+            0x5004 => self.pulse_2.set_control(value),
+            0x5006 => self.pulse_2.set_period_low(value),
+            0x5007 => self.pulse_2.set_length_and_period_high(value),
+            0x5010 => (self.pcm.irq_enabled, self.pcm.read_mode) = splitbits_named!(value, "i......m"),
+            // Zero can't be written in write mode. It only triggers IRQs in read mode.
+            0x5011 if !self.pcm.read_mode && value != 0 => self.pcm.output = value,
+            0x5015 => {
+                let (pulse_2_enabled, pulse_1_enabled) = splitbits_named!(value, "......ba");
+                self.pulse_1.set_enabled(pulse_1_enabled);
+                self.pulse_2.set_enabled(pulse_2_enabled);
+            }
+            // The pulses lack sweep units, so $5001 and $5005 do nothing.
+            _ => { /* Do nothing. */ }
+        }
+    }
+}
+
+//                               Timer
+//                                 |
+//                                 v
+//                             Sequencer   Length Counter
+//                                 |             |
+//                                 v             v
+// Envelope -------------------> Gate -------> Gate ---> (to mixer)
+#[derive(Default, Serialize, Deserialize)]
+struct Mmc5Pulse {
+    length_counter: LengthCounter,
+    enabled: bool,
+    frequency_timer: FrequencyTimer,
+    envelope: Envelope,
+    sequencer: Sequencer,
+}
+
+impl Mmc5Pulse {
+    fn set_control(&mut self, value: u8) {
+        let (duty, halt, constant_volume, envelope) = splitbits_named_ux!(value, "ddhceeee");
+        self.sequencer.set_duty(duty.into());
+        self.length_counter.start_halt(halt);
+        self.envelope.set_control(constant_volume, envelope);
+    }
+
+    fn set_period_low(&mut self, value: u8) {
+        self.frequency_timer.set_period_low(value);
+    }
+
+    fn set_length_and_period_high(&mut self, value: u8) {
+        let (length, period_high) = splitbits_named_ux!(value, "lllllppp");
+        if self.enabled {
+            self.length_counter.start_reload(length);
+            self.envelope.start();
+        }
+
+        self.sequencer.reset();
+        self.frequency_timer.set_period_high_and_reset_index(period_high);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !self.enabled {
+            self.length_counter.set_to_zero();
+        }
+    }
+
+    fn tick_timer(&mut self) {
+        if self.frequency_timer.tick() {
+            self.sequencer.step();
+        }
+    }
+
+    fn tick_frame(&mut self) {
+        self.envelope.step();
+        self.length_counter.decrement_towards_zero();
+    }
+
+    // Unlike the APU pulses, short periods aren't muted since there's no sweep unit.
+    fn sample_volume(&self) -> u4 {
+        if !self.enabled || self.length_counter.is_zero() || !self.sequencer.on_duty() {
+            u4::new(0)
+        } else {
+            self.envelope.volume()
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Pcm {
+    read_mode: bool,
+    irq_enabled: bool,
+    irq_pending_if_enabled: bool,
+    output: u8,
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::counter::irq_counter_info::IrqCounterInfo;
 use crate::mapper::mappers::mmc5::scanline_detector::{ScanlineDetector, DetectedEvent};
 use crate::memory::ppu::ppu_address::PpuAddress;
@@ -5,6 +7,7 @@ use crate::memory::ppu::ppu_address::PpuAddress;
 const SPRITE_TILE_FETCH_START: u8 = 32;
 const BACKGROUND_TILE_FETCH_START: u8 = 40;
 
+#[derive(Serialize, Deserialize)]
 pub struct FrameState {
     in_frame: bool,
     irq_pending_if_enabled: bool,
@@ -67,6 +70,25 @@ impl FrameState {
             .contains(&self.tile_fetch_count)
     }
 
+    // The tile column and scanline of the background tile that the next name table fetch is for.
+    // None for the sprite fetches and for the tiles fetched past the right edge of the screen.
+    pub fn next_background_tile(&self) -> Option<(u8, u8)> {
+        if !self.in_frame {
+            return None;
+        }
+
+        match self.tile_fetch_count {
+            // The first two tiles of the scanline were prefetched at the end of the previous one.
+            count @ 0..=28 => Some((count + 3, self.scanline)),
+            // Prefetching the first two tiles of the next scanline.
+            39 => Some((0, self.scanline + 1)),
+            40 => Some((1, self.scanline + 1)),
+            // The two dummy fetches and the fetch that starts the next scanline are all for the third tile.
+            41..=43 => Some((2, self.scanline + 1)),
+            _ => None,
+        }
+    }
+
     // Called on PPU mask (0x2001) write, and on NMI vector (0xFFFA or 0xFFFB) read.
     pub fn force_end_frame(&mut self) {
         self.in_frame = false;
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use splitbits::splitbits;
+
+// Registers that only exist on the MMC5A revision: the CL3/SL3 general purpose pins and a
+// CPU cycle timer that can trigger IRQs.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Mmc5aRegisters {
+    cl3: Pin,
+    sl3: Pin,
+
+    timer: u16,
+    timer_running: bool,
+    timer_irq_pending: bool,
+}
+
+impl Mmc5aRegisters {
+    // Called every CPU cycle.
+    pub fn step(&mut self) {
+        if !self.timer_running {
+            return;
+        }
+
+        self.timer = self.timer.wrapping_sub(1);
+        if self.timer == 0 {
+            self.timer_running = false;
+            self.timer_irq_pending = true;
+        }
+    }
+
+    pub fn timer_irq_pending(&self) -> bool {
+        self.timer_irq_pending
+    }
+
+    // Read 0x5208. Only the top two bits are driven.
+    pub fn pin_status(&self, scanline_irq_pending: bool) -> u8 {
+        u8::from(self.cl3.level(scanline_irq_pending)) << 7 | u8::from(self.sl3.level(scanline_irq_pending)) << 6
+    }
+
+    // Read 0x5209. Only the top bit is driven.
+    pub fn timer_status(&self) -> u8 {
+        u8::from(self.timer_irq_pending) << 7
+    }
+
+    // Called on 0x5209 read.
+    pub fn acknowledge_timer_irq(&mut self) {
+        self.timer_irq_pending = false;
+    }
+
+    // Write 0x5207
+    pub fn set_pin_modes(&mut self, value: u8) {
+        let fields = splitbits!(value, "ab....cd");
+        self.cl3.irq_sourced = fields.a;
+        self.sl3.irq_sourced = fields.b;
+        self.cl3.is_input = fields.c;
+        self.sl3.is_input = fields.d;
+    }
+
+    // Write 0x5208
+    pub fn set_pin_data(&mut self, value: u8) {
+        let fields = splitbits!(value, "ab......");
+        self.cl3.data = fields.a;
+        self.sl3.data = fields.b;
+    }
+
+    // Write 0x5209. Writing the low byte starts the timer.
+    pub fn set_timer_low_byte(&mut self, value: u8) {
+        self.timer = (self.timer & 0xFF00) | u16::from(value);
+        self.timer_running = true;
+    }
+
+    // Write 0x520A
+    pub fn set_timer_high_byte(&mut self, value: u8) {
+        self.timer = (self.timer & 0x00FF) | (u16::from(value) << 8);
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Pin {
+    is_input: bool,
+    // When set, the pin outputs the scanline IRQ status instead of its data bit.
+    irq_sourced: bool,
+    data: bool,
+}
+
+impl Pin {
+    fn level(&self, scanline_irq_pending: bool) -> bool {
+        if self.is_input {
+            // Nothing is known to be connected to either pin, so inputs float high.
+            true
+        } else if self.irq_sourced {
+            scanline_irq_pending
+        } else {
+            self.data
+        }
+    }
+}
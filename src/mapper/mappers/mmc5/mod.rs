@@ -1,2 +1,5 @@
+pub mod audio;
 pub mod frame_state;
+pub mod mmc5a;
 pub mod scanline_detector;
+pub mod vertical_split;
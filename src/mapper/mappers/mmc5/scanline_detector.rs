@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::memory::ppu::ppu_address::PpuAddress;
 
 // Determines when a new scanline is detected, if rendering is enabled.
 // Indicates that the "in frame" state may occur when scanline_detected() is true.
+#[derive(Serialize, Deserialize)]
 pub struct ScanlineDetector {
     // How many PPU reads in a row have had the same in-range address.
     match_count: u8,
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use splitbits::splitbits;
+
+// The vertical split replaces the background tiles on one side of a tile column threshold with
+// tiles sourced from ExRAM, which have their own vertical scroll and CHR bank.
+#[derive(Default, Serialize, Deserialize)]
+pub struct VerticalSplit {
+    enabled: bool,
+    side: SplitSide,
+    threshold: u8,
+    scroll: u8,
+    bank: u8,
+}
+
+impl VerticalSplit {
+    // Write 0x5200
+    pub fn set_mode(&mut self, value: u8) {
+        let fields = splitbits!(value, "es.ccccc");
+        self.enabled = fields.e;
+        self.side = if fields.s { SplitSide::Right } else { SplitSide::Left };
+        self.threshold = fields.c;
+    }
+
+    // Write 0x5201
+    pub fn set_scroll(&mut self, value: u8) {
+        self.scroll = value;
+    }
+
+    // Write 0x5202
+    pub fn set_bank(&mut self, value: u8) {
+        self.bank = value;
+    }
+
+    pub fn bank(&self) -> u8 {
+        self.bank
+    }
+
+    pub fn contains(&self, tile_column: u8) -> bool {
+        self.enabled && match self.side {
+            SplitSide::Left => tile_column < self.threshold,
+            SplitSide::Right => tile_column >= self.threshold,
+        }
+    }
+
+    // The pixel row within the split region that is displayed on the specified scanline.
+    // The split scroll is applied at the start of the frame and wraps at the bottom of the name table.
+    pub fn pixel_row(&self, scanline: u8) -> u8 {
+        ((u16::from(self.scroll) + u16::from(scanline)) % 240) as u8
+    }
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+enum SplitSide {
+    #[default]
+    Left,
+    Right,
+}
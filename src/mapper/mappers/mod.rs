@@ -2,6 +2,8 @@ pub mod common;
 pub mod mmc1;
 pub mod mmc3;
 pub mod mmc5;
+pub mod namco163;
+pub mod sunsoft5b;
 pub mod vrc;
 
 pub mod mapper000;
@@ -33,6 +35,7 @@ pub mod mapper016_5;
 
 pub mod mapper018;
 pub mod mapper019;
+pub mod mapper020;
 // Mapper 20 is not a real mapper. It's reserved for when the emulator is running an FDS image.
 pub mod mapper021_1;
 pub mod mapper021_2;
@@ -40,10 +43,11 @@ pub mod mapper022;
 pub mod mapper023_1;
 pub mod mapper023_2;
 pub mod mapper023_3;
-
+pub mod mapper024;
 pub mod mapper025_1;
 pub mod mapper025_2;
 pub mod mapper025_3;
+pub mod mapper026;
 
 pub mod mapper028;
 pub mod mapper029;
@@ -88,6 +92,7 @@ pub mod mapper068;
 pub mod mapper069;
 pub mod mapper070;
 pub mod mapper071;
+pub mod mapper072;
 
 pub mod mapper073;
 pub mod mapper074;
@@ -113,12 +118,15 @@ pub mod mapper089;
 
 pub mod mapper091_0;
 pub mod mapper091_1;
+pub mod mapper092;
 
 pub mod mapper093;
 pub mod mapper094;
 
 pub mod mapper097;
 
+pub mod mapper099;
+
 pub mod mapper101;
 
 pub mod mapper103;
@@ -220,3 +228,5 @@ pub mod mapper246;
 
 pub mod mapper264;
 pub mod mapper265;
+
+pub mod nsf_mapper;
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::memory::raw_memory::SaveRam;
+
+// One channel is updated every 15 CPU cycles.
+const CPU_CYCLES_PER_CHANNEL_UPDATE: u8 = 15;
+// At 0dB, a full volume, full swing channel is as loud as a full volume APU pulse channel.
+const BASE_LEVEL: f32 = 0.00752 / 15.0;
+// The channel registers occupy the top of internal RAM, eight bytes per channel.
+const CHANNEL_REGISTERS_START: u8 = 0x40;
+// The high nibble of the last channel's volume register holds the enabled channel count.
+const CHANNEL_COUNT_ADDRESS: u32 = 0x7F;
+
+// Up to eight wavetable channels, with waveforms and channel registers both stored in the
+// mapper's 128 bytes of internal RAM.
+#[derive(Serialize, Deserialize)]
+pub struct Namco163Audio {
+    // How much louder than the APU the board's expansion audio is. None if the board has no audio.
+    level_db: Option<f32>,
+    disabled: bool,
+    cycles_until_next_update: u8,
+    // Channels are updated from 7 down to the lowest enabled channel, then it starts over.
+    current_channel: u8,
+    channel_outputs: [i8; 8],
+    output: f32,
+}
+
+impl Namco163Audio {
+    pub fn new(level_db: Option<f32>) -> Self {
+        Self {
+            level_db,
+            disabled: false,
+            cycles_until_next_update: 0,
+            current_channel: 7,
+            channel_outputs: [0; 8],
+            output: 0.0,
+        }
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+        if disabled {
+            self.output = 0.0;
+        }
+    }
+
+    pub fn step(&mut self, ram: &mut SaveRam) {
+        let Some(level_db) = self.level_db else {
+            return;
+        };
+
+        if self.disabled {
+            return;
+        }
+
+        if self.cycles_until_next_update > 0 {
+            self.cycles_until_next_update -= 1;
+            return;
+        }
+
+        self.cycles_until_next_update = CPU_CYCLES_PER_CHANNEL_UPDATE - 1;
+
+        let channel = self.current_channel;
+        self.channel_outputs[usize::from(channel)] = Self::update_channel(ram, channel);
+        let channel_count = Self::channel_count(ram);
+        self.current_channel = if channel <= 8 - channel_count { 7 } else { channel - 1 };
+
+        // The real chip outputs one channel at a time, so each channel gets quieter as more channels are enabled.
+        // Averaging the enabled channels reproduces that without the high-pitched whine of the multiplexing.
+        let total: i16 = self.channel_outputs[usize::from(8 - channel_count)..].iter()
+            .map(|&output| i16::from(output))
+            .sum();
+        self.output = BASE_LEVEL * 10f32.powf(level_db / 20.0) * f32::from(total) / f32::from(channel_count);
+    }
+
+    pub fn sample(&self) -> f32 {
+        self.output
+    }
+
+    fn channel_count(ram: &SaveRam) -> u8 {
+        ((ram[CHANNEL_COUNT_ADDRESS] >> 4) & 0b111) + 1
+    }
+
+    fn update_channel(ram: &mut SaveRam, channel: u8) -> i8 {
+        let base = u32::from(CHANNEL_REGISTERS_START + 8 * channel);
+        let register = |offset: u32| u32::from(ram[base + offset]);
+
+        let frequency = register(0) | (register(2) << 8) | ((register(4) & 0b11) << 16);
+        let mut phase = register(1) | (register(3) << 8) | (register(5) << 16);
+        let length = 256 - (register(4) & 0b1111_1100);
+        let wave_address = register(6);
+        let volume = (register(7) & 0b1111) as i8;
+
+        phase = (phase + frequency) % (length << 16);
+        ram[base + 1] = phase as u8;
+        ram[base + 3] = (phase >> 8) as u8;
+        ram[base + 5] = (phase >> 16) as u8;
+
+        // Each byte holds two 4-bit samples, low nibble first.
+        let sample_address = ((phase >> 16) + wave_address) & 0xFF;
+        let sample = (ram[sample_address / 2] >> (4 * (sample_address % 2))) & 0b1111;
+        (sample as i8 - 8) * volume
+    }
+}
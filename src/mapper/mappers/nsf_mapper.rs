@@ -0,0 +1,177 @@
+use crate::mapper::mapper::*;
+
+const LAYOUT: Layout = Layout::builder()
+    .prg_rom_max_size(1024 * KIBIBYTE)
+    .prg_layout(&[
+        PrgWindow::new(0x6000, 0x7FFF, 8 * KIBIBYTE, Prg::RAM_OR_ABSENT),
+        PrgWindow::new(0x8000, 0x8FFF, 4 * KIBIBYTE, Prg::ROM).switchable(P),
+        PrgWindow::new(0x9000, 0x9FFF, 4 * KIBIBYTE, Prg::ROM).switchable(Q),
+        PrgWindow::new(0xA000, 0xAFFF, 4 * KIBIBYTE, Prg::ROM).switchable(R),
+        PrgWindow::new(0xB000, 0xBFFF, 4 * KIBIBYTE, Prg::ROM).switchable(S),
+        PrgWindow::new(0xC000, 0xCFFF, 4 * KIBIBYTE, Prg::ROM).switchable(T),
+        PrgWindow::new(0xD000, 0xDFFF, 4 * KIBIBYTE, Prg::ROM).switchable(U),
+        PrgWindow::new(0xE000, 0xEFFF, 4 * KIBIBYTE, Prg::ROM).switchable(V),
+        PrgWindow::new(0xF000, 0xFFFF, 4 * KIBIBYTE, Prg::ROM).switchable(W),
+    ])
+    .chr_rom_max_size(0)
+    .chr_layout(&[
+        ChrWindow::new(0x0000, 0x1FFF, 8 * KIBIBYTE, Chr::RAM),
+    ])
+    .fixed_name_table_mirroring()
+    .build();
+
+const BANK_REGISTER_IDS: [PrgBankRegisterId; 8] = [P, Q, R, S, T, U, V, W];
+
+const DRIVER_START: u16 = 0x4100;
+const INTERRUPT_HANDLER: u16 = 0x416A;
+const INIT_OPERAND: usize = 0x5D;
+const PLAY_OPERAND: usize = 0x65;
+// Resets the machine state, calls INIT once (unless playback is stopped), then calls PLAY whenever it is due.
+// The INIT and PLAY addresses are filled in when the JSR operands are read.
+#[rustfmt::skip]
+const DRIVER: [u8; 0x6B] = [
+    0x78,             // 4100: SEI
+    0xD8,             // 4101: CLD
+    0xA2, 0xFF,       // 4102: LDX #$FF
+    0x9A,             // 4104: TXS
+    0xA9, 0x00,       // 4105: LDA #$00
+    0x8D, 0x00, 0x20, // 4107: STA $2000
+    0x8D, 0x01, 0x20, // 410A: STA $2001
+    0xAA,             // 410D: TAX
+    // Clear CPU RAM.
+    0x95, 0x00,       // 410E: STA $00,X
+    0x9D, 0x00, 0x01, // 4110: STA $0100,X
+    0x9D, 0x00, 0x02, // 4113: STA $0200,X
+    0x9D, 0x00, 0x03, // 4116: STA $0300,X
+    0x9D, 0x00, 0x04, // 4119: STA $0400,X
+    0x9D, 0x00, 0x05, // 411C: STA $0500,X
+    0x9D, 0x00, 0x06, // 411F: STA $0600,X
+    0x9D, 0x00, 0x07, // 4122: STA $0700,X
+    0xE8,             // 4125: INX
+    0xD0, 0xE6,       // 4126: BNE $410E
+    // Clear work RAM, using $00-$01 as a pointer.
+    0xA0, 0x60,       // 4128: LDY #$60
+    0x84, 0x01,       // 412A: STY $01
+    0xA0, 0x00,       // 412C: LDY #$00
+    0x84, 0x00,       // 412E: STY $00
+    0x91, 0x00,       // 4130: STA ($00),Y
+    0xC8,             // 4132: INY
+    0xD0, 0xFB,       // 4133: BNE $4130
+    0xE6, 0x01,       // 4135: INC $01
+    0xA6, 0x01,       // 4137: LDX $01
+    0xE0, 0x80,       // 4139: CPX #$80
+    0xD0, 0xF3,       // 413B: BNE $4130
+    0x85, 0x01,       // 413D: STA $01
+    // Silence the APU.
+    0xA2, 0x13,       // 413F: LDX #$13
+    0x9D, 0x00, 0x40, // 4141: STA $4000,X
+    0xCA,             // 4144: DEX
+    0x10, 0xFA,       // 4145: BPL $4141
+    0xA9, 0x0F,       // 4147: LDA #$0F
+    0x8D, 0x15, 0x40, // 4149: STA $4015
+    0xA9, 0x40,       // 414C: LDA #$40
+    0x8D, 0x17, 0x40, // 414E: STA $4017
+    // Start the selected track.
+    0xAD, 0x80, 0x41, // 4151: LDA $4180 (playing)
+    0xF0, 0x09,       // 4154: BEQ $415F
+    0xAD, 0x81, 0x41, // 4156: LDA $4181 (track)
+    0xAE, 0x82, 0x41, // 4159: LDX $4182 (region)
+    0x20, 0x00, 0x00, // 415C: JSR INIT
+    // Wait for PLAY to be due.
+    0xAD, 0x83, 0x41, // 415F: LDA $4183 (play due)
+    0xF0, 0xFB,       // 4162: BEQ $415F
+    0x20, 0x00, 0x00, // 4164: JSR PLAY
+    0x4C, 0x5F, 0x41, // 4167: JMP $415F
+    0x40,             // 416A: RTI
+];
+
+// Plays NSF music files. Bank switching is the same as mapper 31, but $5FF8-$5FFF are the only
+// bank registers. The driver code and the status registers that it polls live at $4100-$41FF.
+// See https://www.nesdev.org/wiki/NSF
+// TODO: FDS-style NSFs ($5FF6-$5FF7 bank switching, with RAM at $8000-$DFFF).
+#[derive(Serialize, Deserialize)]
+pub struct NsfMapper {
+    initial_banks: [u8; 8],
+}
+
+impl Mapper for NsfMapper {
+    fn peek_register(&self, bus: &Bus, addr: CpuAddress) -> ReadResult {
+        let player = bus.nsf_player.as_ref().expect("NSF mapper must have a player.");
+        let [init_low, init_high] = player.init_address().to_le_bytes();
+        let [play_low, play_high] = player.play_address().to_le_bytes();
+        match *addr {
+            0x0000..=0x401F | 0x6000..=0xFFFF => unreachable!(),
+            0x4100..=0x416A => ReadResult::full(match usize::from(*addr - DRIVER_START) {
+                INIT_OPERAND => init_low,
+                i if i == INIT_OPERAND + 1 => init_high,
+                PLAY_OPERAND => play_low,
+                i if i == PLAY_OPERAND + 1 => play_high,
+                i => DRIVER[i],
+            }),
+            0x4180 => ReadResult::full(u8::from(player.is_playing())),
+            0x4181 => ReadResult::full(player.track()),
+            0x4182 => ReadResult::full(player.region_flag()),
+            0x4183 => ReadResult::full(u8::from(player.play_due())),
+            0x4020..=0x40FF | 0x416B..=0x417F | 0x4184..=0x5FFF => ReadResult::OPEN_BUS,
+        }
+    }
+
+    fn peek_interrupt_vector(&self, addr: CpuAddress) -> Option<u8> {
+        let [driver_low, driver_high] = DRIVER_START.to_le_bytes();
+        let [handler_low, handler_high] = INTERRUPT_HANDLER.to_le_bytes();
+        Some(match *addr {
+            0xFFFA | 0xFFFE => handler_low,
+            0xFFFB | 0xFFFF => handler_high,
+            0xFFFC => driver_low,
+            0xFFFD => driver_high,
+            _ => unreachable!(),
+        })
+    }
+
+    fn on_cpu_read(&mut self, bus: &mut Bus, addr: CpuAddress, _value: u8) {
+        if *addr == 0x4183 && let Some(player) = &mut bus.nsf_player {
+            player.acknowledge_play();
+        }
+    }
+
+    fn write_register(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
+        match *addr {
+            0x0000..=0x401F => unreachable!(),
+            0x4020..=0x5FF7 | 0x6000..=0xFFFF => { /* No regs here. */ }
+            0x5FF8..=0x5FFF => bus.set_prg_register(BANK_REGISTER_IDS[usize::from(*addr - 0x5FF8)], value),
+        }
+    }
+
+    fn init_mapper_params(&self, bus: &mut Bus) {
+        self.set_initial_banks(bus);
+    }
+
+    fn reset(&mut self, bus: &mut Bus) {
+        self.set_initial_banks(bus);
+        if let Some(player) = &mut bus.nsf_player {
+            player.restart_play_timer();
+        }
+    }
+
+    fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
+        if let Some(player) = &mut bus.nsf_player {
+            player.step();
+        }
+    }
+
+    fn layout(&self) -> Layout {
+        LAYOUT
+    }
+}
+
+impl NsfMapper {
+    pub fn new(initial_banks: [u8; 8]) -> Self {
+        Self { initial_banks }
+    }
+
+    fn set_initial_banks(&self, bus: &mut Bus) {
+        for (reg_id, bank) in BANK_REGISTER_IDS.into_iter().zip(self.initial_banks) {
+            bus.set_prg_register(reg_id, bank);
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use splitbits::splitbits_named;
+
+// The tone and noise generators are clocked every 16 CPU cycles, the envelope every 8.
+const CPU_CYCLES_PER_TONE_TICK: u8 = 16;
+const CPU_CYCLES_PER_ENVELOPE_TICK: u8 = 8;
+// A full volume channel is about twice as loud as a full volume APU pulse channel.
+const FULL_VOLUME_LEVEL: f32 = 2.0 * 15.0 * 0.00752;
+// Each of the 32 envelope levels is 1.5dB apart. The 16 channel volumes use every other level.
+const DECIBELS_PER_LEVEL: f32 = 1.5;
+
+// A YM2149-compatible PSG: three square channels that can each be mixed with a shared noise
+// generator, and a shared envelope generator that any channel can use as its volume.
+#[derive(Serialize, Deserialize)]
+pub struct Sunsoft5bAudio {
+    channels: [ToneChannel; 3],
+    noise: Noise,
+    envelope: Envelope,
+    cycles_until_tone_tick: u8,
+    cycles_until_envelope_tick: u8,
+    output: f32,
+}
+
+impl Sunsoft5bAudio {
+    pub fn new() -> Self {
+        Self {
+            channels: [ToneChannel::default(), ToneChannel::default(), ToneChannel::default()],
+            noise: Noise::new(),
+            envelope: Envelope::default(),
+            cycles_until_tone_tick: 0,
+            cycles_until_envelope_tick: 0,
+            output: 0.0,
+        }
+    }
+
+    pub fn step(&mut self) {
+        if self.cycles_until_envelope_tick > 0 {
+            self.cycles_until_envelope_tick -= 1;
+        } else {
+            self.cycles_until_envelope_tick = CPU_CYCLES_PER_ENVELOPE_TICK - 1;
+            self.envelope.tick();
+        }
+
+        if self.cycles_until_tone_tick > 0 {
+            self.cycles_until_tone_tick -= 1;
+            return;
+        }
+
+        self.cycles_until_tone_tick = CPU_CYCLES_PER_TONE_TICK - 1;
+        for channel in &mut self.channels {
+            channel.tick();
+        }
+
+        self.noise.tick();
+
+        let noise_high = self.noise.output();
+        let envelope_level = self.envelope.level();
+        self.output = self.channels.iter()
+            .map(|channel| channel.output(noise_high, envelope_level))
+            .sum();
+    }
+
+    pub fn sample(&self) -> f32 {
+        self.output
+    }
+
+    pub fn write_register(&mut self, register: u8, value: u8) {
+        match register {
+            0x0 | 0x2 | 0x4 => self.channels[usize::from(register / 2)].set_period_low(value),
+            0x1 | 0x3 | 0x5 => self.channels[usize::from(register / 2)].set_period_high(value),
+            0x6 => self.noise.period = value & 0b0001_1111,
+            0x7 => {
+                // The top two bits control the I/O ports, which aren't connected to anything.
+                let (noise_disabled, tone_disabled) = splitbits_named!(value, "..nnnttt");
+                for (i, channel) in self.channels.iter_mut().enumerate() {
+                    channel.noise_disabled = noise_disabled & (1 << i) != 0;
+                    channel.tone_disabled = tone_disabled & (1 << i) != 0;
+                }
+            }
+            0x8..=0xA => {
+                let channel = &mut self.channels[usize::from(register - 0x8)];
+                (channel.envelope_enabled, channel.volume) = splitbits_named!(value, "...evvvv");
+            }
+            0xB => self.envelope.period = (self.envelope.period & 0xFF00) | u16::from(value),
+            0xC => self.envelope.period = (u16::from(value) << 8) | (self.envelope.period & 0x00FF),
+            0xD => self.envelope.set_shape(value),
+            0xE..=0xF => { /* I/O ports. Do nothing. */ }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct ToneChannel {
+    period: u16,
+    divider: u16,
+    high: bool,
+    tone_disabled: bool,
+    noise_disabled: bool,
+    envelope_enabled: bool,
+    volume: u8,
+}
+
+impl ToneChannel {
+    fn tick(&mut self) {
+        self.divider += 1;
+        // A period of 0 behaves the same as a period of 1.
+        if self.divider >= self.period.max(1) {
+            self.divider = 0;
+            self.high = !self.high;
+        }
+    }
+
+    fn output(&self, noise_high: bool, envelope_level: u8) -> f32 {
+        // A disabled generator doesn't gate the channel at all.
+        let tone_high = self.high || self.tone_disabled;
+        let noise_high = noise_high || self.noise_disabled;
+        if !tone_high || !noise_high {
+            return 0.0;
+        }
+
+        let level = if self.envelope_enabled {
+            envelope_level
+        } else if self.volume == 0 {
+            0
+        } else {
+            2 * self.volume + 1
+        };
+
+        level_to_amplitude(level)
+    }
+
+    fn set_period_low(&mut self, value: u8) {
+        self.period = (self.period & 0x0F00) | u16::from(value);
+    }
+
+    fn set_period_high(&mut self, value: u8) {
+        self.period = (u16::from(value & 0b0000_1111) << 8) | (self.period & 0x00FF);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Noise {
+    period: u8,
+    divider: u8,
+    // The shift register only advances on every other noise period.
+    half_period_elapsed: bool,
+    shift_register: u32,
+}
+
+impl Noise {
+    fn new() -> Self {
+        Self {
+            period: 0,
+            divider: 0,
+            half_period_elapsed: false,
+            shift_register: 1,
+        }
+    }
+
+    fn tick(&mut self) {
+        self.divider += 1;
+        if self.divider < self.period.max(1) {
+            return;
+        }
+
+        self.divider = 0;
+        self.half_period_elapsed = !self.half_period_elapsed;
+        if self.half_period_elapsed {
+            return;
+        }
+
+        // 17-bit LFSR, tapped at bits 0 and 3.
+        let feedback = (self.shift_register ^ (self.shift_register >> 3)) & 1;
+        self.shift_register = (self.shift_register >> 1) | (feedback << 16);
+    }
+
+    fn output(&self) -> bool {
+        self.shift_register & 1 == 1
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Envelope {
+    period: u16,
+    divider: u16,
+    // Steps from 0 to 31 through the current ramp.
+    step: u8,
+    rising: bool,
+    holding: bool,
+    continues: bool,
+    alternates: bool,
+    holds: bool,
+}
+
+impl Envelope {
+    fn tick(&mut self) {
+        self.divider += 1;
+        if self.divider < self.period.max(1) {
+            return;
+        }
+
+        self.divider = 0;
+        if self.holding {
+            return;
+        }
+
+        if self.step < 31 {
+            self.step += 1;
+            return;
+        }
+
+        if !self.continues {
+            // Drop to silence and stay there.
+            self.holding = true;
+            self.rising = false;
+        } else if self.holds {
+            // Stay at the end of the ramp, or at the opposite end if alternating.
+            self.holding = true;
+            self.rising ^= self.alternates;
+        } else {
+            self.step = 0;
+            self.rising ^= self.alternates;
+        }
+    }
+
+    fn level(&self) -> u8 {
+        if self.rising { self.step } else { 31 - self.step }
+    }
+
+    // Writing the shape restarts the envelope.
+    fn set_shape(&mut self, value: u8) {
+        (self.continues, self.rising, self.alternates, self.holds) = splitbits_named!(value, "....cash");
+        self.step = 0;
+        self.divider = 0;
+        self.holding = false;
+    }
+}
+
+fn level_to_amplitude(level: u8) -> f32 {
+    if level == 0 {
+        0.0
+    } else {
+        FULL_VOLUME_LEVEL * 10f32.powf(DECIBELS_PER_LEVEL * (f32::from(level) - 31.0) / 20.0)
+    }
+}
@@ -1,3 +1,6 @@
 pub mod vrc_irq_state;
 pub mod vrc2;
 pub mod vrc4;
+pub mod vrc6;
+pub mod vrc6_audio;
+pub mod vrc7_audio;
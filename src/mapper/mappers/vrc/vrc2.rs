@@ -30,6 +30,7 @@ const LAYOUT: Layout = Layout::builder()
     ])
     .build();
 
+#[derive(Serialize, Deserialize)]
 pub struct Vrc2 {
     low_address_bank_register_ids: BTreeMap<CpuAddress, ChrBankRegisterId>,
     high_address_bank_register_ids: BTreeMap<CpuAddress, ChrBankRegisterId>,
@@ -111,7 +112,7 @@ impl Vrc2 {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Serialize, Deserialize)]
 pub enum BankLowBitBehavior {
     Ignore,
     Keep,
@@ -41,6 +41,7 @@ const LAYOUT: Layout = Layout::builder()
     ])
     .build();
 
+#[derive(Serialize, Deserialize)]
 pub struct Vrc4 {
     low_address_bank_register_ids: BTreeMap<CpuAddress, ChrBankRegisterId>,
     high_address_bank_register_ids: BTreeMap<CpuAddress, ChrBankRegisterId>,
@@ -0,0 +1,124 @@
+use crate::mapper::mapper::*;
+use crate::mapper::mappers::vrc::vrc_irq_state::VrcIrqState;
+use crate::mapper::mappers::vrc::vrc6_audio::Vrc6Audio;
+
+const PRG_WINDOWS: &[PrgWindow] = &[
+    PrgWindow::new(0x6000, 0x7FFF, 8 * KIBIBYTE, Prg::RAM_OR_ABSENT).read_write_status(RS0, WS0),
+    PrgWindow::new(0x8000, 0xBFFF, 16 * KIBIBYTE, Prg::ROM).switchable(P),
+    PrgWindow::new(0xC000, 0xDFFF, 8 * KIBIBYTE, Prg::ROM).switchable(Q),
+    PrgWindow::new(0xE000, 0xFFFF, 8 * KIBIBYTE, Prg::ROM).fixed_number(-1),
+];
+
+// TODO: The 2KiB windows should take A10 from either the PPU or the low bit of the register,
+// depending upon bit 5 of $B003. Commercial games only use mode 0, so this doesn't matter yet.
+const LAYOUT: Layout = Layout::builder()
+    .prg_rom_max_size(256 * KIBIBYTE)
+    .prg_layout(PRG_WINDOWS)
+    .chr_rom_max_size(256 * KIBIBYTE)
+    // Mode 0
+    .chr_layout(&[
+        ChrWindow::new(0x0000, 0x03FF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(C),
+        ChrWindow::new(0x0400, 0x07FF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(D),
+        ChrWindow::new(0x0800, 0x0BFF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(E),
+        ChrWindow::new(0x0C00, 0x0FFF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(F),
+        ChrWindow::new(0x1000, 0x13FF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(G),
+        ChrWindow::new(0x1400, 0x17FF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(H),
+        ChrWindow::new(0x1800, 0x1BFF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(I),
+        ChrWindow::new(0x1C00, 0x1FFF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(J),
+    ])
+    // Mode 1
+    .chr_layout(&[
+        ChrWindow::new(0x0000, 0x07FF, 2 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(C),
+        ChrWindow::new(0x0800, 0x0FFF, 2 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(D),
+        ChrWindow::new(0x1000, 0x17FF, 2 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(E),
+        ChrWindow::new(0x1800, 0x1FFF, 2 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(F),
+    ])
+    // Modes 2 and 3
+    .chr_layout(&[
+        ChrWindow::new(0x0000, 0x03FF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(C),
+        ChrWindow::new(0x0400, 0x07FF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(D),
+        ChrWindow::new(0x0800, 0x0BFF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(E),
+        ChrWindow::new(0x0C00, 0x0FFF, 1 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(F),
+        ChrWindow::new(0x1000, 0x17FF, 2 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(G),
+        ChrWindow::new(0x1800, 0x1FFF, 2 * KIBIBYTE, Chr::ROM_OR_RAM).switchable(H),
+    ])
+    .name_table_mirrorings(&[
+        NameTableMirroring::VERTICAL,
+        NameTableMirroring::HORIZONTAL,
+        NameTableMirroring::ONE_SCREEN_LEFT_BANK,
+        NameTableMirroring::ONE_SCREEN_RIGHT_BANK,
+    ])
+    .build();
+
+const CHR_REGISTER_IDS: [ChrBankRegisterId; 8] = [C, D, E, F, G, H, I, J];
+
+// Konami VRC6. VRC6a and VRC6b only differ in which CPU address lines are connected to A0 and A1.
+#[derive(Serialize, Deserialize)]
+pub struct Vrc6 {
+    address_lines_swapped: bool,
+    irq_state: VrcIrqState,
+    audio: Vrc6Audio,
+}
+
+impl Mapper for Vrc6 {
+    fn on_end_of_cpu_cycle(&mut self, bus: &mut Bus) {
+        self.irq_state.step(bus);
+        self.audio.step();
+    }
+
+    fn write_register(&mut self, bus: &mut Bus, addr: CpuAddress, value: u8) {
+        if *addr < 0x8000 {
+            return;
+        }
+
+        let mut addr = *addr & 0xF003;
+        if self.address_lines_swapped {
+            addr = (addr & 0xF000) | ((addr & 0b01) << 1) | ((addr & 0b10) >> 1);
+        }
+
+        match addr {
+            // Bank numbers are in 8KiB units, so 16KiB bank numbers must be doubled.
+            0x8000..=0x8003 => bus.set_prg_register(P, (value & 0b0000_1111) << 1),
+            0x9000..=0x9003 | 0xA000..=0xA002 | 0xB000..=0xB002 => self.audio.write_register(addr, value),
+            0xA003 => { /* Do nothing. */ }
+            0xB003 => {
+                // TODO: Support CHR ROM name tables (bit 4).
+                let (ram_enabled, mirroring, chr_mode) = splitbits_named!(value, "r...mmcc");
+                bus.set_reads_enabled(RS0, ram_enabled);
+                bus.set_writes_enabled(WS0, ram_enabled);
+                bus.set_name_table_mirroring(mirroring);
+                bus.set_chr_layout(chr_mode.min(2));
+            }
+            0xC000..=0xC003 => bus.set_prg_register(Q, value & 0b0001_1111),
+            0xD000..=0xD003 => bus.set_chr_register(CHR_REGISTER_IDS[usize::from(addr & 0b11)], value),
+            0xE000..=0xE003 => bus.set_chr_register(CHR_REGISTER_IDS[usize::from(addr & 0b11) + 4], value),
+            0xF000 => self.irq_state.set_reload_value(value),
+            0xF001 => self.irq_state.set_mode(bus, value),
+            0xF002 => self.irq_state.acknowledge(bus),
+            0xF003 => { /* Do nothing. */ }
+            _ => unreachable!(),
+        }
+    }
+
+    fn expansion_audio_sample(&self) -> f32 {
+        self.audio.sample()
+    }
+
+    fn irq_counter_info(&self) -> Option<IrqCounterInfo> {
+        Some(self.irq_state.to_irq_counter_info())
+    }
+
+    fn layout(&self) -> Layout {
+        LAYOUT
+    }
+}
+
+impl Vrc6 {
+    pub fn new(address_lines_swapped: bool) -> Self {
+        Self {
+            address_lines_swapped,
+            irq_state: VrcIrqState::new(),
+            audio: Vrc6Audio::default(),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use splitbits::splitbits_named;
+
+// Each VRC6 volume step is about as loud as an APU pulse volume step. The APU's pulse output is
+// roughly linear at 0.00752 per step.
+const VOLUME_STEP: f32 = 0.00752;
+
+// Two pulse channels and a sawtooth channel, clocked by the CPU.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Vrc6Audio {
+    pulse_1: Vrc6Pulse,
+    pulse_2: Vrc6Pulse,
+    sawtooth: Vrc6Sawtooth,
+    halted: bool,
+    // How many bits to shift the channel periods right by. Either 0, 4, or 8.
+    period_shift: u8,
+}
+
+impl Vrc6Audio {
+    pub fn step(&mut self) {
+        if self.halted {
+            return;
+        }
+
+        self.pulse_1.step(self.period_shift);
+        self.pulse_2.step(self.period_shift);
+        self.sawtooth.step(self.period_shift);
+    }
+
+    pub fn sample(&self) -> f32 {
+        let total = self.pulse_1.output() + self.pulse_2.output() + self.sawtooth.output();
+        VOLUME_STEP * f32::from(total)
+    }
+
+    // The registers are numbered by their position within $9000-$B003, after A0/A1 are normalized.
+    pub fn write_register(&mut self, addr: u16, value: u8) {
+        match addr {
+            0x9000 => self.pulse_1.set_control(value),
+            0x9001 => self.pulse_1.set_period_low(value),
+            0x9002 => self.pulse_1.set_period_high_and_enabled(value),
+            0x9003 => {
+                let (shift_8, shift_4, halted) = splitbits_named!(value, ".....fgh");
+                self.halted = halted;
+                self.period_shift = if shift_8 { 8 } else if shift_4 { 4 } else { 0 };
+            }
+            0xA000 => self.pulse_2.set_control(value),
+            0xA001 => self.pulse_2.set_period_low(value),
+            0xA002 => self.pulse_2.set_period_high_and_enabled(value),
+            0xB000 => self.sawtooth.accumulator_rate = value & 0b0011_1111,
+            0xB001 => self.sawtooth.set_period_low(value),
+            0xB002 => self.sawtooth.set_period_high_and_enabled(value),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Vrc6Pulse {
+    enabled: bool,
+    // Ignore the duty cycle and always output the volume. Used for PCM playback.
+    constant_output: bool,
+    duty: u8,
+    volume: u8,
+    period: u16,
+    divider: u16,
+    // Counts down from 15 to 0. The channel outputs its volume while this is at or below the duty.
+    duty_step: u8,
+}
+
+impl Vrc6Pulse {
+    fn step(&mut self, period_shift: u8) {
+        if !self.enabled {
+            return;
+        }
+
+        if self.divider == 0 {
+            self.divider = self.period >> period_shift;
+            self.duty_step = self.duty_step.checked_sub(1).unwrap_or(15);
+        } else {
+            self.divider -= 1;
+        }
+    }
+
+    fn output(&self) -> u8 {
+        if self.enabled && (self.constant_output || self.duty_step <= self.duty) {
+            self.volume
+        } else {
+            0
+        }
+    }
+
+    fn set_control(&mut self, value: u8) {
+        (self.constant_output, self.duty, self.volume) = splitbits_named!(value, "mdddvvvv");
+    }
+
+    fn set_period_low(&mut self, value: u8) {
+        self.period = (self.period & 0x0F00) | u16::from(value);
+    }
+
+    fn set_period_high_and_enabled(&mut self, value: u8) {
+        let (enabled, period_high) = splitbits_named!(value, "e...pppp");
+        self.period = (u16::from(period_high) << 8) | (self.period & 0x00FF);
+        self.enabled = enabled;
+        // Disabling the channel resets its duty cycle.
+        if !self.enabled {
+            self.duty_step = 15;
+        }
+    }
+}
+
+impl Default for Vrc6Pulse {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            constant_output: false,
+            duty: 0,
+            volume: 0,
+            period: 0,
+            divider: 0,
+            duty_step: 15,
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Vrc6Sawtooth {
+    enabled: bool,
+    accumulator_rate: u8,
+    accumulator: u8,
+    period: u16,
+    divider: u16,
+    // The accumulator is added to on every other step, then reset after the 14th step.
+    step_index: u8,
+}
+
+impl Vrc6Sawtooth {
+    fn step(&mut self, period_shift: u8) {
+        if !self.enabled {
+            return;
+        }
+
+        if self.divider > 0 {
+            self.divider -= 1;
+            return;
+        }
+
+        self.divider = self.period >> period_shift;
+        self.step_index += 1;
+        if self.step_index == 14 {
+            self.step_index = 0;
+            self.accumulator = 0;
+        } else if self.step_index.is_multiple_of(2) {
+            self.accumulator = self.accumulator.wrapping_add(self.accumulator_rate);
+        }
+    }
+
+    // Only the top five bits of the accumulator reach the DAC.
+    fn output(&self) -> u8 {
+        if self.enabled { self.accumulator >> 3 } else { 0 }
+    }
+
+    fn set_period_low(&mut self, value: u8) {
+        self.period = (self.period & 0x0F00) | u16::from(value);
+    }
+
+    fn set_period_high_and_enabled(&mut self, value: u8) {
+        let (enabled, period_high) = splitbits_named!(value, "e...pppp");
+        self.period = (u16::from(period_high) << 8) | (self.period & 0x00FF);
+        self.enabled = enabled;
+        // Disabling the channel resets the accumulator.
+        if !self.enabled {
+            self.accumulator = 0;
+            self.step_index = 0;
+        }
+    }
+}
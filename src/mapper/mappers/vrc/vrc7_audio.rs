@@ -0,0 +1,386 @@
+use std::f32::consts::TAU;
+
+use serde::{Deserialize, Serialize};
+use splitbits::splitbits_named;
+
+// The synth runs at 3.58MHz / 72, which is one sample every 36 CPU cycles.
+const CPU_CYCLES_PER_SAMPLE: u8 = 36;
+// The peak level of one channel at full volume, on the same scale as the APU's mixed output.
+// Roughly as loud as an APU pulse channel at full volume.
+const CHANNEL_LEVEL: f32 = 0.06;
+// How far (in cycles) a full-scale modulator output shifts the phase of its carrier.
+const MODULATION_DEPTH: f32 = 2.0;
+const ENVELOPE_STEP_DB: f32 = 0.375;
+const ENVELOPE_MAX: f32 = 127.0;
+
+// The built-in instruments (1 through 15), in the same format as custom instrument registers $00-$07.
+const PATCHES: [[u8; 8]; 15] = [
+    [0x03, 0x21, 0x05, 0x06, 0xE8, 0x81, 0x42, 0x27], // Buzzy Bell
+    [0x13, 0x41, 0x14, 0x0D, 0xD8, 0xF6, 0x23, 0x12], // Guitar
+    [0x11, 0x11, 0x08, 0x08, 0xFA, 0xB2, 0x20, 0x12], // Wurly
+    [0x31, 0x61, 0x0C, 0x07, 0xA8, 0x64, 0x61, 0x27], // Flute
+    [0x32, 0x21, 0x1E, 0x06, 0xE1, 0x76, 0x01, 0x28], // Clarinet
+    [0x02, 0x01, 0x06, 0x00, 0xA3, 0xE2, 0xF4, 0xF4], // Synth
+    [0x21, 0x61, 0x1D, 0x07, 0x82, 0x81, 0x11, 0x07], // Trumpet
+    [0x23, 0x21, 0x22, 0x17, 0xA2, 0x72, 0x01, 0x17], // Organ
+    [0x35, 0x11, 0x25, 0x00, 0x40, 0x73, 0x72, 0x01], // Bells
+    [0xB5, 0x01, 0x0F, 0x0F, 0xA8, 0xA5, 0x51, 0x02], // Vibes
+    [0x17, 0xC1, 0x24, 0x07, 0xF8, 0xF8, 0x22, 0x12], // Vibraphone
+    [0x71, 0x23, 0x11, 0x06, 0x65, 0x74, 0x18, 0x16], // Tutti
+    [0x01, 0x02, 0xD3, 0x05, 0xC9, 0x95, 0x03, 0x02], // Fretless
+    [0x61, 0x63, 0x0C, 0x00, 0x94, 0xC0, 0x33, 0xF6], // Synth Bass
+    [0x21, 0x72, 0x0D, 0x00, 0xC1, 0xD5, 0x56, 0x06], // Sweep
+];
+
+// Twice the frequency multiplier, so that the 0.5 multiplier can be an integer.
+const DOUBLED_MULTIPLIERS: [u32; 16] = [1, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 20, 24, 24, 30, 30];
+// Key scale level attenuation at octave 7, indexed by the top four bits of the F-number.
+const KEY_SCALE_LEVEL_DB: [f32; 16] = [
+    0.0, 18.0, 24.0, 27.75, 30.0, 32.25, 33.75, 35.25, 36.0, 37.5, 38.25, 39.0, 39.75, 40.5, 41.25, 42.0,
+];
+// Vibrato offsets in half F-number units, indexed by the top three bits of the F-number then the vibrato step.
+const VIBRATO_OFFSETS: [[i32; 8]; 8] = [
+    [0, 0, 0, 0, 0, 0, 0, 0],
+    [0, 0, 1, 0, 0, 0, -1, 0],
+    [0, 1, 2, 1, 0, -1, -2, -1],
+    [0, 1, 3, 1, 0, -1, -3, -1],
+    [0, 2, 4, 2, 0, -2, -4, -2],
+    [0, 2, 5, 2, 0, -2, -5, -2],
+    [0, 3, 6, 3, 0, -3, -6, -3],
+    [0, 3, 7, 3, 0, -3, -7, -3],
+];
+// Vibrato advances a step every 1024 samples (about 6.1Hz for the full cycle).
+const SAMPLES_PER_VIBRATO_STEP: u32 = 1024;
+// Tremolo is a 4.8dB triangle wave at about 3.7Hz.
+const TREMOLO_PERIOD: u32 = 13_436;
+const TREMOLO_DEPTH_DB: f32 = 4.8;
+
+// The YM2413-derived FM synth: six two-operator channels, 15 built-in instruments, and one custom instrument.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Vrc7Audio {
+    // Silencing also holds the synth in reset.
+    silenced: bool,
+    selected_register: u8,
+    custom_patch: [u8; 8],
+    channels: [Vrc7Channel; 6],
+    cycles_until_next_sample: u8,
+    sample_count: u32,
+    output: f32,
+}
+
+impl Vrc7Audio {
+    pub fn step(&mut self) {
+        if self.silenced {
+            return;
+        }
+
+        if self.cycles_until_next_sample > 0 {
+            self.cycles_until_next_sample -= 1;
+            return;
+        }
+
+        self.cycles_until_next_sample = CPU_CYCLES_PER_SAMPLE - 1;
+        let lfo = Lfo::at(self.sample_count);
+        self.sample_count = self.sample_count.wrapping_add(1);
+
+        let mut total = 0.0;
+        for channel in &mut self.channels {
+            let patch = Patch::new(if channel.instrument == 0 {
+                &self.custom_patch
+            } else {
+                &PATCHES[usize::from(channel.instrument - 1)]
+            });
+            total += channel.step(&patch, &lfo);
+        }
+
+        self.output = CHANNEL_LEVEL * total;
+    }
+
+    pub fn sample(&self) -> f32 {
+        self.output
+    }
+
+    pub fn set_silenced(&mut self, silenced: bool) {
+        if silenced {
+            *self = Self::default();
+        }
+
+        self.silenced = silenced;
+    }
+
+    pub fn select_register(&mut self, value: u8) {
+        self.selected_register = value;
+    }
+
+    pub fn write_selected_register(&mut self, value: u8) {
+        if self.silenced {
+            return;
+        }
+
+        let register = self.selected_register;
+        let channel_index = usize::from(register & 0x0F);
+        match register {
+            0x00..=0x07 => self.custom_patch[usize::from(register)] = value,
+            0x10..=0x15 => {
+                let channel = &mut self.channels[channel_index];
+                channel.f_number = (channel.f_number & 0x100) | u16::from(value);
+            }
+            0x20..=0x25 => {
+                let channel = &mut self.channels[channel_index];
+                let (sustain_on, key_on, block, f_number_high) = splitbits_named!(value, "..skbbbf");
+                channel.sustain_on = sustain_on;
+                channel.block = block;
+                channel.f_number = (u16::from(f_number_high) << 8) | (channel.f_number & 0xFF);
+                channel.set_key_on(key_on);
+            }
+            0x30..=0x35 => {
+                let channel = &mut self.channels[channel_index];
+                (channel.instrument, channel.volume) = splitbits_named!(value, "iiiivvvv");
+            }
+            _ => { /* No register here. */ }
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Vrc7Channel {
+    f_number: u16,
+    block: u8,
+    sustain_on: bool,
+    key_on: bool,
+    instrument: u8,
+    // Attenuation in 3dB steps.
+    volume: u8,
+    modulator: Operator,
+    carrier: Operator,
+    // The modulator's two most recent outputs.
+    feedback: [f32; 2],
+}
+
+impl Vrc7Channel {
+    fn set_key_on(&mut self, key_on: bool) {
+        if key_on && !self.key_on {
+            self.modulator.key_on();
+            self.carrier.key_on();
+        } else if !key_on && self.key_on {
+            self.modulator.key_off();
+            self.carrier.key_off();
+        }
+
+        self.key_on = key_on;
+    }
+
+    fn step(&mut self, patch: &Patch, lfo: &Lfo) -> f32 {
+        let pitch = Pitch { f_number: self.f_number, block: self.block, sustain_on: self.sustain_on };
+
+        let feedback_offset = if patch.feedback == 0 {
+            0.0
+        } else {
+            (self.feedback[0] + self.feedback[1]) / 2.0 * f32::from(1u8 << (patch.feedback - 1)) / 32.0
+        };
+        let modulator_output = self.modulator.step(
+            &patch.modulator, &pitch, lfo, 0.75 * f32::from(patch.modulator_total_level), feedback_offset);
+        self.feedback = [modulator_output, self.feedback[0]];
+
+        self.carrier.step(
+            &patch.carrier, &pitch, lfo, 3.0 * f32::from(self.volume), MODULATION_DEPTH * modulator_output)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Operator {
+    // 19 bits per waveform cycle.
+    phase: u32,
+    envelope_phase: EnvelopePhase,
+    // Attenuation in 0.375dB steps. 0 is full volume.
+    envelope: f32,
+}
+
+impl Operator {
+    fn key_on(&mut self) {
+        self.phase = 0;
+        self.envelope_phase = EnvelopePhase::Attack;
+    }
+
+    fn key_off(&mut self) {
+        self.envelope_phase = EnvelopePhase::Release;
+    }
+
+    fn step(&mut self, patch: &OperatorPatch, pitch: &Pitch, lfo: &Lfo, base_attenuation_db: f32, phase_offset: f32) -> f32 {
+        let mut doubled_f_number = 2 * i32::from(pitch.f_number);
+        if patch.vibrato {
+            doubled_f_number += VIBRATO_OFFSETS[usize::from(pitch.f_number >> 6)][lfo.vibrato_step];
+        }
+
+        let increment = ((doubled_f_number as u32 * DOUBLED_MULTIPLIERS[usize::from(patch.multiplier)]) << pitch.block) >> 2;
+        self.phase = (self.phase + increment) & 0x7_FFFF;
+
+        self.step_envelope(patch, pitch);
+
+        let key_scale_level_db = match patch.key_scale_level {
+            0 => 0.0,
+            level => {
+                let db = KEY_SCALE_LEVEL_DB[usize::from(pitch.f_number >> 5)] - 6.0 * f32::from(7 - pitch.block);
+                db.max(0.0) / f32::from(1u8 << (3 - level))
+            }
+        };
+        let tremolo_db = if patch.tremolo { lfo.tremolo_db } else { 0.0 };
+        let attenuation_db = ENVELOPE_STEP_DB * self.envelope + base_attenuation_db + key_scale_level_db + tremolo_db;
+
+        let mut wave = (TAU * (self.phase as f32 / 524_288.0 + phase_offset)).sin();
+        if patch.rectified && wave < 0.0 {
+            wave = 0.0;
+        }
+
+        wave * 10f32.powf(-attenuation_db / 20.0)
+    }
+
+    fn step_envelope(&mut self, patch: &OperatorPatch, pitch: &Pitch) {
+        let key_scale = if patch.key_scale_rate {
+            pitch.key_scale()
+        } else {
+            pitch.key_scale() >> 2
+        };
+        let steps_per_sample = |rate: u8| -> f32 {
+            if rate == 0 {
+                return 0.0;
+            }
+
+            let rate = (4 * rate + key_scale).min(63);
+            f32::from(4 + (rate & 0b11)) / 4.0 * 2f32.powi(i32::from(rate >> 2) - 13)
+        };
+
+        match self.envelope_phase {
+            EnvelopePhase::Attack => {
+                if patch.attack_rate == 15 {
+                    self.envelope = 0.0;
+                } else {
+                    // Attack is exponential, so it slows down as it nears full volume.
+                    self.envelope -= 0.23 * steps_per_sample(patch.attack_rate) * (self.envelope + 1.0);
+                }
+
+                if self.envelope <= 0.0 {
+                    self.envelope = 0.0;
+                    self.envelope_phase = EnvelopePhase::Decay;
+                }
+            }
+            EnvelopePhase::Decay => {
+                let sustain_level = 8.0 * f32::from(patch.sustain_level);
+                self.envelope += steps_per_sample(patch.decay_rate);
+                if self.envelope >= sustain_level {
+                    self.envelope = sustain_level;
+                    self.envelope_phase = EnvelopePhase::Sustain;
+                }
+            }
+            // Percussive instruments keep decaying while the key is held.
+            EnvelopePhase::Sustain if !patch.sustained => self.envelope += steps_per_sample(patch.release_rate),
+            EnvelopePhase::Sustain => {}
+            EnvelopePhase::Release => {
+                let release_rate = if pitch.sustain_on {
+                    5
+                } else if patch.sustained {
+                    patch.release_rate
+                } else {
+                    7
+                };
+                self.envelope += steps_per_sample(release_rate);
+            }
+        }
+
+        self.envelope = self.envelope.min(ENVELOPE_MAX);
+    }
+}
+
+impl Default for Operator {
+    fn default() -> Self {
+        Self { phase: 0, envelope_phase: EnvelopePhase::Release, envelope: ENVELOPE_MAX }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+enum EnvelopePhase {
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+struct Pitch {
+    f_number: u16,
+    block: u8,
+    sustain_on: bool,
+}
+
+impl Pitch {
+    fn key_scale(&self) -> u8 {
+        (self.block << 1) | (self.f_number >> 8) as u8
+    }
+}
+
+struct Lfo {
+    vibrato_step: usize,
+    tremolo_db: f32,
+}
+
+impl Lfo {
+    fn at(sample_count: u32) -> Self {
+        let half_period = TREMOLO_PERIOD / 2;
+        let position = sample_count % TREMOLO_PERIOD;
+        let triangle = if position < half_period { position } else { TREMOLO_PERIOD - position };
+        Self {
+            vibrato_step: ((sample_count / SAMPLES_PER_VIBRATO_STEP) % 8) as usize,
+            tremolo_db: TREMOLO_DEPTH_DB * triangle as f32 / half_period as f32,
+        }
+    }
+}
+
+struct Patch {
+    modulator: OperatorPatch,
+    carrier: OperatorPatch,
+    // Attenuation in 0.75dB steps.
+    modulator_total_level: u8,
+    feedback: u8,
+}
+
+impl Patch {
+    fn new(bytes: &[u8; 8]) -> Self {
+        let (modulator_key_scale_level, modulator_total_level) = splitbits_named!(bytes[2], "kkllllll");
+        let (carrier_key_scale_level, carrier_rectified, modulator_rectified, feedback) =
+            splitbits_named!(bytes[3], "kk.cmfff");
+        Self {
+            modulator: OperatorPatch::new(bytes[0], modulator_key_scale_level, modulator_rectified, bytes[4], bytes[6]),
+            carrier: OperatorPatch::new(bytes[1], carrier_key_scale_level, carrier_rectified, bytes[5], bytes[7]),
+            modulator_total_level,
+            feedback,
+        }
+    }
+}
+
+struct OperatorPatch {
+    tremolo: bool,
+    vibrato: bool,
+    // Whether the envelope holds at the sustain level (rather than decaying) while the key is held.
+    sustained: bool,
+    key_scale_rate: bool,
+    multiplier: u8,
+    key_scale_level: u8,
+    // Whether the negative half of the sine wave is cut off.
+    rectified: bool,
+    attack_rate: u8,
+    decay_rate: u8,
+    sustain_level: u8,
+    release_rate: u8,
+}
+
+impl OperatorPatch {
+    fn new(flags: u8, key_scale_level: u8, rectified: bool, rates: u8, levels: u8) -> Self {
+        let (tremolo, vibrato, sustained, key_scale_rate, multiplier) = splitbits_named!(flags, "tvskmmmm");
+        let (attack_rate, decay_rate) = splitbits_named!(rates, "aaaadddd");
+        let (sustain_level, release_rate) = splitbits_named!(levels, "ssssrrrr");
+        Self {
+            tremolo, vibrato, sustained, key_scale_rate, multiplier,
+            key_scale_level, rectified,
+            attack_rate, decay_rate, sustain_level, release_rate,
+        }
+    }
+}
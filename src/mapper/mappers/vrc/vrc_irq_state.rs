@@ -1,8 +1,10 @@
+use serde::{Deserialize, Serialize};
 use splitbits::splitbits_named;
 
 use crate::counter::irq_counter_info::IrqCounterInfo;
 use crate::bus::Bus;
 
+#[derive(Serialize, Deserialize)]
 pub struct VrcIrqState {
     enabled: bool,
     enable_upon_acknowledgement: bool,
@@ -92,7 +94,7 @@ impl Default for VrcIrqState {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
 enum IrqMode {
     Scanline,
     Cycle,
@@ -1,76 +1,123 @@
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
 use crate::apu::apu_clock::ApuClock;
-use crate::ppu::ppu_clock::{LastCycle, PpuClock};
+use crate::power_on::{PowerOnState, PowerOnValues};
+use crate::ppu::ppu_clock::{LastCycle, Overclock, PpuClock};
+use crate::region::Region;
 
 use CycleType::*;
-const NTSC_SCHEDULE: [&[CycleType]; 12] = [
-    &[Apu, CpuFirstHalf, PpuFirstHalf],
-    &[],
-    &[PpuSecondHalf],
-    &[],
-    &[CpuSecondHalf, PpuFirstHalf],
-    &[],
-    &[PpuSecondHalf],
-    &[],
-    &[PpuFirstHalf],
-    &[],
-    &[PpuSecondHalf],
-    &[],
-];
-
-const NTSC_SCHEDULE_WITH_LOGGING: [&[CycleType]; 12] = [
-    &[ApuWithLogging, CpuFirstHalfWithLogging, PpuFirstHalfWithLogging],
-    &[],
-    &[PpuSecondHalf],
-    &[],
-    &[CpuSecondHalfWithLogging, PpuFirstHalfWithLogging],
-    &[],
-    &[PpuSecondHalf],
-    &[],
-    &[PpuFirstHalfWithLogging],
-    &[],
-    &[PpuSecondHalf],
-    &[],
-];
 
+// The CPU and PPU each run once every fixed number of master clock cycles. A schedule lists what runs
+// during each master cycle, and repeats once both the CPU and PPU are back at the start of their cycles.
+// There is one schedule per CPU/PPU alignment.
+static NTSC_SCHEDULES: LazyLock<Schedules> = LazyLock::new(|| build_schedules(12, 4, false));
+static NTSC_SCHEDULES_WITH_LOGGING: LazyLock<Schedules> = LazyLock::new(|| build_schedules(12, 4, true));
+static PAL_SCHEDULES: LazyLock<Schedules> = LazyLock::new(|| build_schedules(16, 5, false));
+static PAL_SCHEDULES_WITH_LOGGING: LazyLock<Schedules> = LazyLock::new(|| build_schedules(16, 5, true));
+static DENDY_SCHEDULES: LazyLock<Schedules> = LazyLock::new(|| build_schedules(15, 5, false));
+static DENDY_SCHEDULES_WITH_LOGGING: LazyLock<Schedules> = LazyLock::new(|| build_schedules(15, 5, true));
+
+type Schedule = Vec<Vec<CycleType>>;
+type Schedules = [Schedule; CpuPpuAlignment::COUNT];
+
+// Placeholder for deserialization. MasterClock::load_state keeps the schedule that was already in use.
+fn default_schedule() -> &'static [Vec<CycleType>] {
+    &NTSC_SCHEDULES[0]
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct MasterClock {
     master_cycle: u64,
+    region: Region,
+    // Saved in the save state header instead, since it must match rather than be restored.
+    #[serde(skip)]
+    alignment: CpuPpuAlignment,
 
     cpu_cycle: i64,
     ppu_clock: PpuClock,
     pub apu_clock: ApuClock,
 
-    schedule: [&'static [CycleType]; 12],
+    // Not part of save states since it never changes after start up.
+    #[serde(skip, default = "default_schedule")]
+    schedule: &'static [Vec<CycleType>],
 }
 
 impl MasterClock {
-    pub fn new(starting_cpu_cycle: i64, ppu_clock: PpuClock) -> Self {
-        Self {
-            master_cycle: 0,
+    pub fn new(starting_cpu_cycle: i64, ppu_clock: PpuClock, region: Region, alignment: CpuPpuAlignment) -> Self {
+        let schedules: &'static Schedules = match region {
+            Region::Ntsc => &NTSC_SCHEDULES,
+            Region::Pal => &PAL_SCHEDULES,
+            Region::Dendy => &DENDY_SCHEDULES,
+        };
 
-            cpu_cycle: starting_cpu_cycle,
-            ppu_clock,
-            apu_clock: ApuClock::new(),
+        Self::with_schedule(starting_cpu_cycle, ppu_clock, region, alignment, &schedules[alignment.index()])
+    }
 
-            schedule: NTSC_SCHEDULE,
-        }
+    pub fn new_with_diff_logging(starting_cpu_cycle: i64, ppu_clock: PpuClock, region: Region, alignment: CpuPpuAlignment) -> Self {
+        let schedules: &'static Schedules = match region {
+            Region::Ntsc => &NTSC_SCHEDULES_WITH_LOGGING,
+            Region::Pal => &PAL_SCHEDULES_WITH_LOGGING,
+            Region::Dendy => &DENDY_SCHEDULES_WITH_LOGGING,
+        };
+
+        Self::with_schedule(starting_cpu_cycle, ppu_clock, region, alignment, &schedules[alignment.index()])
     }
 
-    pub fn new_with_diff_logging(starting_cpu_cycle: i64, ppu_clock: PpuClock) -> Self {
+    fn with_schedule(
+        starting_cpu_cycle: i64,
+        mut ppu_clock: PpuClock,
+        region: Region,
+        alignment: CpuPpuAlignment,
+        schedule: &'static [Vec<CycleType>],
+    ) -> Self {
+        ppu_clock.set_region(region);
         Self {
             master_cycle: 0,
+            region,
+            alignment,
 
             cpu_cycle: starting_cpu_cycle,
             ppu_clock,
-            apu_clock: ApuClock::new(),
+            apu_clock: ApuClock::new(region),
 
-            schedule: NTSC_SCHEDULE_WITH_LOGGING,
+            schedule,
         }
     }
 
+    // Restores a saved clock while keeping the current schedule, alignment, and overclock setting.
+    pub fn load_state(&mut self, saved: MasterClock) {
+        let schedule = self.schedule;
+        let alignment = self.alignment;
+        let overclock = self.ppu_clock.overclock();
+        *self = saved;
+        self.schedule = schedule;
+        self.alignment = alignment;
+        self.ppu_clock.set_overclock(overclock);
+    }
+
+    pub fn set_overclock(&mut self, overclock: Overclock) {
+        self.ppu_clock.set_overclock(overclock);
+    }
+
+    pub fn set_overclock_at_next_frame(&mut self, overclock: Overclock) {
+        self.ppu_clock.set_overclock_at_next_frame(overclock);
+    }
+
     pub fn master_cycle(&self) -> u64 {
         self.master_cycle
     }
 
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    pub fn cpu_ppu_alignment(&self) -> CpuPpuAlignment {
+        self.alignment
+    }
+
     pub fn cpu_cycle(&self) -> i64 {
         self.cpu_cycle
     }
@@ -94,7 +141,7 @@ impl MasterClock {
             .expect("Sane index.");
         self.master_cycle += 1;
         let end_reached = self.master_cycle.is_multiple_of(period);
-        (self.schedule[index], end_reached)
+        (self.schedule[index].as_slice(), end_reached)
     }
 
     // TODO: Remove this. Stepping the master clock should do this automatically.
@@ -112,6 +159,82 @@ impl MasterClock {
     }
 }
 
+fn build_schedules(cpu_divider: usize, ppu_divider: usize, logging: bool) -> Schedules {
+    // Each alignment must put the CPU in a different phase of the PPU clock.
+    assert!(CpuPpuAlignment::COUNT <= ppu_divider, "More CPU/PPU alignments than PPU phases.");
+    CpuPpuAlignment::ALL.map(|alignment| build_schedule(cpu_divider, ppu_divider, alignment, logging))
+}
+
+// Each CPU and PPU cycle is split into two halves. The second half of a CPU cycle lines up with the start
+// of the following PPU cycle. The APU runs at the start of every CPU cycle. The alignment delays the CPU
+// by a number of master cycles, shifting it relative to the PPU.
+fn build_schedule(cpu_divider: usize, ppu_divider: usize, alignment: CpuPpuAlignment, logging: bool) -> Schedule {
+    let period = least_common_multiple(cpu_divider, ppu_divider);
+    let mut schedule = vec![Vec::new(); period];
+    for master_cycle in (alignment.index()..period + alignment.index()).step_by(cpu_divider) {
+        let second_half = (master_cycle + ppu_divider) % period;
+        let master_cycle = master_cycle % period;
+        if logging {
+            schedule[master_cycle].extend([ApuWithLogging, CpuFirstHalfWithLogging]);
+            schedule[second_half].push(CpuSecondHalfWithLogging);
+        } else {
+            schedule[master_cycle].extend([Apu, CpuFirstHalf]);
+            schedule[second_half].push(CpuSecondHalf);
+        }
+    }
+
+    for master_cycle in (0..period).step_by(ppu_divider) {
+        schedule[master_cycle].push(if logging { PpuFirstHalfWithLogging } else { PpuFirstHalf });
+        schedule[master_cycle + ppu_divider / 2].push(PpuSecondHalf);
+    }
+
+    schedule
+}
+
+fn least_common_multiple(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    a / x * b
+}
+
+// Which of the four phases the CPU clock is in relative to the PPU clock. Real consoles power on in a random one.
+// PAL and Dendy consoles divide each PPU cycle into five master cycles rather than four, so they have a
+// fifth alignment. Only the first four are offered for every region, so the fifth is never emulated.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct CpuPpuAlignment(u8);
+
+impl CpuPpuAlignment {
+    pub const COUNT: usize = 4;
+    pub const ALL: [CpuPpuAlignment; Self::COUNT] = [Self(0), Self(1), Self(2), Self(3)];
+
+    pub fn random() -> Self {
+        let random_byte = PowerOnValues::new(PowerOnState::Random, PowerOnValues::generate_seed()).values(1)[0];
+        Self::ALL[usize::from(random_byte) % Self::COUNT]
+    }
+
+    pub fn index(self) -> usize {
+        self.0.into()
+    }
+}
+
+impl FromStr for CpuPpuAlignment {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        if value.eq_ignore_ascii_case("random") {
+            return Ok(Self::random());
+        }
+
+        value.parse::<usize>().ok()
+            .and_then(|index| Self::ALL.get(index).copied())
+            .ok_or_else(|| format!("Invalid CPU/PPU alignment: {value}. Must be 0 through 3, or random."))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum CycleType {
     Apu,
     ApuWithLogging,
@@ -123,3 +246,38 @@ pub enum CycleType {
     PpuFirstHalfWithLogging,
     PpuSecondHalf,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alignment_delays_cpu_relative_to_ppu() {
+        for alignment in CpuPpuAlignment::ALL {
+            let schedule = &NTSC_SCHEDULES[alignment.index()];
+            let first_half = schedule.iter().position(|types| types.contains(&CpuFirstHalf)).unwrap();
+            let second_half = schedule.iter().position(|types| types.contains(&CpuSecondHalf)).unwrap();
+            assert_eq!(first_half, alignment.index());
+            assert_eq!(second_half, alignment.index() + 4);
+            assert_eq!(schedule[0].last(), Some(&PpuFirstHalf));
+        }
+    }
+
+    #[test]
+    fn pal_and_dendy_alignments_are_distinct() {
+        for schedules in [&*PAL_SCHEDULES, &*DENDY_SCHEDULES] {
+            for (i, schedule) in schedules.iter().enumerate() {
+                let first_half = schedule.iter().position(|types| types.contains(&CpuFirstHalf)).unwrap();
+                assert_eq!(first_half, i);
+                assert!(schedules[i + 1..].iter().all(|other| other != schedule));
+            }
+        }
+    }
+
+    #[test]
+    fn parse_alignment() {
+        assert_eq!("2".parse::<CpuPpuAlignment>(), Ok(CpuPpuAlignment(2)));
+        assert!("4".parse::<CpuPpuAlignment>().is_err());
+        assert!("random".parse::<CpuPpuAlignment>().unwrap().index() < CpuPpuAlignment::COUNT);
+    }
+}
@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Display)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Display, Serialize, Deserialize)]
 pub enum MemoryPresence {
     Absent,
     Supported,
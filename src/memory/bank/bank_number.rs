@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::memory::bank::bank::MemoryPresence;
 use crate::memory::regions::ciram::CiramSide;
 use crate::memory::register_ids::bank::{ChrBankRegisterId, MetaRegisterId, PrgBankRegisterId};
@@ -5,7 +7,7 @@ use crate::memory::register_ids::read_write_status::{ReadStatusRegisterId, Write
 use crate::memory::register_ids::source::{ChrSourceRegisterId, PrgSourceRegisterId};
 use crate::memory::window::{ChrSource, PrgSource};
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct BankNumber(u16);
 
 impl BankNumber {
@@ -34,7 +36,7 @@ impl From<u8> for BankNumber {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrgBankRegisters {
     registers: [BankNumber; 11],
     read_statuses: [ReadStatus; 16],
@@ -90,6 +92,13 @@ impl PrgBankRegisters {
         }
     }
 
+    // Extra values are ignored.
+    pub fn fill_registers(&mut self, values: &[u8]) {
+        for (register, &value) in self.registers.iter_mut().zip(values) {
+            *register = BankNumber::from_u8(value);
+        }
+    }
+
     pub fn read_status(&self, id: ReadStatusRegisterId) -> ReadStatus {
         self.read_statuses[id as usize]
     }
@@ -115,7 +124,7 @@ impl PrgBankRegisters {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChrBankRegisters {
     registers: [BankNumber; 16],
     chr_meta_registers: [ChrBankRegisterId; 4],
@@ -230,6 +239,13 @@ impl ChrBankRegisters {
         }
     }
 
+    // Extra values are ignored.
+    pub fn fill_registers(&mut self, values: &[u8]) {
+        for (register, &value) in self.registers.iter_mut().zip(values) {
+            *register = BankNumber::from_u8(value);
+        }
+    }
+
     pub fn read_status(&self, id: ReadStatusRegisterId) -> ReadStatus {
         self.read_statuses[id as usize]
     }
@@ -255,14 +271,14 @@ impl ChrBankRegisters {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum ReadStatus {
     Disabled,
     Enabled,
     ReadOnlyZeros,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum WriteStatus {
     Disabled,
     Enabled,
@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::memory::cpu::cpu_address::CpuAddress;
 use crate::memory::signal_level::SignalLevel;
 use crate::util::edge_detector::EdgeDetector;
 
+#[derive(Serialize, Deserialize)]
 pub struct CpuPinout {
     // AD1 (Audio Pinout: Both pulse waves)
     // AD2 (Audio Pinout: Triangle, Noise, DPCM)
@@ -12,6 +12,9 @@ use crate::memory::register_ids::source::PrgSourceRegisterId;
 use crate::memory::window::{PrgWindow, PrgSource};
 use crate::util::unit::KIBIBYTE;
 use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+const TRAINER_START: u16 = 0x7000;
 
 pub struct PrgMemory {
     layouts: PrgLayouts,
@@ -125,6 +128,25 @@ impl PrgMemory {
         }
     }
 
+    // Copiers loaded trainers straight into PRG RAM, so write protection doesn't apply.
+    pub fn load_trainer(&mut self, trainer: &[u8]) -> Result<(), String> {
+        for (address, &value) in (TRAINER_START..).zip(trainer) {
+            match self.memory_maps[self.layout_index() as usize].index_for_address(CpuAddress::new(address)) {
+                Some((index, PrgMemTypeStatus::WorkRam(..))) if !self.work_ram.is_empty() =>
+                    self.work_ram[index - self.save_ram.size()] = value,
+                Some((index, PrgMemTypeStatus::SaveRam(..))) if !self.save_ram.is_empty() =>
+                    self.save_ram[index] = value,
+                _ => return Err(format!("The mapper has no PRG RAM at ${address:04X} to load the trainer into.")),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn work_ram_size(&self) -> u32 {
+        self.work_ram.size()
+    }
+
     // Very few mappers should use this.
     pub fn write_raw_work_ram(&mut self, index: u32, value: u8) {
         self.work_ram[index] = value;
@@ -174,6 +196,11 @@ impl PrgMemory {
         self.regs.reset_registers();
     }
 
+    pub fn fill_bank_registers(&mut self, values: &[u8]) {
+        self.regs.fill_registers(values);
+        self.update_page_ids();
+    }
+
     pub fn ram_present(&self) -> bool {
         !self.work_ram.is_empty() || !self.save_ram.is_empty()
     }
@@ -200,6 +227,46 @@ impl PrgMemory {
         }
     }
 
+    pub fn save_ram(&self) -> Vec<u8> {
+        self.save_ram.to_vec()
+    }
+
+    pub fn load_save_ram(&mut self, data: &[u8]) -> Result<(), String> {
+        self.save_ram.load(data)
+    }
+
+    pub fn state(&self) -> PrgMemoryState {
+        PrgMemoryState {
+            regs: self.regs.clone(),
+            base_memory_map_index: self.base_memory_map_index,
+            memory_map_index: self.memory_map_index,
+            rom_outer_bank_number: self.rom_outer_bank_number,
+            work_ram: self.work_ram.to_vec(),
+            save_ram: self.save_ram.to_vec(),
+        }
+    }
+
+    pub fn check_state(&self, state: &PrgMemoryState) -> Result<(), String> {
+        if state.base_memory_map_index >= self.layouts.count() || state.memory_map_index >= self.layouts.count() {
+            return Err(format!("PRG layout index {} is out of range.", state.memory_map_index));
+        }
+
+        self.work_ram.check_load(&state.work_ram)?;
+        self.save_ram.check_load(&state.save_ram)
+    }
+
+    // The state must have passed check_state().
+    // The memory maps aren't saved since they are fully determined by the bank registers.
+    pub fn load_state(&mut self, state: PrgMemoryState) {
+        self.work_ram.load(&state.work_ram).expect("Work RAM size should have been checked.");
+        self.save_ram.load(&state.save_ram).expect("Save RAM size should have been checked.");
+        self.regs = state.regs;
+        self.base_memory_map_index = state.base_memory_map_index;
+        self.memory_map_index = state.memory_map_index;
+        // Also updates the page ids for every memory map.
+        self.set_rom_outer_bank_number(state.rom_outer_bank_number);
+    }
+
     fn update_page_ids(&mut self) {
         for memory_map in &mut self.memory_maps {
             memory_map.update_page_ids(&self.regs);
@@ -254,3 +321,13 @@ impl PrgMemory {
         result
     }
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct PrgMemoryState {
+    regs: PrgBankRegisters,
+    base_memory_map_index: u8,
+    memory_map_index: u8,
+    rom_outer_bank_number: u8,
+    work_ram: Vec<u8>,
+    save_ram: Vec<u8>,
+}
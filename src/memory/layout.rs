@@ -21,6 +21,7 @@ pub struct Layout {
     prg_layout_index: u8,
     prg_layouts: PrgLayouts,
     prg_rom_outer_bank_layout: OuterBankLayout,
+    miscellaneous_rom_extends_prg_rom: bool,
 
     chr_rom_max_size: u32,
     align_large_chr_windows: bool,
@@ -47,7 +48,14 @@ impl Layout {
 
     pub fn make_mapper_params(self, metadata: &ResolvedMetadata, cartridge: &Cartridge, allow_saving: bool)
             -> Result<(PrgMemory, ChrMemory, &'static [NameTableMirroring]), String> {
-        let prg_rom_size = cartridge.prg_rom().size();
+        let miscellaneous_rom = cartridge.miscellaneous_rom().as_slice();
+        let prg_rom = if self.miscellaneous_rom_extends_prg_rom && !miscellaneous_rom.is_empty() {
+            RawMemory::from_vec([&cartridge.prg_rom().to_vec(), miscellaneous_rom].concat())?
+        } else {
+            cartridge.prg_rom().clone()
+        };
+
+        let prg_rom_size = prg_rom.size();
         if prg_rom_size > self.prg_layouts.rom_max_bank_sizes().full_size() {
             return Err(format!("PRG ROM size of {}KiB is too large for this mapper.", prg_rom_size / KIBIBYTE));
         }
@@ -96,7 +104,7 @@ impl Layout {
         let prg_memory = PrgMemory::new(
             self.prg_layouts,
             self.prg_layout_index,
-            cartridge.prg_rom().clone(),
+            prg_rom,
             self.prg_rom_outer_bank_layout,
             RawMemory::new(metadata.prg_work_ram_size)?,
             SaveRam::open(&cartridge.path().to_prg_save_ram_file_path(), metadata.prg_save_ram_size, allow_saving),
@@ -122,6 +130,17 @@ impl Layout {
         Ok((prg_memory, chr_memory, self.name_table_mirrorings))
     }
 
+    // Re-applies the hard-wired bank register values, in case something else has overwritten them.
+    pub fn apply_bank_register_overrides(&self, prg_memory: &mut PrgMemory, chr_memory: &mut ChrMemory) {
+        for (register_id, bank_number) in self.bank_register_overrides.as_iter() {
+            prg_memory.set_bank_register(register_id, bank_number.to_raw());
+        }
+
+        for (register_id, bank_number) in self.chr_bank_register_overrides.as_iter() {
+            chr_memory.set_bank_register(register_id, bank_number.to_raw());
+        }
+    }
+
     pub fn supports_prg_ram(&self) -> bool {
         self.prg_layouts.ram_supported()
     }
@@ -134,6 +153,7 @@ pub struct LayoutBuilder {
     prg_layout_index: u8,
     prg_rom_outer_bank_layout: Option<OuterBankLayout>,
     prg_rom_inner_bank_size: Option<u32>,
+    miscellaneous_rom_extends_prg_rom: bool,
 
     chr_rom_max_size: Option<u32>,
     chr_layouts: ConstVec<ChrLayout, 16>,
@@ -161,6 +181,7 @@ impl LayoutBuilder {
             prg_layouts: ConstVec::new(),
             prg_rom_outer_bank_layout: None,
             prg_rom_inner_bank_size: None,
+            miscellaneous_rom_extends_prg_rom: false,
 
             chr_rom_max_size: None,
             align_large_chr_windows: true,
@@ -174,10 +195,8 @@ impl LayoutBuilder {
             cartridge_selection_name_table_mirrorings: [
                 Some(NameTableMirroring::HORIZONTAL),
                 Some(NameTableMirroring::VERTICAL),
-                // Four screen
-                None,
-                // Four screen
-                None,
+                Some(NameTableMirroring::FOUR_SCREEN),
+                Some(NameTableMirroring::FOUR_SCREEN),
             ],
             name_table_mirrorings: &[],
 
@@ -221,6 +240,12 @@ impl LayoutBuilder {
         self
     }
 
+    // For boards with an external ROM that is banked in as if it came after the cartridge's own PRG ROM.
+    pub const fn miscellaneous_rom_extends_prg_rom(&mut self) -> &mut Self {
+        self.miscellaneous_rom_extends_prg_rom = true;
+        self
+    }
+
     pub const fn chr_rom_max_size(&mut self, value: u32) -> &mut Self {
         self.chr_rom_max_size = Some(value);
         self
@@ -340,6 +365,7 @@ impl LayoutBuilder {
             prg_layouts,
             prg_layout_index: self.prg_layout_index,
             prg_rom_outer_bank_layout,
+            miscellaneous_rom_extends_prg_rom: self.miscellaneous_rom_extends_prg_rom,
 
             chr_rom_max_size: self.chr_rom_max_size.expect("chr_rom_max_size must be set"),
             chr_layouts,
@@ -1,6 +1,7 @@
 use std::num::NonZeroU16;
 
 use log::{info, warn};
+use serde::{Deserialize, Serialize};
 
 use crate::memory::address_template::bank_sizes::BankSizes;
 use crate::memory::bank::bank::MemoryPresence;
@@ -225,6 +226,14 @@ impl ChrMemory {
         (self.ram.size() / bank_size).try_into().unwrap()
     }
 
+    pub fn ram_size(&self) -> u32 {
+        self.ram.size()
+    }
+
+    pub fn write_raw_ram(&mut self, index: u32, value: u8) {
+        self.ram[index] = value;
+    }
+
     pub fn layout_index(&self) -> u8 {
         self.memory_map_index
     }
@@ -245,6 +254,11 @@ impl ChrMemory {
         self.regs.reset_registers();
     }
 
+    pub fn fill_bank_registers(&mut self, values: &[u8]) {
+        self.regs.fill_registers(values);
+        self.update_page_ids();
+    }
+
     pub fn set_layout(&mut self, index: u8) {
         assert!(index < self.layouts.count());
         self.base_memory_map_index = index;
@@ -331,6 +345,35 @@ impl ChrMemory {
         self.update_page_ids();
     }
 
+    pub fn state(&self) -> ChrMemoryState {
+        ChrMemoryState {
+            regs: self.regs.clone(),
+            base_memory_map_index: self.base_memory_map_index,
+            memory_map_index: self.memory_map_index,
+            rom_outer_bank_number: self.rom_outer_bank_number,
+            ram: self.ram.to_vec(),
+        }
+    }
+
+    pub fn check_state(&self, state: &ChrMemoryState) -> Result<(), String> {
+        if state.base_memory_map_index >= self.layouts.count() || state.memory_map_index >= self.layouts.count() {
+            return Err(format!("CHR layout index {} is out of range.", state.memory_map_index));
+        }
+
+        self.ram.check_load(&state.ram)
+    }
+
+    // The state must have passed check_state().
+    // The memory maps aren't saved since they are fully determined by the bank registers.
+    pub fn load_state(&mut self, state: ChrMemoryState) {
+        self.ram.load(&state.ram).expect("CHR RAM size should have been checked.");
+        self.regs = state.regs;
+        self.base_memory_map_index = state.base_memory_map_index;
+        self.memory_map_index = state.memory_map_index;
+        // Also updates the page ids for every memory map.
+        self.set_rom_outer_bank_number(state.rom_outer_bank_number);
+    }
+
     fn update_page_ids(&mut self) {
         for page_mapping in &mut self.memory_maps {
             page_mapping.update_page_ids(&self.regs);
@@ -430,7 +473,7 @@ impl ChrMemory {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct PpuPeek {
     value: u8,
     source: PeekSource,
@@ -452,7 +495,7 @@ impl PpuPeek {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum PeekSource {
     Rom(BankNumber),
     Ram(BankNumber),
@@ -472,4 +515,13 @@ impl PeekSource {
             NameTableSource::MapperCustom { page_id } => Self::MapperCustom { page_id },
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ChrMemoryState {
+    regs: ChrBankRegisters,
+    base_memory_map_index: u8,
+    memory_map_index: u8,
+    rom_outer_bank_number: u8,
+    ram: Vec<u8>,
+}
@@ -56,7 +56,6 @@ impl ChrMemoryMap {
         // TODO: Map through 0x3EFF
         if page_mappings.len() == 8 {
             let Some(cartridge_name_table_mirroring) = cartridge_name_table_mirroring else {
-                // TODO: Promote this to a panic once VS mode is supported.
                 return Err("The mapper must specify mappings from 0x2000 to 0x2FFF when four screen mirroring is specified.".into());
             };
 
@@ -68,7 +67,8 @@ impl ChrMemoryMap {
             );
             if name_table_mirroring_fixed {
                 for quadrant in cartridge_name_table_mirroring.quadrants() {
-                    assert!(matches!(quadrant, NameTableSource::Ciram(_)), "Configure non-CIRAM mirrorings using chr_layouts instead.");
+                    assert!(matches!(quadrant, NameTableSource::Ciram(_)) || cartridge_name_table_mirroring.is_four_screen(),
+                        "Configure non-CIRAM mirrorings using chr_layouts instead.");
                     page_mappings.push(ChrMapping::from_name_table_source(quadrant, address_template, address_template));
                 }
             } else {
@@ -76,7 +76,8 @@ impl ChrMemoryMap {
                     .zip(cartridge_name_table_mirroring.quadrants())
                     .zip(ChrSourceRegisterId::ALL_NAME_TABLE_SOURCE_IDS);
                 for ((addr, quadrant), reg_id) in quadrants_with_source_reg_ids {
-                    assert!(matches!(quadrant, NameTableSource::Ciram(_)), "Configure non-CIRAM mirrorings using chr_layouts instead.");
+                    assert!(matches!(quadrant, NameTableSource::Ciram(_)) || cartridge_name_table_mirroring.is_four_screen(),
+                        "Configure non-CIRAM mirrorings using chr_layouts instead.");
                     let window = ChrWindow::new(addr, addr + 0x3FF, 0x400, ChrSourceProvider::Switchable(reg_id));
                     page_mappings.push(ChrMapping::from_name_table_source_with_register(
                         window, quadrant, address_template, address_template, reg_id, regs));
@@ -2,6 +2,7 @@ use std::fmt;
 use std::marker::ConstParamTy;
 
 use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
 use splitbits::{splitbits_named, splitbits_named_into_ux, splitbits_named_ux, combinebits, replacebits};
 use ux::u5;
 
@@ -26,7 +27,7 @@ const PALETTE_TABLE_START: u16 = 0x3F00;
  * | +++----------------- Fine Y Scroll
  * +--------------------- Unused, always zero
  */
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Default, ConstParamTy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Default, ConstParamTy, Serialize, Deserialize)]
 pub struct PpuAddress {
     address: u16,
 }
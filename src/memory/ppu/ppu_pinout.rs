@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use crate::memory::ppu::ppu_address::PpuAddress;
 use crate::util::edge_detector::EdgeDetector;
 
+#[derive(Serialize, Deserialize)]
 pub struct PpuPinout {
     // cpu_data_bus: u8, // D0-D8
     // cpu_address_bus: u3, // A2-A0
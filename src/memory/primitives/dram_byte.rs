@@ -1,8 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 // The smallest value for oam_stress to pass. Eight frames is almost 134 ms,
 // which is 100x how long the wiki says OAM will retain its values.
 const OAM_DECAY_TICKS: u8 = 8;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct DramByte {
     value: u8,
     // Zeros in the mask are zeros in the peeked/read value, not open bus.
@@ -1,4 +1,4 @@
-use std::fs::OpenOptions;
+use std::fs::{DirBuilder, OpenOptions};
 use std::ops::{Index, IndexMut, Range, RangeInclusive};
 use std::path::Path;
 
@@ -145,6 +145,22 @@ impl RawMemory {
     pub fn is_empty(&self) -> bool {
         matches!(self, Self::Absent)
     }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        (0..self.size()).map(|index| self[index]).collect()
+    }
+
+    // Overwrites the full contents. The size must not change.
+    pub fn load(&mut self, data: &[u8]) -> Result<(), String> {
+        self.check_load(data)?;
+        load_bytes(self, data);
+        Ok(())
+    }
+
+    // Whether load() would accept the data.
+    pub fn check_load(&self, data: &[u8]) -> Result<(), String> {
+        check_size(self.size(), data)
+    }
 }
 
 impl Index<u32> for RawMemory {
@@ -201,6 +217,24 @@ impl IndexMut<u32> for RawMemory {
 #[derive(Clone, Debug)]
 pub struct RawMemoryArray<const SIZE: u32>(Box<[u8]>);
 
+impl <const SIZE: u32> RawMemoryArray<SIZE> {
+    pub fn from_raw(raw: &[u8]) -> Result<RawMemoryArray<SIZE>, String> {
+        if raw.len() != SIZE as usize {
+            return Err(format!("Memory must be exactly {SIZE} bytes, but was {} bytes.", raw.len()));
+        }
+
+        Ok(RawMemoryArray(raw.into()))
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn hash(&self) -> u32 {
+        crc32fast::hash(&self.0)
+    }
+}
+
 impl <const SIZE: u32> Index<u32> for RawMemoryArray<SIZE> {
     type Output = u8;
 
@@ -266,6 +300,11 @@ impl SaveRam {
             return SaveRam { mode_state: SaveRamModeState::NonSaving(vec![0; size as usize]) }
         }
 
+        if let Some(directory) = path.parent().filter(|directory| !directory.as_os_str().is_empty())
+                && let Err(err) = DirBuilder::new().recursive(true).create(directory) {
+            warn!("Failed to create Save RAM directory {}. {err}", directory.display());
+        }
+
         let file = OpenOptions::new()
             .read(true)
             .write(true)
@@ -296,6 +335,36 @@ impl SaveRam {
     pub fn is_empty(&self) -> bool {
         matches!(self.mode_state, SaveRamModeState::Empty)
     }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        (0..self.size()).map(|index| self[index]).collect()
+    }
+
+    // Overwrites the full contents. The size must not change.
+    pub fn load(&mut self, data: &[u8]) -> Result<(), String> {
+        self.check_load(data)?;
+        load_bytes(self, data);
+        Ok(())
+    }
+
+    // Whether load() would accept the data.
+    pub fn check_load(&self, data: &[u8]) -> Result<(), String> {
+        check_size(self.size(), data)
+    }
+}
+
+fn check_size(size: u32, data: &[u8]) -> Result<(), String> {
+    if data.len() != size as usize {
+        return Err(format!("Expected {size} bytes of memory but found {}.", data.len()));
+    }
+
+    Ok(())
+}
+
+fn load_bytes<M: IndexMut<u32, Output = u8>>(memory: &mut M, data: &[u8]) {
+    for (index, &value) in data.iter().enumerate() {
+        memory[index as u32] = value;
+    }
 }
 
 impl Index<u32> for SaveRam {
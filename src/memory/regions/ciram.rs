@@ -1,15 +1,19 @@
 // Clippy bug.
 #![allow(clippy::needless_borrow)]
 
+use serde::{Deserialize, Serialize};
+
 use crate::memory::bank::bank_number::WriteStatus;
 use crate::util::unit::KIBIBYTE;
 
-const CIRAM_SIZE: usize = 2 * KIBIBYTE as usize;
+pub const CIRAM_SIZE: usize = 2 * KIBIBYTE as usize;
 const SIDE_SIZE: usize = KIBIBYTE as usize;
 
 // Console-internal name table RAM.
 // TODO: Is CIRAM disabled again upon soft reset?
+#[derive(Serialize, Deserialize)]
 pub struct Ciram {
+    #[serde(with = "crate::util::serde_util::boxed_array")]
     raw: Box<[u8; CIRAM_SIZE]>,
     write_status: WriteStatus,
 }
@@ -30,6 +34,11 @@ impl Ciram {
             .unwrap()
     }
 
+    // Power-on contents don't depend upon whether writes are enabled yet.
+    pub fn fill(&mut self, values: &[u8; CIRAM_SIZE]) {
+        self.raw.copy_from_slice(values);
+    }
+
     pub fn enable_writes(&mut self) {
         self.write_status = WriteStatus::Enabled;
     }
@@ -45,7 +54,7 @@ impl Ciram {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum CiramSide {
     #[default]
     Left = 0,
@@ -1,10 +1,12 @@
+use serde::{Deserialize, Serialize};
 use ux::u11;
 
 use crate::memory::read_result::ReadResult;
 
 const RAM_SIZE: usize = 0x2000;
 
-pub struct CpuInternalRam(Box<[u8; RAM_SIZE]>);
+#[derive(Serialize, Deserialize)]
+pub struct CpuInternalRam(#[serde(with = "crate::util::serde_util::boxed_array")] Box<[u8; RAM_SIZE]>);
 
 impl CpuInternalRam {
     pub fn new() -> CpuInternalRam {
@@ -18,4 +20,10 @@ impl CpuInternalRam {
     pub fn write(&mut self, index: u11, value: u8) {
         self.0[usize::from(u16::from(index))] = value;
     }
+
+    pub fn fill(&mut self, values: &[u8]) {
+        for (byte, &value) in self.0.iter_mut().zip(values) {
+            *byte = value;
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,125 @@
+use std::path::Path;
+
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use crate::cartridge::fds::FdsImage;
+use crate::memory::raw_memory::SaveRam;
+
+// How long a side must be out of the drive before the next one is inserted, so that the BIOS
+// notices that the disk was changed. About one second.
+const SIDE_SWAP_CPU_CYCLES: u32 = 1_800_000;
+
+// The disk in the Famicom Disk System drive. Each side is stored the way the drive head sees it
+// (with gaps and block start marks) so that games can rewrite it in place.
+// Writes are persisted to a separate file so that the original image is never modified.
+pub struct Disk {
+    data: SaveRam,
+    // The start offset and length of each side within data.
+    sides: Vec<(u32, u32)>,
+    inserted_side: Option<u8>,
+    // The side to insert once the previous side has been out of the drive long enough.
+    pending_side: Option<(u8, u32)>,
+}
+
+impl Disk {
+    pub fn new(image: &FdsImage, save_path: &Path, allow_saving: bool) -> Result<Disk, String> {
+        let gapped_sides = image.gapped_sides();
+        let mut sides = Vec::new();
+        let mut initial_data = Vec::new();
+        for side in &gapped_sides {
+            sides.push((initial_data.len() as u32, side.len() as u32));
+            initial_data.extend_from_slice(side);
+        }
+
+        let size = initial_data.len() as u32;
+        let previously_saved = allow_saving
+            && save_path.metadata().is_ok_and(|metadata| metadata.len() == u64::from(size));
+        let mut data = SaveRam::open(save_path, size, allow_saving);
+        if previously_saved {
+            info!("Loaded disk writes from {}.", save_path.display());
+        } else {
+            data.load(&initial_data)?;
+        }
+
+        Ok(Disk { data, sides, inserted_side: Some(0), pending_side: None })
+    }
+
+    pub fn side_count(&self) -> u8 {
+        self.sides.len() as u8
+    }
+
+    pub fn inserted_side(&self) -> Option<u8> {
+        self.inserted_side
+    }
+
+    // The length of the inserted side, or zero if there is no disk in the drive.
+    pub fn side_length(&self) -> u32 {
+        self.inserted_side.map_or(0, |side| self.sides[side as usize].1)
+    }
+
+    pub fn read(&self, position: u32) -> u8 {
+        let side = self.inserted_side.expect("A disk must be inserted to be read.");
+        self.data[self.sides[side as usize].0 + position]
+    }
+
+    pub fn write(&mut self, position: u32, value: u8) {
+        let side = self.inserted_side.expect("A disk must be inserted to be written.");
+        self.data[self.sides[side as usize].0 + position] = value;
+    }
+
+    pub fn eject(&mut self) {
+        self.inserted_side = None;
+        self.pending_side = None;
+    }
+
+    // Ejects the current side (if any), then inserts the specified side after a delay.
+    pub fn insert_side(&mut self, side: u8) {
+        assert!(side < self.side_count(), "Disk side {side} doesn't exist.");
+        self.inserted_side = None;
+        self.pending_side = Some((side, SIDE_SWAP_CPU_CYCLES));
+    }
+
+    pub fn step(&mut self) {
+        if let Some((side, remaining_cycles)) = self.pending_side {
+            if remaining_cycles == 0 {
+                self.inserted_side = Some(side);
+                self.pending_side = None;
+            } else {
+                self.pending_side = Some((side, remaining_cycles - 1));
+            }
+        }
+    }
+
+    pub fn state(&self) -> DiskState {
+        DiskState {
+            data: self.data.to_vec(),
+            inserted_side: self.inserted_side,
+            pending_side: self.pending_side,
+        }
+    }
+
+    pub fn check_state(&self, state: &DiskState) -> Result<(), String> {
+        let side_count = self.side_count();
+        let side_is_valid = |side: Option<u8>| side.is_none_or(|side| side < side_count);
+        if !side_is_valid(state.inserted_side) || !side_is_valid(state.pending_side.map(|(side, _)| side)) {
+            return Err("Save state has an invalid disk side.".to_string());
+        }
+
+        self.data.check_load(&state.data)
+    }
+
+    // The state must have passed check_state().
+    pub fn load_state(&mut self, state: DiskState) {
+        self.data.load(&state.data).expect("Disk size should have been checked.");
+        self.inserted_side = state.inserted_side;
+        self.pending_side = state.pending_side;
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DiskState {
+    data: Vec<u8>,
+    inserted_side: Option<u8>,
+    pending_side: Option<(u8, u32)>,
+}
@@ -1,4 +1,5 @@
 pub mod ciram;
 pub mod cpu_internal_ram;
+pub mod disk;
 pub mod palette_ram;
 pub mod small_page;
\ No newline at end of file
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use ux::u5;
 
 use crate::memory::ppu::chr_memory::{PeekSource, PpuPeek};
@@ -7,13 +8,14 @@ use crate::ppu::register::ppu_registers::PpuRegisters;
 use crate::ppu::palette::color::Color;
 use crate::ppu::palette::palette::Palette;
 
-const PALETTE_RAM_SIZE: usize = 0x20;
+pub const PALETTE_RAM_SIZE: usize = 0x20;
 const INITIAL_PALETTE_DATA: [u8; PALETTE_RAM_SIZE] = [
     0x09, 0x01, 0x00, 0x01, 0x00, 0x02, 0x02, 0x0D, 0x08, 0x10, 0x08, 0x24, 0x00, 0x00, 0x04, 0x2C,
     0x09, 0x01, 0x34, 0x03, 0x00, 0x04, 0x00, 0x14, 0x08, 0x3A, 0x00, 0x02, 0x00, 0x20, 0x2C, 0x08,
 ];
 
 // See https://wiki.nesdev.org/w/index.php?title=PPU_palettes#Memory_Map
+#[derive(Serialize, Deserialize)]
 pub struct PaletteRam {
     backdrop_color: Color,             // 0x00 and 0x10
     unused_colors: [Color; 3],         // 0x04, 0x08, 0x0C (and their mirrors: 0x14, 0x18, 0x1C)
@@ -30,12 +32,15 @@ impl PaletteRam {
             sprite_palettes: [Palette::ALL_BLACK; 4],
         };
 
-        for (i, &data) in INITIAL_PALETTE_DATA.iter().enumerate() {
+        palette_ram.fill(&INITIAL_PALETTE_DATA);
+        palette_ram
+    }
+
+    pub fn fill(&mut self, values: &[u8; PALETTE_RAM_SIZE]) {
+        for (i, &value) in values.iter().enumerate() {
             let i = PaletteRamIndex::new(u5::new(i as u8));
-            palette_ram.write(i, data);
+            self.write(i, value);
         }
-
-        palette_ram
     }
 
     pub fn peek(&self, regs: &PpuRegisters, index: PaletteRamIndex) -> PpuPeek {
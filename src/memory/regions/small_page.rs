@@ -1,10 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 use crate::memory::bank::bank_number::{ReadStatus, WriteStatus};
 use crate::memory::read_result::ReadResult;
 
 const KIBIBYTE: usize = 0x400;
 
+#[derive(Serialize, Deserialize)]
 pub struct SmallPage {
     _name: String,
+    #[serde(with = "crate::util::serde_util::array")]
     page: [u8; KIBIBYTE],
     read_status: ReadStatus,
     write_status: WriteStatus,
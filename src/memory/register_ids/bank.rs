@@ -1,6 +1,7 @@
 use num_derive::FromPrimitive;
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, FromPrimitive)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, FromPrimitive, Serialize, Deserialize)]
 pub enum PrgBankRegisterId {
     P,
     Q,
@@ -53,7 +54,7 @@ impl const RegisterId for PrgBankRegisterId {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, FromPrimitive)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, FromPrimitive, Serialize, Deserialize)]
 pub enum ChrBankRegisterId {
     C,
     D,
@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use std::marker::ConstParamTy;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy, Serialize, Deserialize)]
 pub enum SignalLevel {
     #[default]
     High,
@@ -1,5 +1,7 @@
 use std::num::NonZeroU16;
 
+use serde::{Deserialize, Serialize};
+
 use crate::memory::address_template::address_resolver::AddressResolver;
 use crate::memory::address_template::bank_sizes::BankSizes;
 use crate::memory::bank::bank::MemoryPresence;
@@ -265,7 +267,7 @@ impl PrgSourceProvider {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum PrgSource {
     Rom,
     // Work RAM or Save RAM
@@ -522,7 +524,7 @@ impl ChrSourceProvider {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum ChrSource {
     RomOrRam,
     Rom,
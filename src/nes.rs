@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 use std::fmt;
-use std::fs::{DirBuilder, File};
+use std::fs::{self, File};
 use std::io::Read;
 use std::path::Path;
 
@@ -13,7 +13,7 @@ use crate::apu::apu::Apu;
 use crate::apu::apu_clock::ApuClock;
 use crate::apu::apu_registers::{ApuRegisters, ClockResetStatus};
 use crate::cartridge::cartridge::Cartridge;
-use crate::cartridge::cartridge_metadata::CartridgeMetadataBuilder;
+use crate::cartridge::cartridge_metadata::{CartridgeMetadataBuilder, ConsoleType};
 use crate::cartridge::header_db::HeaderDb;
 use crate::cartridge::resolved_metadata::{MetadataResolver, ResolvedMetadata};
 use crate::config::Config;
@@ -28,18 +28,25 @@ use crate::logging::formatter;
 use crate::logging::formatter::*;
 use crate::mapper::mapper::Mapper;
 use crate::mapper::mapper_list;
+use crate::nsf_player::NsfPlayer;
 use crate::master_clock::{CycleType, MasterClock};
-use crate::memory::raw_memory::RawData;
+use crate::memory::raw_memory::{RawData, RawMemory, SaveRam};
 use crate::memory::bank::bank_number::{BankNumber, ReadStatus, WriteStatus};
 use crate::bus::Bus;
 use crate::memory::register_ids::bank::{ChrBankRegisterId, PrgBankRegisterId};
 use crate::memory::signal_level::SignalLevel;
-use crate::ppu::name_table::name_table_mirroring::NameTableMirroring;
-use crate::ppu::ppu_clock::PpuClock;
+use crate::memory::regions::disk::Disk;
+use crate::memory::regions::small_page::SmallPage;
+use crate::power_on::PowerOnValues;
+use crate::ppu::name_table::name_table_mirroring::{NameTableMirroring, FOUR_SCREEN_PAGE_IDS};
+use crate::ppu::ppu_clock::{Overclock, PpuClock};
 use crate::ppu::palette::bank_color_assigner::BankColorAssigner;
 use crate::ppu::ppu::Ppu;
 use crate::ppu::render::frame::Frame;
+use crate::region::Region;
+use crate::save_state;
 use crate::util::edge_detector::EdgeDetector;
+use crate::vs_system::VsSystem;
 
 pub struct Nes {
     bus: Bus,
@@ -62,13 +69,15 @@ impl Nes {
         Cartridge::load(path, &raw_header_and_data)
     }
 
+    // Loads a ROM image that isn't in a file. The name is used in place of the file name.
+    pub fn load_cartridge_from_bytes(name: &str, raw_header_and_data: Vec<u8>) -> Result<Cartridge, String> {
+        info!("Loading ROM '{name}'.");
+        Cartridge::load(Path::new(name), &RawData::from_vec(raw_header_and_data))
+    }
+
     pub fn new(header_db: &HeaderDb, config: &Config, cartridge: &Cartridge) -> Result<Nes, String> {
         let (mapper, bus, metadata_resolver) = Nes::load_rom(header_db, config, cartridge)?;
 
-        if let Err(err) = DirBuilder::new().recursive(true).create("saveram") {
-            warn!("Failed to create saveram directory. {err}");
-        }
-
         let latest_values = LatestValues::new(&bus);
 
         Ok(Nes {
@@ -112,6 +121,19 @@ impl Nes {
         &self.frame
     }
 
+    pub fn region(&self) -> Region {
+        self.bus.master_clock.region()
+    }
+
+    pub fn overclock(&self) -> Overclock {
+        self.bus.master_clock.ppu_clock().overclock()
+    }
+
+    // Takes effect at the start of the next frame, so that no frame is only partly overclocked.
+    pub fn set_overclock(&mut self, overclock: Overclock) {
+        self.bus.master_clock.set_overclock_at_next_frame(overclock);
+    }
+
     pub fn frame_mut(&mut self) -> &mut Frame {
         &mut self.frame
     }
@@ -125,6 +147,15 @@ impl Nes {
     }
 
     fn load_rom(header_db: &HeaderDb, config: &Config, cartridge: &Cartridge) -> Result<(Box<dyn Mapper>, Bus, MetadataResolver), String> {
+        // The BIOS is the PRG ROM of the RAM adapter that disk images are played through.
+        let fds_cartridge;
+        let cartridge = if cartridge.disk().is_some() {
+            fds_cartridge = cartridge.with_fds_bios(Nes::load_fds_bios(config)?)?;
+            &fds_cartridge
+        } else {
+            cartridge
+        };
+
         let header = cartridge.header();
         let cartridge_mapper_number = header.mapper_number().unwrap();
         let prg_rom_hash = header.prg_rom_hash().unwrap();
@@ -179,24 +210,97 @@ impl Nes {
         let mapper = mapper_list::lookup_mapper(&metadata_resolver, cartridge)?;
 
         let metadata = metadata_resolver.resolve();
+        if !matches!(metadata.console_type, ConsoleType::NesFamiconDendy | ConsoleType::Vs | ConsoleType::DecimalModeFamiclone) {
+            return Err(format!("Console type {:?} is not supported.", metadata.console_type));
+        }
+
         let (prg_memory, chr_memory, name_table_mirrorings) =
             mapper.layout().make_mapper_params(&metadata, cartridge, config.allow_saving)?;
 
-        let master_clock = if config.diff_logging_enabled {
-            MasterClock::new_with_diff_logging(config.starting_cpu_cycle, config.ppu_clock.clone())
+        let region = config.region_override
+            .unwrap_or_else(|| Region::from_timing_mode(metadata.region_timing_mode));
+        let mut master_clock = if config.diff_logging_enabled {
+            MasterClock::new_with_diff_logging(config.starting_cpu_cycle, config.ppu_clock.clone(), region, config.cpu_ppu_alignment)
         } else {
-            MasterClock::new(config.starting_cpu_cycle, config.ppu_clock.clone())
+            MasterClock::new(config.starting_cpu_cycle, config.ppu_clock.clone(), region, config.cpu_ppu_alignment)
+        };
+        info!("CPU/PPU alignment: {}", config.cpu_ppu_alignment.index());
+        if config.overclock.extra_scanlines > 0 {
+            info!("Overclocking with {} extra scanlines ({:?}).", config.overclock.extra_scanlines, config.overclock.position);
+        }
+
+        master_clock.set_overclock(config.overclock);
+
+        let decimal_mode_enabled =
+            config.decimal_mode_override || metadata.console_type == ConsoleType::DecimalModeFamiclone;
+        if decimal_mode_enabled {
+            info!("CPU decimal mode enabled.");
+        }
+
+        let vs_system = match &metadata.vs {
+            Some(vs) if metadata.console_type == ConsoleType::Vs => Some(VsSystem::new(vs.hardware_type, vs.ppu_type)?),
+            _ => None,
         };
+        // The Dendy outputs PAL video too, so its colors are decoded the same way as the 2C07's.
+        let system_palette = vs_system.as_ref().map_or_else(
+            || match region {
+                Region::Ntsc => config.ntsc_system_palette.clone(),
+                Region::Pal | Region::Dendy => config.pal_system_palette.clone(),
+            },
+            VsSystem::system_palette,
+        );
+
+        let disk = cartridge.disk()
+            .map(|image| Disk::new(image, &cartridge.path().to_disk_save_file_path(), config.allow_saving))
+            .transpose()?;
+        let nsf_player = cartridge.nsf().map(|nsf| NsfPlayer::new(nsf, region));
 
         let bank_color_assigner = BankColorAssigner::new(&chr_memory);
         let mut bus = Bus::new(
             master_clock,
-            Cpu::new(config.cpu_step_formatting),
-            Ppu::new(bank_color_assigner),
-            Apu::new(config.disable_audio),
-            prg_memory, chr_memory, name_table_mirrorings,
-            config.dip_switch, config.system_palette.clone());
+            Cpu::new(config.cpu_step_formatting, decimal_mode_enabled),
+            Ppu::new(bank_color_assigner, region),
+            Apu::new(config.disable_audio, config.sample_rate, region),
+            prg_memory, chr_memory, cartridge.miscellaneous_rom().clone(), name_table_mirrorings,
+            config.dip_switch, vs_system, disk, nsf_player, system_palette);
+        if bus.name_table_mirroring().is_four_screen() {
+            // The extra 2KiB of name table RAM on the cartridge.
+            for page_id in FOUR_SCREEN_PAGE_IDS {
+                bus.mapper_custom_pages.push(
+                    SmallPage::new(format!("FourScreenRam{page_id}"), ReadStatus::Enabled, WriteStatus::Enabled));
+            }
+        }
+
+        if let Some(power_on_state) = config.power_on_state {
+            info!("Power-on state: {power_on_state:?}, seed: {}", config.power_on_seed);
+            bus.apply_power_on_state(&mut PowerOnValues::new(power_on_state, config.power_on_seed));
+            mapper.layout().apply_bank_register_overrides(&mut bus.prg_memory, &mut bus.chr_memory);
+        }
+
+        let internal_ram_size = mapper.internal_ram_size();
+        if internal_ram_size > 0 {
+            // Only persisted if the cartridge has a battery.
+            bus.mapper_internal_ram = SaveRam::open(
+                &cartridge.path().to_mapper_internal_ram_file_path(),
+                internal_ram_size,
+                config.allow_saving && metadata.has_persistent_memory,
+            );
+        }
+
         mapper.init_mapper_params(&mut bus);
+        if let Some(speech_chip) = &mut bus.speech_chip {
+            if let Some(path) = &config.speech_clips_path {
+                speech_chip.load_clips(path)?;
+            }
+
+            for (&clip_number, raw) in &config.speech_clips {
+                speech_chip.add_clip(clip_number, raw)?;
+            }
+        }
+
+        if let Some(trainer) = cartridge.trainer() && let Err(err) = bus.prg_memory.load_trainer(trainer) {
+            warn!("Ignoring the trainer. {err}");
+        }
 
         let name_table_mirroring = bus.chr_memory().name_table_mirroring();
         metadata_resolver.cartridge.set_name_table_mirroring(name_table_mirroring);
@@ -209,10 +313,134 @@ impl Nes {
         Ok((mapper, bus, metadata_resolver))
     }
 
+    fn load_fds_bios(config: &Config) -> Result<RawMemory, String> {
+        if let Some(bios) = &config.fds_bios {
+            return RawMemory::from_vec(bios.clone());
+        }
+
+        let Some(path) = &config.fds_bios_path else {
+            return Err("An FDS BIOS is required to run disk images. Specify one with --fdsbios.".to_string());
+        };
+
+        let bios = fs::read(path)
+            .map_err(|err| format!("Failed to read FDS BIOS from {}. {err}", path.display()))?;
+        RawMemory::from_vec(bios)
+    }
+
+    pub fn eject_disk(&mut self) {
+        if let Some(disk) = &mut self.bus.disk {
+            disk.eject();
+        }
+    }
+
+    // Sides are numbered from zero: disk 1 side A, disk 1 side B, disk 2 side A, etc.
+    pub fn insert_disk_side(&mut self, side: u8) {
+        if let Some(disk) = &mut self.bus.disk {
+            disk.insert_side(side);
+        }
+    }
+
+    pub fn nsf_player(&self) -> Option<&NsfPlayer> {
+        self.bus.nsf_player.as_ref()
+    }
+
+    // The NSF driver code restarts the tune upon reset.
+    pub fn play_nsf_track(&mut self, track: u8) {
+        if let Some(player) = &mut self.bus.nsf_player {
+            player.select_track(track);
+            self.set_reset_signal();
+        }
+    }
+
+    pub fn stop_nsf(&mut self) {
+        if let Some(player) = &mut self.bus.nsf_player {
+            player.stop();
+            self.set_reset_signal();
+        }
+    }
+
+    pub fn save_state(&self) -> Result<Vec<u8>, String> {
+        save_state::save(&self.bus, &*self.mapper, &self.frame, self.resolved_metadata.full_hash)
+    }
+
+    pub fn load_state(&mut self, state: &[u8]) -> Result<(), String> {
+        save_state::load(state, &mut self.bus, &mut *self.mapper, &mut self.frame, self.resolved_metadata.full_hash)
+    }
+
+    pub fn save_state_to_file(&self, path: &Path) -> Result<(), String> {
+        let state = self.save_state()?;
+        fs::write(path, state)
+            .map_err(|err| format!("Failed to write save state to {}. {err}", path.display()))?;
+        info!("Saved state to {}.", path.display());
+        Ok(())
+    }
+
+    pub fn load_state_from_file(&mut self, path: &Path) -> Result<(), String> {
+        let state = fs::read(path)
+            .map_err(|err| format!("Failed to read save state from {}. {err}", path.display()))?;
+        self.load_state(&state)?;
+        info!("Loaded state from {}.", path.display());
+        Ok(())
+    }
+
     pub fn mute(&mut self) {
         self.bus.apu.mute();
     }
 
+    pub fn clear_queued_audio(&mut self) {
+        self.bus.apu.clear_queued_samples();
+    }
+
+    // Only useful when audio is disabled, since otherwise the audio thread is consuming the same samples.
+    pub fn take_queued_audio(&mut self) -> Vec<f32> {
+        self.bus.apu.take_queued_samples()
+    }
+
+    // Records the mixed audio output (including expansion audio) to a WAV file.
+    pub fn start_audio_recording(&mut self, path: &Path) -> Result<(), String> {
+        self.bus.apu.start_recording(path)
+    }
+
+    pub fn stop_audio_recording(&mut self) {
+        self.bus.apu.stop_recording();
+    }
+
+    pub fn is_recording_audio(&self) -> bool {
+        self.bus.apu.is_recording()
+    }
+
+    // Battery-backed PRG RAM, followed by the mapper's internal RAM (such as the Namco 163's) if the
+    // cartridge has a battery. Empty if the cartridge has neither.
+    pub fn save_ram(&self) -> Vec<u8> {
+        let mut save_ram = self.bus.prg_memory.save_ram();
+        if self.resolved_metadata.has_persistent_memory {
+            save_ram.extend(self.bus.mapper_internal_ram.to_vec());
+        }
+
+        save_ram
+    }
+
+    pub fn load_save_ram(&mut self, data: &[u8]) -> Result<(), String> {
+        let prg_save_ram_size = self.bus.prg_memory.save_ram().len();
+        let internal_ram_size = if self.resolved_metadata.has_persistent_memory {
+            self.bus.mapper_internal_ram.size() as usize
+        } else {
+            0
+        };
+        if data.len() != prg_save_ram_size + internal_ram_size {
+            return Err(format!(
+                "Expected {} bytes of save RAM but found {}.", prg_save_ram_size + internal_ram_size, data.len()));
+        }
+
+        let (prg_save_ram, internal_ram) = data.split_at(prg_save_ram_size);
+        self.bus.prg_memory.load_save_ram(prg_save_ram)?;
+        if internal_ram_size > 0 {
+            self.bus.mapper_internal_ram.load(internal_ram)?;
+        }
+
+        Ok(())
+    }
+
     pub fn set_reset_signal(&mut self) {
         self.bus.cpu_pinout.reset.set_value(SignalLevel::Low);
     }
@@ -238,6 +466,7 @@ impl Nes {
                     info!("CPU is jammed!");
                 }
 
+                self.bus.apu.flush_recording();
                 break;
             }
         }
@@ -269,16 +498,25 @@ impl Nes {
     }
 
     fn apu_step(&mut self) {
-        Apu::step(&mut self.bus);
+        // The APU is paused during overclocking scanlines to keep it running at its normal speed.
+        if self.bus.ppu_clock().is_on_extra_scanline() {
+            return;
+        }
+
+        Apu::step(&mut self.bus, &*self.mapper);
         self.bus.master_clock_mut().apu_clock_mut().tick();
     }
 
     fn apu_step_with_logging(&mut self) {
+        if self.bus.ppu_clock().is_on_extra_scanline() {
+            return;
+        }
+
         if log_enabled!(target: "timings", Info) {
             self.snapshots.current().apu_regs(self.bus.apu_clock(), &self.bus.apu_regs);
         }
 
-        Apu::step(&mut self.bus);
+        Apu::step(&mut self.bus, &*self.mapper);
 
         if log_enabled!(target: "timings", Info) {
             self.snapshots.current().frame_irq(&self.bus);
@@ -638,6 +876,16 @@ impl Nes {
         for (button, status) in &events.joypad2_button_statuses {
             self.bus.joypad2.set_button_status(*button, *status);
         }
+
+        if events.reset {
+            self.set_reset_signal();
+        }
+
+        if let Some(vs_system) = &mut self.bus.vs_system {
+            for (input, status) in &events.vs_input_statuses {
+                vs_system.set_input_status(*input, *status);
+            }
+        }
     }
 }
 
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cartridge::nsf::NsfFile;
+use crate::region::Region;
+
+// Track selection and PLAY call scheduling for NSF music files. The NSF mapper's driver code
+// polls this to know when to call the tune's INIT and PLAY routines.
+pub struct NsfPlayer {
+    title: String,
+    artist: String,
+    copyright: String,
+    track_labels: Vec<String>,
+    init_address: u16,
+    play_address: u16,
+    region: Region,
+    play_period: u32,
+
+    state: NsfPlayerState,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NsfPlayerState {
+    track: u8,
+    playing: bool,
+    cycles_until_play: u32,
+    play_due: bool,
+}
+
+impl NsfPlayer {
+    pub fn new(nsf: &NsfFile, region: Region) -> NsfPlayer {
+        let play_period_micros = match region {
+            Region::Ntsc => nsf.ntsc_play_period_micros,
+            Region::Pal | Region::Dendy => nsf.pal_play_period_micros,
+        };
+        let play_period = (u64::from(play_period_micros) * u64::from(region.cpu_frequency()) / 1_000_000) as u32;
+
+        NsfPlayer {
+            title: nsf.title.clone(),
+            artist: nsf.artist.clone(),
+            copyright: nsf.copyright.clone(),
+            track_labels: nsf.track_labels.clone(),
+            init_address: nsf.init_address,
+            play_address: nsf.play_address,
+            region,
+            play_period,
+
+            state: NsfPlayerState {
+                track: nsf.starting_track.min(nsf.track_count() - 1),
+                playing: true,
+                cycles_until_play: play_period,
+                play_due: false,
+            },
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn artist(&self) -> &str {
+        &self.artist
+    }
+
+    pub fn copyright(&self) -> &str {
+        &self.copyright
+    }
+
+    pub fn track_labels(&self) -> &[String] {
+        &self.track_labels
+    }
+
+    pub fn track_count(&self) -> u8 {
+        self.track_labels.len() as u8
+    }
+
+    // Zero-based.
+    pub fn track(&self) -> u8 {
+        self.state.track
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.state.playing
+    }
+
+    pub fn init_address(&self) -> u16 {
+        self.init_address
+    }
+
+    pub fn play_address(&self) -> u16 {
+        self.play_address
+    }
+
+    // The value passed to INIT in the X register.
+    pub fn region_flag(&self) -> u8 {
+        match self.region {
+            Region::Ntsc => 0,
+            Region::Pal | Region::Dendy => 1,
+        }
+    }
+
+    // Takes effect once the console has been reset.
+    pub fn select_track(&mut self, track: u8) {
+        assert!(track < self.track_count(), "Track {track} doesn't exist.");
+        self.state.track = track;
+        self.state.playing = true;
+    }
+
+    // Takes effect once the console has been reset.
+    pub fn stop(&mut self) {
+        self.state.playing = false;
+    }
+
+    pub fn restart_play_timer(&mut self) {
+        self.state.cycles_until_play = self.play_period;
+        self.state.play_due = false;
+    }
+
+    pub fn play_due(&self) -> bool {
+        self.state.playing && self.state.play_due
+    }
+
+    pub fn acknowledge_play(&mut self) {
+        self.state.play_due = false;
+    }
+
+    pub fn step(&mut self) {
+        if self.state.cycles_until_play <= 1 {
+            self.state.cycles_until_play = self.play_period;
+            self.state.play_due = true;
+        } else {
+            self.state.cycles_until_play -= 1;
+        }
+    }
+
+    pub fn state(&self) -> NsfPlayerState {
+        self.state.clone()
+    }
+
+    pub fn check_state(&self, state: &NsfPlayerState) -> Result<(), String> {
+        if state.track >= self.track_count() {
+            return Err("Save state has an invalid NSF track.".to_string());
+        }
+
+        Ok(())
+    }
+
+    // The state must have passed check_state().
+    pub fn load_state(&mut self, state: NsfPlayerState) {
+        self.state = state;
+    }
+}
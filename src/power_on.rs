@@ -0,0 +1,64 @@
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// How memory and mapper registers that real hardware leaves undefined are initialized at power-on.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerOnState {
+    AllZero,
+    AllFf,
+    // Runs of four $00 bytes alternating with runs of four $FF bytes, as commonly seen in internal RAM.
+    Pattern,
+    Random,
+}
+
+impl FromStr for PowerOnState {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().as_str() {
+            "zero" => Ok(PowerOnState::AllZero),
+            "ff" => Ok(PowerOnState::AllFf),
+            "pattern" => Ok(PowerOnState::Pattern),
+            "random" => Ok(PowerOnState::Random),
+            _ => Err(format!("Invalid power-on state: {value}")),
+        }
+    }
+}
+
+// Generates the power-on values for each memory region. Regions are always filled in the same order,
+// so a given seed always results in the same power-on state.
+pub struct PowerOnValues {
+    state: PowerOnState,
+    rng_state: u64,
+}
+
+impl PowerOnValues {
+    pub fn new(state: PowerOnState, seed: u64) -> Self {
+        Self { state, rng_state: seed }
+    }
+
+    pub fn generate_seed() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_nanos() as u64)
+    }
+
+    // The pattern starts over at the beginning of each region.
+    pub fn values(&mut self, count: usize) -> Vec<u8> {
+        (0..count)
+            .map(|i| match self.state {
+                PowerOnState::AllZero => 0x00,
+                PowerOnState::AllFf => 0xFF,
+                PowerOnState::Pattern => if (i / 4) % 2 == 0 { 0x00 } else { 0xFF },
+                PowerOnState::Random => self.next_random_byte(),
+            })
+            .collect()
+    }
+
+    // SplitMix64. Quality only needs to be good enough to not look patterned.
+    fn next_random_byte(&mut self) -> u8 {
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        (z ^ (z >> 31)) as u8
+    }
+}
@@ -1,8 +1,6 @@
 use std::fmt::Write;
 use std::sync::LazyLock;
 
-use arr_macro::arr;
-
 use crate::ppu::ppu_clock::PpuClock;
 use crate::ppu::cycle_action::cycle_action::CycleAction;
 use crate::ppu::cycle_action::scanline_actions::{
@@ -16,7 +14,7 @@ use crate::ppu::cycle_action::scanline_actions::{
 };
 
 pub static NTSC_FRAME_ACTIONS: LazyLock<FrameActions> = LazyLock::new(|| {
-    let mut ntsc_frame = FrameActions::new();
+    let mut ntsc_frame = FrameActions::new(262);
 
     ntsc_frame.set_scanline_actions_at(0, FIRST_VISIBLE_SCANLINE_ACTIONS.clone());
     for scanline in 1..=239 {
@@ -35,9 +33,55 @@ pub static NTSC_FRAME_ACTIONS: LazyLock<FrameActions> = LazyLock::new(|| {
     ntsc_frame
 });
 
+// Identical to NTSC except for the 50 extra vblank scanlines.
+pub static PAL_FRAME_ACTIONS: LazyLock<FrameActions> = LazyLock::new(|| {
+    let mut pal_frame = FrameActions::new(312);
+
+    pal_frame.set_scanline_actions_at(0, FIRST_VISIBLE_SCANLINE_ACTIONS.clone());
+    for scanline in 1..=239 {
+        pal_frame.set_scanline_actions_at(scanline, VISIBLE_SCANLINE_ACTIONS.clone());
+    }
+
+    // POST-RENDER SCANLINES
+    pal_frame.set_scanline_actions_at(240, POST_RENDER_SCANLINE_ACTIONS.clone());
+    pal_frame.set_scanline_actions_at(241, START_VBLANK_SCANLINE_ACTIONS.clone());
+    for scanline in 242..=310 {
+        pal_frame.set_scanline_actions_at(scanline, EMPTY_SCANLINE_ACTIONS.clone());
+    }
+
+    pal_frame.set_scanline_actions_at(311, PRE_RENDER_SCANLINE_ACTIONS.clone());
+
+    pal_frame
+});
+
+// Same scanline count as PAL, but vblank doesn't start until 51 scanlines after the post-render scanline.
+pub static DENDY_FRAME_ACTIONS: LazyLock<FrameActions> = LazyLock::new(|| {
+    let mut dendy_frame = FrameActions::new(312);
+
+    dendy_frame.set_scanline_actions_at(0, FIRST_VISIBLE_SCANLINE_ACTIONS.clone());
+    for scanline in 1..=239 {
+        dendy_frame.set_scanline_actions_at(scanline, VISIBLE_SCANLINE_ACTIONS.clone());
+    }
+
+    // POST-RENDER SCANLINES
+    dendy_frame.set_scanline_actions_at(240, POST_RENDER_SCANLINE_ACTIONS.clone());
+    for scanline in 241..=290 {
+        dendy_frame.set_scanline_actions_at(scanline, EMPTY_SCANLINE_ACTIONS.clone());
+    }
+
+    dendy_frame.set_scanline_actions_at(291, START_VBLANK_SCANLINE_ACTIONS.clone());
+    for scanline in 292..=310 {
+        dendy_frame.set_scanline_actions_at(scanline, EMPTY_SCANLINE_ACTIONS.clone());
+    }
+
+    dendy_frame.set_scanline_actions_at(311, PRE_RENDER_SCANLINE_ACTIONS.clone());
+
+    dendy_frame
+});
+
 #[derive(Clone)]
 pub struct FrameActions {
-    all_scanline_actions: Box<[ScanlineActions; 262]>,
+    all_scanline_actions: Box<[ScanlineActions]>,
 }
 
 impl FrameActions {
@@ -59,9 +103,9 @@ impl FrameActions {
         }
     }
 
-    fn new() -> FrameActions {
+    fn new(scanline_count: usize) -> FrameActions {
         FrameActions {
-            all_scanline_actions: Box::new(arr![EMPTY_SCANLINE_ACTIONS.clone(); 262]),
+            all_scanline_actions: vec![EMPTY_SCANLINE_ACTIONS.clone(); scanline_count].into_boxed_slice(),
         }
     }
 
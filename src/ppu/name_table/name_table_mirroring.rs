@@ -1,10 +1,16 @@
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 use crate::memory::bank::bank_number::BankNumber;
 use crate::memory::regions::ciram::CiramSide;
 use crate::ppu::name_table::name_table_quadrant::NameTableQuadrant;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+// The mapper custom pages that hold the extra four screen VRAM. Mappers that add their own custom
+// pages manage their own name tables, so they never need four screen VRAM too.
+pub const FOUR_SCREEN_PAGE_IDS: [u8; 2] = [0, 1];
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct NameTableMirroring {
     // TopLeft, TopRight, BottomLeft, BottomRight
     quadrants: [NameTableSource; 4],
@@ -19,6 +25,13 @@ impl NameTableMirroring {
         build([CiramSide::Left, CiramSide::Left, CiramSide::Left, CiramSide::Left]);
     pub const ONE_SCREEN_RIGHT_BANK: NameTableMirroring =
         build([CiramSide::Right, CiramSide::Right, CiramSide::Right, CiramSide::Right]);
+    // The bottom two name tables are backed by 2KiB of extra VRAM on the cartridge.
+    pub const FOUR_SCREEN: NameTableMirroring = NameTableMirroring::new(
+        NameTableSource::Ciram(CiramSide::Left),
+        NameTableSource::Ciram(CiramSide::Right),
+        NameTableSource::MapperCustom { page_id: FOUR_SCREEN_PAGE_IDS[0] },
+        NameTableSource::MapperCustom { page_id: FOUR_SCREEN_PAGE_IDS[1] },
+    );
 
     pub const fn new(
         top_left_quadrant: NameTableSource,
@@ -63,6 +76,10 @@ impl NameTableMirroring {
         self == Self::HORIZONTAL
     }
 
+    pub fn is_four_screen(self) -> bool {
+        self == Self::FOUR_SCREEN
+    }
+
     pub fn is_regular_one_screen(self) -> bool {
         self == Self::ONE_SCREEN_LEFT_BANK || self == Self::ONE_SCREEN_RIGHT_BANK
     }
@@ -92,6 +109,7 @@ impl fmt::Display for NameTableMirroring {
             NameTableMirroring::HORIZONTAL => "Horizontal".to_string(),
             NameTableMirroring::ONE_SCREEN_LEFT_BANK => "OneScreenLeftBank".to_string(),
             NameTableMirroring::ONE_SCREEN_RIGHT_BANK => "OneScreenRightBank".to_string(),
+            NameTableMirroring::FOUR_SCREEN => "FourScreen".to_string(),
             NameTableMirroring { quadrants: [top_left, top_right, bottom_left, bottom_right] } =>
                 format!("[{top_left}, {top_right}, {bottom_left}, {bottom_right}]"),
         };
@@ -100,7 +118,7 @@ impl fmt::Display for NameTableMirroring {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum NameTableSource {
     Ciram(CiramSide),
     Rom { bank_number: BankNumber },
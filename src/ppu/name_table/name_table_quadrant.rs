@@ -4,11 +4,12 @@
 use modular_bitfield::Specifier;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
 use ux::u2;
 
 use crate::memory::register_ids::{bank::ChrBankRegisterId, source::ChrSourceRegisterId};
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, FromPrimitive, Specifier)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, FromPrimitive, Specifier, Serialize, Deserialize)]
 pub enum NameTableQuadrant {
     TopLeft = 0,
     TopRight = 1,
@@ -4,6 +4,7 @@ use crate::ppu::palette::util;
 
 use super::rgb::Rgb;
 
+#[derive(Default)]
 pub struct BankColorAssigner {
     rom_spectrum: Vec<Rgb>,
     ram_greyscale: Vec<Rgb>,
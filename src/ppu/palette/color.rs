@@ -1,10 +1,11 @@
 use enum_iterator::Sequence;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
 use splitbits::{combinebits, splitbits_ux};
 use ux::{u2, u4, u6};
 
-#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Color {
     brightness: Brightness,
     hue: Hue,
@@ -42,7 +43,7 @@ impl From<u8> for Color {
     }
 }
 
-#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug, FromPrimitive, Sequence)]
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug, FromPrimitive, Sequence, Serialize, Deserialize)]
 pub enum Hue {
     Gray,
     Azure,
@@ -68,7 +69,7 @@ impl From<u4> for Hue {
     }
 }
 
-#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug, FromPrimitive, Sequence)]
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug, FromPrimitive, Sequence, Serialize, Deserialize)]
 pub enum Brightness {
     Minimum,
     Low,
@@ -1,10 +1,12 @@
 use std::ops::Index;
 
+use serde::{Deserialize, Serialize};
+
 use crate::ppu::palette::palette_index::PaletteIndex;
 use crate::ppu::palette::color::Color;
 use crate::ppu::palette::color_t::ColorT;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Palette([Color; 3]);
 
 impl Palette {
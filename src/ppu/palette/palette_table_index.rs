@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::ppu::name_table::background_tile_index::{TileColumn, TileRow};
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum PaletteTableIndex {
     #[default]
     Zero,
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::ppu::palette::rgb::Rgb;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Rgbt {
     Transparent,
     Opaque(Rgb),
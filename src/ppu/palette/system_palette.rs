@@ -74,6 +74,13 @@ impl SystemPalette {
         &self.0[emphasis_index]
     }
 
+    // Rearranges the colors, such that color i becomes the color formerly at lookup[i].
+    pub fn remapped(&self, lookup: &[u8; 64]) -> SystemPalette {
+        SystemPalette(self.0.clone().map(|section| {
+            SystemPaletteSection(lookup.map(|index| section.0[usize::from(index)]))
+        }))
+    }
+
     fn parse_line(
         palette: &mut [Rgb; 64],
         brightness: Brightness,
@@ -1,13 +1,14 @@
 use std::marker::ConstParamTy;
 
 use modular_bitfield::Specifier;
+use serde::{Deserialize, Serialize};
 
 use crate::util::unit::KIBIBYTE;
 
 
 const PATTERN_TABLE_SIZE: u32 = 4 * KIBIBYTE;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy, Specifier)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, ConstParamTy, Specifier, Serialize, Deserialize)]
 pub enum PatternTableSide {
     #[default]
     Left,
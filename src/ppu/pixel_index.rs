@@ -5,6 +5,7 @@ use itertools::structs::Product;
 use itertools::Itertools;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
 use ux::u3;
 
 use crate::ppu::ppu_clock::PpuClock;
@@ -190,7 +191,7 @@ impl Iterator for PixelRowIterator {
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, FromPrimitive, Sequence, ConstParamTy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, FromPrimitive, Sequence, ConstParamTy, Serialize, Deserialize)]
 pub enum ColumnInTile {
     Zero,
     One,
@@ -1,30 +1,39 @@
 use log::{info, log_enabled};
 use log::Level::Info;
+use serde::{Deserialize, Serialize};
 
 use crate::mapper::mapper::Mapper;
 use crate::bus::Bus;
 use crate::memory::ppu::chr_memory::PpuPeek;
 use crate::memory::ppu::ppu_address::PpuAddress;
+use crate::memory::regions::palette_ram::PaletteRam;
 use crate::memory::signal_level::SignalLevel;
 use crate::ppu::cycle_action::cycle_action::CycleAction;
-use crate::ppu::cycle_action::frame_actions::{FrameActions, NTSC_FRAME_ACTIONS};
+use crate::ppu::cycle_action::frame_actions::{FrameActions, DENDY_FRAME_ACTIONS, NTSC_FRAME_ACTIONS, PAL_FRAME_ACTIONS};
 use crate::ppu::palette::rgbt::Rgbt;
 use crate::ppu::palette::color_t::ColorT;
 use crate::ppu::pattern_table_side::PatternTableSide;
-use crate::ppu::pixel_index::PixelIndex;
+use crate::ppu::pixel_index::{PixelColumn, PixelIndex};
 use crate::ppu::register::ppu_registers::Toggle;
 use crate::ppu::register::registers::attribute_register::AttributeRegister;
 use crate::ppu::register::registers::pattern_register::PatternRegister;
 use crate::ppu::render::frame::Frame;
-use crate::ppu::sprite::sprite_attributes::SpriteAttributes;
+use crate::ppu::sprite::sprite_attributes::{Priority, SpriteAttributes};
 use crate::ppu::sprite::oam_registers::OamRegisters;
 use crate::ppu::sprite::sprite_y::SpriteY;
 use crate::ppu::sprite::sprite_height::SpriteHeight;
 use crate::ppu::tile_number::TileNumber;
+use crate::region::Region;
 
 use super::palette::bank_color_assigner::BankColorAssigner;
 use super::sprite::sprite_evaluator::SpriteEvaluator;
 
+// Placeholder for deserialization. Ppu::load_state keeps the frame actions that were already in use.
+fn default_frame_actions() -> FrameActions {
+    NTSC_FRAME_ACTIONS.clone()
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct Ppu {
     oam_registers: OamRegisters,
     oam_register_index: usize,
@@ -41,14 +50,30 @@ pub struct Ppu {
     current_sprite_y: SpriteY,
     sprite_visible: bool,
 
+    // Not part of save states since these never change after start up.
+    #[serde(skip, default = "default_frame_actions")]
     frame_actions: FrameActions,
 
+    // Sprites that the 8-sprite limit drops from the next scanline. Only used when the limit is disabled
+    // for the output frame, so not part of save states.
+    #[serde(skip)]
+    extra_sprites: Vec<ExtraSprite>,
+
+    // Only used for debug screens, so not part of save states.
+    #[serde(skip, default = "Frame::new")]
     pattern_source_frame: Frame,
+    #[serde(skip)]
     bank_color_assigner: BankColorAssigner,
 }
 
 impl Ppu {
-    pub fn new(bank_color_assigner: BankColorAssigner) -> Ppu {
+    pub fn new(bank_color_assigner: BankColorAssigner, region: Region) -> Ppu {
+        let frame_actions = match region {
+            Region::Ntsc => NTSC_FRAME_ACTIONS.clone(),
+            Region::Pal => PAL_FRAME_ACTIONS.clone(),
+            Region::Dendy => DENDY_FRAME_ACTIONS.clone(),
+        };
+
         Ppu {
             oam_registers: OamRegisters::new(),
             oam_register_index: 0,
@@ -65,13 +90,23 @@ impl Ppu {
             current_sprite_y: SpriteY::new(0),
             sprite_visible: false,
 
-            frame_actions: NTSC_FRAME_ACTIONS.clone(),
+            frame_actions,
+
+            extra_sprites: Vec::new(),
 
             pattern_source_frame: Frame::new(),
             bank_color_assigner,
         }
     }
 
+    // Restores a saved PPU while keeping the state that isn't saved.
+    pub fn load_state(&mut self, saved: Ppu) {
+        let Ppu { frame_actions, pattern_source_frame, bank_color_assigner, .. } = std::mem::replace(self, saved);
+        self.frame_actions = frame_actions;
+        self.pattern_source_frame = pattern_source_frame;
+        self.bank_color_assigner = bank_color_assigner;
+    }
+
     pub fn step_first_half(bus: &mut Bus, mapper: &mut dyn Mapper, frame: &mut Frame) {
         let tick_result = bus.ppu_regs.tick(bus.master_clock.ppu_clock());
         if tick_result.rendering_toggled == Some(Toggle::Disable) {
@@ -243,7 +278,14 @@ impl Ppu {
 
                 // This is not delayed, unlike ppu_regs.rendering_enabled()
                 let rendering_enabled = bus.ppu_regs.background_enabled() || bus.ppu_regs.sprites_enabled();
-                let (mut sprite_pixel, priority, is_sprite_0, ppu_peek) = bus.ppu.oam_registers.step(&bus.palette_ram, rendering_enabled);
+                let (mut sprite_pixel, mut priority, is_sprite_0, mut ppu_peek) =
+                    bus.ppu.oam_registers.step(&bus.palette_ram, rendering_enabled);
+                // Sprites dropped by the 8-sprite limit are lower priority than the ones that made it.
+                if frame.show_all_sprites() && sprite_pixel.is_transparent()
+                        && let Some(extra_sprite_pixel) = bus.ppu.extra_sprite_pixel(&bus.palette_ram, pixel_column) {
+                    (sprite_pixel, priority, ppu_peek) = extra_sprite_pixel;
+                }
+
                 if rendering_enabled {
                     if !bus.ppu_regs.sprites_enabled() {
                         sprite_pixel = ColorT::Transparent;
@@ -294,6 +336,14 @@ impl Ppu {
                 info!(target: "ppustage", "\t\tLoading OAM registers.");
                 bus.ppu.sprite_evaluator.start_loading_oam_registers();
                 bus.ppu.oam_registers.set_sprite_0_presence(bus.ppu.sprite_evaluator.sprite_0_present());
+                // The extra sprite patterns are peeked from the current CHR banks, which are only
+                // correct for mappers that use the same banks for sprites as for the background.
+                let extra_sprites_supported = !mapper.has_sprite_specific_chr_banking();
+                bus.ppu.extra_sprites = if frame.show_all_sprites() && bus.ppu_regs.rendering_enabled() && extra_sprites_supported {
+                    Ppu::load_extra_sprites(bus)
+                } else {
+                    Vec::new()
+                };
             }
             StopLoadingOamRegisters => {
                 info!(target: "ppustage", "\t\tLoading OAM registers ended.");
@@ -410,6 +460,48 @@ impl Ppu {
         &self.pattern_source_frame
     }
 
+    // Peeks rather than reads the sprite patterns, so mappers can't observe these fetches.
+    fn load_extra_sprites(bus: &Bus) -> Vec<ExtraSprite> {
+        let Some(pixel_row) = bus.ppu_clock().scanline_pixel_row() else {
+            return Vec::new();
+        };
+
+        let mut extra_sprites = Vec::new();
+        let sprite_height = bus.ppu_regs.sprite_height();
+        for [y, tile_number, attributes, x] in SpriteEvaluator::evaluate_extra_sprites(&bus.oam, pixel_row, sprite_height) {
+            let tile_number = TileNumber::new(tile_number);
+            let attributes = SpriteAttributes::from_u8(attributes);
+            let sprite_table_side = match sprite_height {
+                SpriteHeight::Normal => bus.ppu_regs.sprite_table_side(),
+                SpriteHeight::Tall => tile_number.tall_sprite_pattern_table_side(),
+            };
+            let Some((tile_number, row_in_half, true)) =
+                tile_number.number_and_row(SpriteY::new(y), attributes.flip_vertically(), sprite_height, pixel_row)
+            else {
+                continue;
+            };
+
+            let pattern = |select_high| {
+                bus.peek_chr(PpuAddress::in_pattern_table(sprite_table_side, tile_number, row_in_half, select_high))
+            };
+            extra_sprites.push(ExtraSprite { x, attributes, low_pattern: pattern(false), high_pattern: pattern(true) });
+        }
+
+        extra_sprites
+    }
+
+    fn extra_sprite_pixel(&self, palette_ram: &PaletteRam, column: PixelColumn) -> Option<(ColorT, Priority, PpuPeek)> {
+        self.extra_sprites.iter().find_map(|sprite| {
+            let column_in_sprite = column.to_u8().checked_sub(sprite.x).filter(|&column| column < 8)?;
+            let shift = if sprite.attributes.flip_horizontally() { column_in_sprite } else { 7 - column_in_sprite };
+            let low_bit = (sprite.low_pattern.value() >> shift) & 1 == 1;
+            let high_bit = (sprite.high_pattern.value() >> shift) & 1 == 1;
+            let palette = palette_ram.sprite_palette(sprite.attributes.palette_table_index());
+            let color_t = palette.color_t_from_low_high(low_bit, high_bit);
+            color_t.is_opaque().then_some((color_t, sprite.attributes.priority(), sprite.low_pattern))
+        })
+    }
+
     fn current_sprite_pattern_address(&self, bus: &Bus, select_high: bool) -> (PpuAddress, bool) {
         let sprite_table_side = bus.ppu_regs.sprite_table_side();
         let sprite_height = bus.ppu_regs.sprite_height();
@@ -454,6 +546,7 @@ impl Ppu {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 enum RenderingRegisterField {
     PatternIndex,
     PaletteIndex,
@@ -462,3 +555,11 @@ enum RenderingRegisterField {
     SpritePatternLow,
     SpritePatternHighAndNextSprite,
 }
+
+#[derive(Clone, Copy)]
+struct ExtraSprite {
+    x: u8,
+    attributes: SpriteAttributes,
+    low_pattern: PpuPeek,
+    high_pattern: PpuPeek,
+}
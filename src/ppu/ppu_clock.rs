@@ -1,25 +1,67 @@
 use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
 
 use crate::ppu::pixel_index::PixelRow;
+use crate::region::Region;
 
-pub const MAX_SCANLINE: u16 = 261;
+pub const NTSC_MAX_SCANLINE: u16 = 261;
+pub const PAL_MAX_SCANLINE: u16 = 311;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PpuClock {
     frame: i64,
     scanline: u16,
     cycle: u16,
 
     total_cycles: u64,
+    region: Region,
+
+    // How many times the current scanline has been repeated due to overclocking. Zero on a normal scanline.
+    extra_scanline: u16,
+    // Not part of save states, since these are user settings rather than part of the console state.
+    #[serde(skip)]
+    overclock: Overclock,
+    // Applied when the current frame ends, so that no frame is only partly overclocked.
+    #[serde(skip)]
+    pending_overclock: Option<Overclock>,
 }
 
 impl PpuClock {
     pub fn mesen_compatible() -> PpuClock {
-        PpuClock { frame: 0, scanline: 0, cycle: 5, total_cycles: 0 }
+        PpuClock::starting_at(0, 0, 5)
     }
 
     pub fn starting_at(frame: i64, scanline: u16, cycle: u16) -> PpuClock {
-        PpuClock { frame, scanline, cycle, total_cycles: 0 }
+        PpuClock {
+            frame,
+            scanline,
+            cycle,
+            total_cycles: 0,
+            region: Region::Ntsc,
+            extra_scanline: 0,
+            overclock: Overclock::default(),
+            pending_overclock: None,
+        }
+    }
+
+    pub fn set_region(&mut self, region: Region) {
+        self.region = region;
+    }
+
+    // Includes a change that hasn't taken effect yet.
+    pub fn overclock(&self) -> Overclock {
+        self.pending_overclock.unwrap_or(self.overclock)
+    }
+
+    pub fn set_overclock(&mut self, overclock: Overclock) {
+        self.overclock = overclock;
+        self.pending_overclock = None;
+    }
+
+    pub fn set_overclock_at_next_frame(&mut self, overclock: Overclock) {
+        self.pending_overclock = Some(overclock);
     }
 
     pub fn frame(&self) -> i64 {
@@ -46,12 +88,25 @@ impl PpuClock {
         self.scanline < 240
     }
 
+    // An idle scanline inserted by overclocking. The CPU runs during it, but the APU doesn't.
+    pub fn is_on_extra_scanline(&self) -> bool {
+        self.extra_scanline > 0
+    }
+
     pub fn is_on_vblank_scanline(&self) -> bool {
-        self.scanline >= 240 && self.scanline < 261
+        self.scanline >= 240 && self.scanline < self.prerender_scanline()
     }
 
     pub fn is_on_prerender_scanline(&self) -> bool {
-        self.scanline == 261
+        self.scanline == self.prerender_scanline()
+    }
+
+    // The last scanline of the frame. PAL and Dendy have 50 more scanlines than NTSC.
+    pub fn prerender_scanline(&self) -> u16 {
+        match self.region {
+            Region::Ntsc => NTSC_MAX_SCANLINE,
+            Region::Pal | Region::Dendy => PAL_MAX_SCANLINE,
+        }
     }
 
     pub fn is_oam_clearing_window(&self) -> bool {
@@ -67,20 +122,31 @@ impl PpuClock {
     pub fn tick(&mut self, skip_odd_frame_cycle: bool) -> Option<LastCycle> {
         self.total_cycles += 1;
 
-        let last_cycle = if skip_odd_frame_cycle && self.frame % 2 == 1 {
+        // Only the NTSC PPU skips a cycle on odd frames.
+        let last_cycle = if skip_odd_frame_cycle && self.region == Region::Ntsc && self.frame % 2 == 1 {
             LastCycle::Skipped
         } else {
             LastCycle::Normal
         };
 
-        let is_last_cycle_of_frame = self.scanline == MAX_SCANLINE && self.cycle >= last_cycle as u16;
+        let is_last_cycle_of_frame = self.is_on_prerender_scanline() && self.cycle >= last_cycle as u16;
         if is_last_cycle_of_frame {
             self.frame += 1;
             self.scanline = 0;
             self.cycle = 0;
+            if let Some(overclock) = self.pending_overclock.take() {
+                self.overclock = overclock;
+            }
+
             Some(last_cycle)
         } else if self.cycle == LastCycle::Normal as u16 {
-            self.scanline += 1;
+            if self.should_repeat_scanline() {
+                self.extra_scanline += 1;
+            } else {
+                self.extra_scanline = 0;
+                self.scanline += 1;
+            }
+
             self.cycle = 0;
             None
         } else {
@@ -88,6 +154,15 @@ impl PpuClock {
             None
         }
     }
+
+    // Overclocking repeats a scanline on which the PPU is idle, so raster timing is unaffected.
+    fn should_repeat_scanline(&self) -> bool {
+        let repeated_scanline = match self.overclock.position {
+            OverclockPosition::PostRender => 240,
+            OverclockPosition::Vblank => self.prerender_scanline() - 1,
+        };
+        self.scanline == repeated_scanline && self.extra_scanline < self.overclock.extra_scanlines
+    }
 }
 
 impl fmt::Display for PpuClock {
@@ -101,3 +176,31 @@ pub enum LastCycle {
     Normal = 340,
     Skipped = 339,
 }
+
+// Extra idle scanlines per frame, giving games more CPU time without changing their raster timing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Overclock {
+    pub extra_scanlines: u16,
+    pub position: OverclockPosition,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OverclockPosition {
+    // After the post-render scanline (240), delaying the vblank NMI.
+    #[default]
+    PostRender,
+    // At the end of vblank, right before the pre-render scanline.
+    Vblank,
+}
+
+impl FromStr for OverclockPosition {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().as_str() {
+            "postrender" => Ok(OverclockPosition::PostRender),
+            "vblank" => Ok(OverclockPosition::Vblank),
+            _ => Err(format!("Invalid overclock position: {value}. Must be postrender or vblank.")),
+        }
+    }
+}
@@ -1,6 +1,11 @@
-use crate::ppu::ppu_clock::MAX_SCANLINE;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
+use crate::ppu::ppu_clock::NTSC_MAX_SCANLINE;
+
+// About one frame.
+const DECAY_SCANLINES: u16 = NTSC_MAX_SCANLINE;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PpuIoBus {
     value: u8,
     // Measured in scanlines.
@@ -24,14 +29,14 @@ impl PpuIoBus {
     pub fn update(&mut self, value: u8) {
         self.value = value;
         // All bits are updated, both upper and lower.
-        self.upper_bits_decay_countdown = MAX_SCANLINE;
-        self.lower_bits_decay_countdown = MAX_SCANLINE;
+        self.upper_bits_decay_countdown = DECAY_SCANLINES;
+        self.lower_bits_decay_countdown = DECAY_SCANLINES;
     }
 
     pub fn update_from_status_read(&mut self, value: u8) {
         self.value = value;
         // The lower bits are unaffected since PPUStatus doesn't power them.
-        self.upper_bits_decay_countdown = MAX_SCANLINE;
+        self.upper_bits_decay_countdown = DECAY_SCANLINES;
     }
 
     pub fn maybe_decay(&mut self) {
@@ -1,4 +1,5 @@
 use log::{Level, info, log_enabled};
+use serde::{Deserialize, Serialize};
 use splitbits::{splitbits, combinebits};
 
 use crate::memory::ppu::ppu_address::{PpuAddress, XScroll};
@@ -11,7 +12,9 @@ use crate::ppu::register::ppu_io_bus::PpuIoBus;
 use crate::ppu::sprite::oam::Oam;
 use crate::ppu::sprite::oam_address::OamAddress;
 use crate::ppu::sprite::sprite_height::SpriteHeight;
+use crate::region::Region;
 
+#[derive(Serialize, Deserialize)]
 pub struct PpuRegisters {
     // PPUCTRL (0x2000) sub-registers
     nmi_enabled: bool,
@@ -139,13 +142,16 @@ impl PpuRegisters {
     pub fn rendering_enabled(&self) -> bool { self.rendering_enabled }
 
     // Write 0x2001
-    pub fn write_mask(&mut self, value: u8) {
+    pub fn write_mask(&mut self, value: u8, region: Region) {
         self.ppu_io_bus.update(value);
 
         let fields = splitbits!(value, "efgs blmz");
         self.mask.emphasize_blue = fields.e;
-        self.mask.emphasize_green = fields.f;
-        self.mask.emphasize_red = fields.g;
+        // The PAL and Dendy PPUs have the red and green emphasis bits swapped.
+        (self.mask.emphasize_green, self.mask.emphasize_red) = match region {
+            Region::Ntsc => (fields.f, fields.g),
+            Region::Pal | Region::Dendy => (fields.g, fields.f),
+        };
         self.mask.sprites_enabled = fields.s;
         self.mask.background_enabled = fields.b;
         self.mask.left_sprite_columns_enabled = fields.l;
@@ -340,7 +346,7 @@ impl PpuRegisters {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum WriteToggle {
     FirstByte,
     SecondByte,
@@ -356,7 +362,7 @@ impl WriteToggle {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum RenderingToggleState {
     Inactive,
     Pending,
@@ -373,19 +379,19 @@ pub enum Toggle {
     Disable,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum ExtPinRole {
     Read,
     Write,
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum AddressIncrement {
     Right,
     Down,
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub struct Mask {
     greyscale_enabled: bool,
     left_background_columns_enabled: bool,
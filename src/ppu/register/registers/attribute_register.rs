@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::ppu::palette::palette_table_index::PaletteTableIndex;
 use crate::ppu::pixel_index::ColumnInTile;
 use crate::ppu::register::registers::shift_array::ShiftArray;
 
+#[derive(Serialize, Deserialize)]
 pub struct AttributeRegister {
     pending_index: PaletteTableIndex,
     next_index: PaletteTableIndex,
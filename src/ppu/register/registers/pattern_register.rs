@@ -1,9 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::memory::ppu::chr_memory::PpuPeek;
 use crate::ppu::palette::palette_index::PaletteIndex;
 use crate::ppu::pixel_index::ColumnInTile;
 use crate::ppu::register::registers::shift_array::ShiftArray;
 use crate::util::bit_util::unpack_bools;
 
+#[derive(Serialize, Deserialize)]
 pub struct PatternRegister {
     pending_low_byte: PpuPeek,
     pending_high_byte: PpuPeek,
@@ -1,8 +1,11 @@
 use std::collections::VecDeque;
 use std::ops::{Index, IndexMut};
 
+use serde::{Deserialize, Serialize};
+
 use crate::ppu::pixel_index::ColumnInTile;
 
+#[derive(Serialize, Deserialize)]
 pub struct ShiftArray<T, const N: usize>(VecDeque<T>);
 
 impl <T: Copy + Default, const N: usize> ShiftArray<T, N> {
@@ -1,6 +1,8 @@
 use std::ops::{Index, IndexMut};
 
 use enum_iterator::all;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::ppu::palette::rgb::Rgb;
 use crate::ppu::palette::rgbt::Rgbt;
@@ -12,7 +14,7 @@ use crate::ppu::register::ppu_registers::Mask;
 use crate::ppu::render::ppm::Ppm;
 use crate::ppu::sprite::sprite_attributes::Priority;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Frame {
     buffer: FrameBuffer<(Rgb, bool)>,
 
@@ -20,7 +22,11 @@ pub struct Frame {
     sprite_buffer: FrameBuffer<(Rgbt, Priority, bool)>,
     universal_background_rgb: Rgb,
 
+    // Display settings rather than part of the frame contents.
+    #[serde(skip)]
     show_overscan: bool,
+    #[serde(skip)]
+    show_all_sprites: bool,
 }
 
 impl Frame {
@@ -37,6 +43,7 @@ impl Frame {
             universal_background_rgb: Rgb::BLACK,
 
             show_overscan: false,
+            show_all_sprites: false,
         }
     }
 
@@ -53,6 +60,23 @@ impl Frame {
         &mut self.show_overscan
     }
 
+    // Whether to render sprites beyond the eighth on a scanline, reducing flicker.
+    pub fn show_all_sprites(&self) -> bool {
+        self.show_all_sprites
+    }
+
+    pub fn show_all_sprites_mut(&mut self) -> &mut bool {
+        &mut self.show_all_sprites
+    }
+
+    // Replace the frame contents with those from a save state, keeping the display settings.
+    pub fn load_state(&mut self, frame: Frame) {
+        let (show_overscan, show_all_sprites) = (self.show_overscan, self.show_all_sprites);
+        *self = frame;
+        self.show_overscan = show_overscan;
+        self.show_all_sprites = show_all_sprites;
+    }
+
     pub fn set_pixel(&mut self, mask: Mask, column: PixelColumn, row: PixelRow) -> Sprite0Hit {
         use Rgbt::{Opaque, Transparent};
         let mut background_pixel = self.background_buffer[(column, row)];
@@ -194,6 +218,27 @@ impl<T: Copy> FrameBuffer<T> {
     }
 }
 
+// Serialized as a flat sequence of pixels, row by row.
+impl<T: Serialize> Serialize for FrameBuffer<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().flatten())
+    }
+}
+
+impl<'de, T: Copy + Deserialize<'de>> Deserialize<'de> for FrameBuffer<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pixels: Vec<T> = Vec::deserialize(deserializer)?;
+        if pixels.len() != PixelIndex::PIXEL_COUNT {
+            return Err(D::Error::invalid_length(pixels.len(), &"one entry per pixel"));
+        }
+
+        let rows: Vec<[T; PixelColumn::COLUMN_COUNT]> = pixels.chunks_exact(PixelColumn::COLUMN_COUNT)
+            .map(|row| row.try_into().unwrap())
+            .collect();
+        Ok(FrameBuffer(rows.into_boxed_slice().try_into().unwrap_or_else(|_| unreachable!())))
+    }
+}
+
 impl<T> Index<(PixelColumn, PixelRow)> for FrameBuffer<T> {
     type Output = T;
 
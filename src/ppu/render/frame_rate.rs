@@ -46,6 +46,8 @@ impl FromStr for FrameRate {
 #[derive(Clone, Copy, Debug)]
 pub enum TargetFrameRate {
     Value(FrameRate),
+    // The native frame rate of the console region being emulated.
+    Console,
     Unbounded,
 }
 
@@ -54,6 +56,7 @@ impl FromStr for TargetFrameRate {
 
     fn from_str(value: &str) -> Result<TargetFrameRate, String> {
         match value {
+            "console" => Ok(TargetFrameRate::Console),
             "unbounded" => Ok(TargetFrameRate::Unbounded),
             _ => Ok(TargetFrameRate::Value(FrameRate::from_str(value)?)),
         }
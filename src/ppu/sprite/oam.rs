@@ -1,13 +1,14 @@
 use std::fmt;
 
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
 use crate::memory::primitives::dram_byte::DramByte;
 use crate::ppu::ppu_clock::PpuClock;
 use crate::ppu::sprite::oam_address::OamAddress;
 
-#[derive(Clone)]
-pub struct Oam([DramByte; 256]);
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Oam(#[serde(with = "crate::util::serde_util::array")] [DramByte; 256]);
 
 impl Oam {
     pub fn new() -> Oam {
@@ -23,6 +24,18 @@ impl Oam {
         Oam(raw_oam.try_into().unwrap())
     }
 
+    pub fn fill(&mut self, values: &[u8; 256]) {
+        for (dram_byte, &value) in self.0.iter_mut().zip(values) {
+            dram_byte.write(value);
+        }
+    }
+
+    // Doesn't refresh DRAM decay, so it is only suitable for output that the CPU can't observe.
+    pub fn peek_sprite(&self, sprite_index: usize) -> [u8; 4] {
+        let start = 4 * sprite_index;
+        [0, 1, 2, 3].map(|offset| self.0[start + offset].peek())
+    }
+
     // For debug screens only.
     pub fn to_raw(&self) -> &[DramByte; 256] {
         &self.0
@@ -1,6 +1,7 @@
 use log::info;
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct OamAddress(u8);
 
 impl OamAddress {
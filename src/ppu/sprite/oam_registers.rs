@@ -1,10 +1,13 @@
 use std::ops::{Index, IndexMut};
 
+use serde::{Deserialize, Serialize};
+
 use crate::memory::ppu::chr_memory::PpuPeek;
 use crate::memory::regions::palette_ram::PaletteRam;
 use crate::ppu::palette::color_t::ColorT;
 use crate::ppu::sprite::sprite_attributes::{SpriteAttributes, Priority};
 
+#[derive(Serialize, Deserialize)]
 pub struct OamRegisters {
     registers: [SpriteRegisters; 8],
 }
@@ -47,7 +50,7 @@ impl IndexMut<usize> for OamRegisters {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct SpriteRegisters {
     low_pattern: u8,
     low_pattern_info: PpuPeek,
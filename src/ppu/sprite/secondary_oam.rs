@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
 use ux::u5;
 
+#[derive(Serialize, Deserialize)]
 pub struct SecondaryOam {
     data: [u8; 32],
+    #[serde(with = "crate::util::serde_util::ux")]
     index: u5,
     is_full: bool,
 }
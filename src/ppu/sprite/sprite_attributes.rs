@@ -1,8 +1,9 @@
+use serde::{Deserialize, Serialize};
 use splitbits::splitbits;
 
 use crate::ppu::palette::palette_table_index::PaletteTableIndex;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct SpriteAttributes {
     flip_vertically: bool,
     flip_horizontally: bool,
@@ -55,7 +56,7 @@ impl SpriteAttributes {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Priority {
     InFront,
     Behind,
@@ -1,11 +1,15 @@
+use serde::{Deserialize, Serialize};
+
 use crate::ppu::pixel_index::PixelRow;
 use crate::ppu::ppu_clock::PpuClock;
 use crate::ppu::register::ppu_registers::PpuRegisters;
 use crate::ppu::sprite::oam::Oam;
 use crate::ppu::sprite::secondary_oam::SpriteField;
+use crate::ppu::sprite::sprite_height::SpriteHeight;
 
 use super::secondary_oam::SecondaryOam;
 
+#[derive(Serialize, Deserialize)]
 pub struct SpriteEvaluator {
     oam_data_read: u8,
     secondary_oam: SecondaryOam,
@@ -106,4 +110,20 @@ impl SpriteEvaluator {
         // If the sprite isn't in range, move to the next sprite.
         self.all_sprites_evaluated = ppu_regs.oam_addr.next_sprite();
     }
-}
\ No newline at end of file
+
+    // A second evaluation path for when the sprite limit is disabled. Returns the raw OAM bytes of every in-range
+    // sprite after the first eight. Only feeds the output frame, so it leaves OAM, secondary OAM, OAMADDR,
+    // and the sprite overflow flag untouched.
+    pub fn evaluate_extra_sprites(oam: &Oam, pixel_row: PixelRow, sprite_height: SpriteHeight) -> Vec<[u8; 4]> {
+        (0..64)
+            .map(|sprite_index| oam.peek_sprite(sprite_index))
+            .filter(|[y, ..]| {
+                PixelRow::try_from_u8(*y)
+                    .and_then(|top_sprite_row| pixel_row.difference(top_sprite_row))
+                    .is_some_and(|offset| offset < sprite_height.to_dimension())
+            })
+            // Secondary OAM only has room for the first eight.
+            .skip(8)
+            .collect()
+    }
+}
@@ -2,10 +2,11 @@
 #![allow(clippy::cast_lossless, clippy::no_effect_underscore_binding)]
 
 use modular_bitfield::Specifier;
+use serde::{Deserialize, Serialize};
 
 use crate::ppu::sprite::sprite_half::SpriteHalf;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Specifier)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Specifier, Serialize, Deserialize)]
 pub enum SpriteHeight {
     Normal,
     Tall,
@@ -1,10 +1,11 @@
 use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
 
 use crate::ppu::pixel_index::{PixelRow, RowInTile};
 use crate::ppu::sprite::sprite_half::SpriteHalf;
 use crate::ppu::sprite::sprite_height::SpriteHeight;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct SpriteY(u8);
 
 impl SpriteY {
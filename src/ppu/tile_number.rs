@@ -1,10 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::ppu::pattern_table_side::PatternTableSide;
 use crate::ppu::pixel_index::{PixelRow, RowInTile};
 use crate::ppu::sprite::sprite_half::SpriteHalf;
 use crate::ppu::sprite::sprite_height::SpriteHeight;
 use crate::ppu::sprite::sprite_y::SpriteY;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct TileNumber(u8);
 
 impl TileNumber {
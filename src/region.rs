@@ -0,0 +1,72 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cartridge::cartridge_metadata::TimingMode;
+use crate::ppu::render::frame_rate::FrameRate;
+
+// The console hardware whose timing is emulated.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub enum Region {
+    // RP2A03 CPU and RP2C02 PPU.
+    #[default]
+    Ntsc,
+    // RP2A07 CPU and RP2C07 PPU.
+    Pal,
+    // UA6538 famiclone. PAL frame rate, but with NTSC CPU speed and APU tables.
+    Dendy,
+}
+
+impl Region {
+    pub fn from_timing_mode(timing_mode: TimingMode) -> Region {
+        match timing_mode {
+            // Multi-region cartridges are run on the most common hardware.
+            TimingMode::Ntsc | TimingMode::MultiRegion => Region::Ntsc,
+            TimingMode::Pal => Region::Pal,
+            TimingMode::Dendy => Region::Dendy,
+        }
+    }
+
+    // CPU cycles per second, rounded.
+    pub fn cpu_frequency(self) -> u32 {
+        match self {
+            Region::Ntsc => 1_789_773,
+            Region::Pal => 1_662_607,
+            Region::Dendy => 1_773_448,
+        }
+    }
+
+    pub fn frame_rate(self) -> FrameRate {
+        match self {
+            Region::Ntsc => FrameRate::NTSC,
+            Region::Pal => FrameRate::PAL,
+            Region::Dendy => FrameRate::DENDY,
+        }
+    }
+}
+
+impl FromStr for Region {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().as_str() {
+            "ntsc" => Ok(Region::Ntsc),
+            "pal" => Ok(Region::Pal),
+            "dendy" => Ok(Region::Dendy),
+            _ => Err(format!("Invalid region: {value}")),
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Region::Ntsc => "NTSC",
+            Region::Pal => "PAL",
+            Region::Dendy => "Dendy",
+        };
+
+        write!(f, "{text}")
+    }
+}
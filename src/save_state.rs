@@ -0,0 +1,261 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::apu::apu_registers::ApuRegisters;
+use crate::apu::speech_chip::{SpeechChip, SpeechChipState};
+use crate::bus::Bus;
+use crate::controller::joypad::Joypad;
+use crate::cpu::cpu::Cpu;
+use crate::cpu::dmc_dma::DmcDma;
+use crate::cpu::oam_dma::OamDma;
+use crate::mapper::mapper::Mapper;
+use crate::master_clock::{CpuPpuAlignment, MasterClock};
+use crate::memory::cpu::cpu_address::CpuAddress;
+use crate::memory::cpu::cpu_pinout::CpuPinout;
+use crate::memory::cpu::prg_memory::PrgMemoryState;
+use crate::memory::ppu::chr_memory::ChrMemoryState;
+use crate::memory::ppu::ppu_pinout::PpuPinout;
+use crate::memory::regions::ciram::Ciram;
+use crate::memory::regions::cpu_internal_ram::CpuInternalRam;
+use crate::memory::regions::disk::{Disk, DiskState};
+use crate::nsf_player::{NsfPlayer, NsfPlayerState};
+use crate::memory::regions::palette_ram::PaletteRam;
+use crate::memory::regions::small_page::SmallPage;
+use crate::ppu::ppu::Ppu;
+use crate::ppu::register::ppu_registers::PpuRegisters;
+use crate::ppu::render::frame::Frame;
+use crate::ppu::sprite::oam::Oam;
+use crate::region::Region;
+use crate::vs_system::VsSystem;
+
+const MAGIC: [u8; 4] = *b"RZSS";
+// Must be incremented whenever the serialized form of any part of the machine changes,
+// since old save states won't be loadable after that.
+pub const SAVE_STATE_VERSION: u32 = 14;
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    magic: [u8; 4],
+    version: u32,
+    // The full CRC of the ROM that the state was saved from.
+    full_hash: u32,
+    region: Region,
+    // Each alignment has different timing, so a state can only be resumed with the one it was saved with.
+    cpu_ppu_alignment: CpuPpuAlignment,
+}
+
+// Serializes everything needed to resume emulation at the current cycle.
+// ROM, layouts, and other data that is fixed for a cartridge are not included, nor is anything
+// that only affects logging, debug screens, or the audio output device.
+// The partially rendered frame is included so that states saved mid-frame resume with identical output.
+pub fn save(bus: &Bus, mapper: &dyn Mapper, frame: &Frame, full_hash: u32) -> Result<Vec<u8>, String> {
+    let mut writer = StateWriter(Vec::new());
+    writer.write(&Header {
+        magic: MAGIC,
+        version: SAVE_STATE_VERSION,
+        full_hash,
+        region: bus.master_clock.region(),
+        cpu_ppu_alignment: bus.master_clock.cpu_ppu_alignment(),
+    })?;
+
+    writer.write(&bus.cpu)?;
+    writer.write(&bus.ppu)?;
+    writer.write(&bus.master_clock)?;
+    writer.write(&bus.dmc_dma)?;
+    writer.write(&bus.oam_dma)?;
+    writer.write(&bus.joypad1)?;
+    writer.write(&bus.joypad2)?;
+
+    writer.write(&bus.ppu_regs)?;
+    writer.write(&bus.apu_regs)?;
+
+    writer.write(&bus.cpu_internal_ram)?;
+    writer.write(&bus.ciram)?;
+    writer.write(&bus.palette_ram)?;
+    writer.write(&bus.oam)?;
+    writer.write(&bus.prg_memory.state())?;
+    writer.write(&bus.chr_memory.state())?;
+    writer.write(&bus.mapper_custom_pages)?;
+    writer.write(&bus.mapper_internal_ram.to_vec())?;
+
+    writer.write(&bus.cpu_pinout)?;
+    writer.write(&bus.ppu_pinout)?;
+    writer.write(&bus.oam_dma_address_bus)?;
+    writer.write(&bus.dmc_dma_address_bus)?;
+    writer.write(&bus.dip_switch)?;
+    writer.write(&bus.vs_system)?;
+    writer.write(&bus.disk.as_ref().map(Disk::state))?;
+    writer.write(&bus.nsf_player.as_ref().map(NsfPlayer::state))?;
+    writer.write(&bus.speech_chip.as_ref().map(SpeechChip::state))?;
+
+    writer.write(&mapper.save_state()?)?;
+    writer.write(frame)?;
+
+    Ok(writer.0)
+}
+
+// Restores a state created by save(). The machine is left untouched if the state is rejected.
+pub fn load(state: &[u8], bus: &mut Bus, mapper: &mut dyn Mapper, frame: &mut Frame, full_hash: u32) -> Result<(), String> {
+    let mut reader = StateReader(state);
+    let header: Header = reader.read()?;
+    if header.magic != MAGIC {
+        return Err("Not a save state.".to_string());
+    }
+
+    if header.version != SAVE_STATE_VERSION {
+        return Err(format!(
+            "Save state version {} is not supported. Only version {SAVE_STATE_VERSION} can be loaded.", header.version));
+    }
+
+    if header.full_hash != full_hash {
+        return Err(format!(
+            "Save state is for a different ROM (Full CRC: 0x{:X}, expected 0x{full_hash:X}).", header.full_hash));
+    }
+
+    let region = bus.master_clock.region();
+    if header.region != region {
+        return Err(format!("Save state is for a {} console, but a {region} console is being emulated.", header.region));
+    }
+
+    let alignment = bus.master_clock.cpu_ppu_alignment();
+    if header.cpu_ppu_alignment != alignment {
+        return Err(format!(
+            "Save state has CPU/PPU alignment {}, but alignment {} is being emulated.",
+            header.cpu_ppu_alignment.index(), alignment.index()));
+    }
+
+    // Read everything before modifying anything so that a corrupt state can't leave the machine half-loaded.
+    let cpu: Cpu = reader.read()?;
+    let ppu: Ppu = reader.read()?;
+    let master_clock: MasterClock = reader.read()?;
+    let dmc_dma: DmcDma = reader.read()?;
+    let oam_dma: OamDma = reader.read()?;
+    let joypad1: Joypad = reader.read()?;
+    let joypad2: Joypad = reader.read()?;
+
+    let ppu_regs: PpuRegisters = reader.read()?;
+    let apu_regs: ApuRegisters = reader.read()?;
+
+    let cpu_internal_ram: CpuInternalRam = reader.read()?;
+    let ciram: Ciram = reader.read()?;
+    let palette_ram: PaletteRam = reader.read()?;
+    let oam: Oam = reader.read()?;
+    let prg_memory: PrgMemoryState = reader.read()?;
+    let chr_memory: ChrMemoryState = reader.read()?;
+    let mapper_custom_pages: Vec<SmallPage> = reader.read()?;
+    let mapper_internal_ram: Vec<u8> = reader.read()?;
+
+    let cpu_pinout: CpuPinout = reader.read()?;
+    let ppu_pinout: PpuPinout = reader.read()?;
+    let oam_dma_address_bus: CpuAddress = reader.read()?;
+    let dmc_dma_address_bus: CpuAddress = reader.read()?;
+    let dip_switch: u8 = reader.read()?;
+    let vs_system: Option<VsSystem> = reader.read()?;
+    let disk: Option<DiskState> = reader.read()?;
+    let nsf_player: Option<NsfPlayerState> = reader.read()?;
+    let speech_chip: Option<SpeechChipState> = reader.read()?;
+
+    let mapper_state: Vec<u8> = reader.read()?;
+    let loaded_frame: Frame = reader.read()?;
+    if !reader.0.is_empty() {
+        return Err(format!("Save state has {} unexpected trailing bytes.", reader.0.len()));
+    }
+
+    if mapper_custom_pages.len() != bus.mapper_custom_pages.len() {
+        return Err("Save state has the wrong number of mapper custom pages.".to_string());
+    }
+
+    if vs_system.is_some() != bus.vs_system.is_some() {
+        return Err("Save state doesn't match whether a Vs. System is being emulated.".to_string());
+    }
+
+    if disk.is_some() != bus.disk.is_some() {
+        return Err("Save state doesn't match whether a disk is being emulated.".to_string());
+    }
+
+    if nsf_player.is_some() != bus.nsf_player.is_some() {
+        return Err("Save state doesn't match whether an NSF is being played.".to_string());
+    }
+
+    if speech_chip.is_some() != bus.speech_chip.is_some() {
+        return Err("Save state doesn't match whether a speech chip is being emulated.".to_string());
+    }
+
+    // The ROM matches, so these can only fail if the state was crafted by hand.
+    bus.prg_memory.check_state(&prg_memory)?;
+    bus.chr_memory.check_state(&chr_memory)?;
+    bus.mapper_internal_ram.check_load(&mapper_internal_ram)?;
+    if let (Some(disk), Some(disk_state)) = (&bus.disk, &disk) {
+        disk.check_state(disk_state)?;
+    }
+
+    if let (Some(player), Some(player_state)) = (&bus.nsf_player, &nsf_player) {
+        player.check_state(player_state)?;
+    }
+
+    if let (Some(chip), Some(chip_state)) = (&bus.speech_chip, &speech_chip) {
+        chip.check_state(chip_state)?;
+    }
+
+    // The mapper is only replaced if its state deserializes successfully, so this must be the
+    // last fallible step. Nothing below can fail.
+    mapper.load_state(&mapper_state)?;
+    bus.prg_memory.load_state(prg_memory);
+    bus.chr_memory.load_state(chr_memory);
+    bus.mapper_internal_ram.load(&mapper_internal_ram).expect("Mapper internal RAM size should have been checked.");
+    if let (Some(disk), Some(disk_state)) = (&mut bus.disk, disk) {
+        disk.load_state(disk_state);
+    }
+
+    if let (Some(player), Some(player_state)) = (&mut bus.nsf_player, nsf_player) {
+        player.load_state(player_state);
+    }
+
+    if let (Some(chip), Some(chip_state)) = (&mut bus.speech_chip, speech_chip) {
+        chip.load_state(chip_state);
+    }
+
+    bus.cpu.load_state(cpu);
+    bus.ppu.load_state(ppu);
+    bus.master_clock.load_state(master_clock);
+    bus.dmc_dma = dmc_dma;
+    bus.oam_dma = oam_dma;
+    bus.joypad1 = joypad1;
+    bus.joypad2 = joypad2;
+
+    bus.ppu_regs = ppu_regs;
+    bus.apu_regs = apu_regs;
+
+    bus.cpu_internal_ram = cpu_internal_ram;
+    bus.ciram = ciram;
+    bus.palette_ram = palette_ram;
+    bus.oam = oam;
+    bus.mapper_custom_pages = mapper_custom_pages;
+
+    bus.cpu_pinout = cpu_pinout;
+    bus.ppu_pinout = ppu_pinout;
+    bus.oam_dma_address_bus = oam_dma_address_bus;
+    bus.dmc_dma_address_bus = dmc_dma_address_bus;
+    bus.dip_switch = dip_switch;
+    bus.vs_system = vs_system;
+
+    frame.load_state(loaded_frame);
+
+    Ok(())
+}
+
+struct StateWriter(Vec<u8>);
+
+impl StateWriter {
+    fn write<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), String> {
+        bincode::serialize_into(&mut self.0, value).map_err(|err| format!("Failed to write save state. {err}"))
+    }
+}
+
+struct StateReader<'a>(&'a [u8]);
+
+impl StateReader<'_> {
+    fn read<T: DeserializeOwned>(&mut self) -> Result<T, String> {
+        bincode::deserialize_from(&mut self.0).map_err(|err| format!("Failed to read save state. {err}"))
+    }
+}
@@ -1,3 +1,6 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
 pub struct CircularBuffer<T> {
     // TODO: Replace this with a boxed array.
     buffer: Vec<T>,
@@ -0,0 +1,124 @@
+// Compresses a buffer against a similar reference buffer (such as the previous save state).
+// The two buffers are XORed together, then runs of unchanged (zero) bytes are collapsed.
+// The encoding is a series of (zero run length, literal length, literal bytes) chunks,
+// with each length stored as a LEB128 varint.
+
+pub fn encode(reference: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut result = Vec::new();
+    write_varint(&mut result, target.len());
+
+    let xored: Vec<u8> = target.iter().enumerate()
+        .map(|(i, &value)| value ^ reference.get(i).copied().unwrap_or(0))
+        .collect();
+
+    let mut index = 0;
+    while index < xored.len() {
+        let zero_run_start = index;
+        while index < xored.len() && xored[index] == 0 {
+            index += 1;
+        }
+
+        let literal_start = index;
+        // Short zero runs inside a literal cost more to encode separately than to store as-is.
+        while index < xored.len() && !xored[index..].starts_with(&[0, 0, 0]) {
+            index += 1;
+        }
+
+        write_varint(&mut result, literal_start - zero_run_start);
+        write_varint(&mut result, index - literal_start);
+        result.extend_from_slice(&xored[literal_start..index]);
+    }
+
+    result
+}
+
+pub fn decode(reference: &[u8], delta: &[u8]) -> Result<Vec<u8>, String> {
+    let mut delta = delta;
+    let len = read_varint(&mut delta)?;
+    let mut result: Vec<u8> = (0..len).map(|i| reference.get(i).copied().unwrap_or(0)).collect();
+
+    let mut index = 0;
+    while !delta.is_empty() {
+        index += read_varint(&mut delta)?;
+        let literal_len = read_varint(&mut delta)?;
+        if literal_len > delta.len() || index + literal_len > result.len() {
+            return Err("Delta literal extends past the end of the data.".to_string());
+        }
+
+        for (value, &xor) in result[index..index + literal_len].iter_mut().zip(&delta[..literal_len]) {
+            *value ^= xor;
+        }
+
+        index += literal_len;
+        delta = &delta[literal_len..];
+    }
+
+    Ok(result)
+}
+
+fn write_varint(output: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        output.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+
+    output.push(value as u8);
+}
+
+fn read_varint(input: &mut &[u8]) -> Result<usize, String> {
+    let mut value: usize = 0;
+    for shift in (0..usize::BITS).step_by(7) {
+        let (&byte, rest) = input.split_first().ok_or("Delta ended in the middle of a length.")?;
+        *input = rest;
+        value |= usize::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err("Delta length is too large.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_identical() {
+        let data: Vec<u8> = (0..=255).collect();
+        let delta = encode(&data, &data);
+        assert!(delta.len() < 8);
+        assert_eq!(decode(&data, &delta).unwrap(), data);
+    }
+
+    #[test]
+    fn round_trip_sparse_changes() {
+        let reference: Vec<u8> = (0..1000).map(|i| (i % 251) as u8).collect();
+        let mut target = reference.clone();
+        target[0] = 0xFF;
+        target[500] ^= 0x10;
+        target[501] ^= 0x01;
+        target[999] = 0;
+        let delta = encode(&reference, &target);
+        assert!(delta.len() < 32);
+        assert_eq!(decode(&reference, &delta).unwrap(), target);
+    }
+
+    #[test]
+    fn round_trip_different_lengths() {
+        let reference = vec![1, 2, 3, 4, 5];
+        let longer = vec![1, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0];
+        assert_eq!(decode(&reference, &encode(&reference, &longer)).unwrap(), longer);
+        let shorter = vec![1, 9];
+        assert_eq!(decode(&reference, &encode(&reference, &shorter)).unwrap(), shorter);
+        assert_eq!(decode(&reference, &encode(&reference, &[])).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn decode_rejects_truncated_delta() {
+        let reference = vec![0; 16];
+        let target = vec![7; 16];
+        let delta = encode(&reference, &target);
+        assert!(decode(&reference, &delta[..delta.len() - 1]).is_err());
+    }
+}
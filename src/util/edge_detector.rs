@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
 use std::marker::ConstParamTy_;
 
+#[derive(Serialize, Deserialize)]
 pub struct EdgeDetector<V: ConstParamTy_> {
     target_value: Option<V>,
 
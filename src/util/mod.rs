@@ -1,7 +1,10 @@
 pub mod bit_util;
 pub mod circular_buffer;
 pub mod const_vec;
+pub mod delta_compression;
 pub mod edge_detector;
 pub mod hash_util;
 pub mod pattern_table_transition_detector;
-pub mod unit;
\ No newline at end of file
+pub mod serde_util;
+pub mod unit;
+pub mod wav;
@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::memory::ppu::ppu_address::PpuAddress;
 use crate::ppu::pattern_table_side::PatternTableSide;
 
 // A specialized EdgeDetector for PatternTableSide transitions.
+#[derive(Serialize, Deserialize)]
 pub struct PatternTableTransitionDetector {
     prev_side: PatternTableSide,
     allowed_addresses: AllowedAddresses,
@@ -30,7 +33,7 @@ impl PatternTableTransitionDetector {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum AllowedAddresses {
     All,
     PatternTableOnly,
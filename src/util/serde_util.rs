@@ -0,0 +1,75 @@
+// Helpers for serializing types that serde doesn't support out of the box.
+// Use with #[serde(with = "crate::util::serde_util::...")].
+
+// Non-standard-width integers from the ux crate. Stored as u64 and range-checked upon load.
+pub mod ux {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer, T: Copy + Into<u64>>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64((*value).into())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: TryFrom<u64>>(deserializer: D) -> Result<T, D::Error> {
+        let raw = u64::deserialize(deserializer)?;
+        T::try_from(raw).map_err(|_| D::Error::custom(format!("Value {raw} is out of range.")))
+    }
+}
+
+// Arrays of non-standard-width integers from the ux crate.
+pub mod ux_array {
+    use serde::{Deserializer, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S, T, const N: usize>(values: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer, T: Copy + Into<u64> {
+        serializer.collect_seq(values.iter().map(|&value| value.into()))
+    }
+
+    pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where D: Deserializer<'de>, T: TryFrom<u64> + Copy {
+        let raws: [u64; N] = super::array::deserialize(deserializer)?;
+        let mut values = Vec::with_capacity(N);
+        for raw in raws {
+            values.push(T::try_from(raw).map_err(|_| D::Error::custom(format!("Value {raw} is out of range.")))?);
+        }
+
+        Ok(values.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+}
+
+// Arrays longer than 32 elements.
+pub mod array {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(value: &[T; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(value.iter())
+    }
+
+    pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where D: Deserializer<'de>, T: Deserialize<'de> {
+        let values: Vec<T> = Vec::deserialize(deserializer)?;
+        let len = values.len();
+        values.try_into().map_err(|_| D::Error::invalid_length(len, &format!("an array of length {N}").as_str()))
+    }
+}
+
+// Heap-allocated arrays longer than 32 elements. Large arrays are boxed to avoid stack overflows,
+// so they are never constructed on the stack here either.
+pub mod boxed_array {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(value: &[T; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(value.iter())
+    }
+
+    pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<Box<[T; N]>, D::Error>
+    where D: Deserializer<'de>, T: Deserialize<'de> {
+        let values: Vec<T> = Vec::deserialize(deserializer)?;
+        let len = values.len();
+        values.into_boxed_slice().try_into()
+            .map_err(|_| D::Error::invalid_length(len, &format!("an array of length {N}").as_str()))
+    }
+}
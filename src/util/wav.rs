@@ -0,0 +1,146 @@
+// Minimal RIFF WAVE support: uncompressed 8-bit and 16-bit PCM only (16-bit mono when writing).
+
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+const PCM_FORMAT: u16 = 1;
+const HEADER_SIZE: u32 = 44;
+
+pub struct WavAudio {
+    pub sample_rate: u32,
+    // Mixed down to mono, between -1.0 and 1.0.
+    pub samples: Vec<f32>,
+}
+
+impl WavAudio {
+    pub fn parse(raw: &[u8]) -> Result<WavAudio, String> {
+        if raw.len() < 12 || &raw[0..4] != b"RIFF" || &raw[8..12] != b"WAVE" {
+            return Err("Not a RIFF WAVE file.".to_string());
+        }
+
+        let mut format = None;
+        let mut data = None;
+        let mut remaining = &raw[12..];
+        while remaining.len() >= 8 {
+            let id = &remaining[0..4];
+            let size = u32::from_le_bytes(remaining[4..8].try_into().unwrap()) as usize;
+            let body = remaining.get(8..8 + size).ok_or("WAV chunk extends past the end of the file.")?;
+            match id {
+                b"fmt " => format = Some(WavFormat::parse(body)?),
+                b"data" => data = Some(body),
+                _ => { /* Ignore metadata chunks. */ }
+            }
+
+            // Chunks are padded to an even length.
+            remaining = remaining.get(8 + size + size % 2..).unwrap_or(&[]);
+        }
+
+        let format = format.ok_or("WAV file has no format chunk.")?;
+        let data = data.ok_or("WAV file has no data chunk.")?;
+        let frame_size = usize::from(format.channel_count) * usize::from(format.bits_per_sample / 8);
+        let samples = data.chunks_exact(frame_size)
+            .map(|frame| {
+                let channel_total: f32 = match format.bits_per_sample {
+                    8 => frame.iter().map(|&sample| (f32::from(sample) - 128.0) / 128.0).sum(),
+                    16 => frame.chunks_exact(2)
+                        .map(|sample| f32::from(i16::from_le_bytes([sample[0], sample[1]])) / 32768.0)
+                        .sum(),
+                    _ => unreachable!(),
+                };
+                channel_total / f32::from(format.channel_count)
+            })
+            .collect();
+
+        Ok(WavAudio { sample_rate: format.sample_rate, samples })
+    }
+}
+
+struct WavFormat {
+    channel_count: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+}
+
+impl WavFormat {
+    fn parse(body: &[u8]) -> Result<WavFormat, String> {
+        if body.len() < 16 {
+            return Err("WAV format chunk is too short.".to_string());
+        }
+
+        let audio_format = u16::from_le_bytes([body[0], body[1]]);
+        let channel_count = u16::from_le_bytes([body[2], body[3]]);
+        let sample_rate = u32::from_le_bytes(body[4..8].try_into().unwrap());
+        let bits_per_sample = u16::from_le_bytes([body[14], body[15]]);
+        if audio_format != PCM_FORMAT {
+            return Err(format!("Only uncompressed PCM WAV files are supported, not format {audio_format}."));
+        }
+
+        if bits_per_sample != 8 && bits_per_sample != 16 {
+            return Err(format!("Only 8-bit and 16-bit WAV files are supported, not {bits_per_sample}-bit."));
+        }
+
+        if channel_count == 0 || sample_rate == 0 {
+            return Err("WAV file has no channels or a zero sample rate.".to_string());
+        }
+
+        Ok(WavFormat { channel_count, sample_rate, bits_per_sample })
+    }
+}
+
+// Writes 16-bit mono PCM.
+pub struct WavWriter {
+    file: BufWriter<File>,
+    sample_rate: u32,
+    sample_count: u32,
+}
+
+impl WavWriter {
+    pub fn create(path: &Path, sample_rate: u32) -> Result<WavWriter, String> {
+        let file = File::create(path)
+            .map_err(|err| format!("Failed to create WAV file {}. {err}", path.display()))?;
+        let mut writer = WavWriter { file: BufWriter::new(file), sample_rate, sample_count: 0 };
+        writer.write_header()?;
+        Ok(writer)
+    }
+
+    // Samples outside of -1.0 to 1.0 are clipped.
+    pub fn write_sample(&mut self, sample: f32) -> Result<(), String> {
+        let sample = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+        self.file.write_all(&sample.to_le_bytes()).map_err(|err| format!("Failed to write WAV sample. {err}"))?;
+        self.sample_count += 1;
+        Ok(())
+    }
+
+    // Rewrites the header with the current length, so that the file is valid even if nothing
+    // more is written to it (for example, if the program is killed).
+    pub fn update_header(&mut self) -> Result<(), String> {
+        self.file.seek(SeekFrom::Start(0)).map_err(|err| format!("Failed to seek in WAV file. {err}"))?;
+        self.write_header()?;
+        self.file.seek(SeekFrom::End(0)).map_err(|err| format!("Failed to seek in WAV file. {err}"))?;
+        self.file.flush().map_err(|err| format!("Failed to flush WAV file. {err}"))
+    }
+
+    fn write_header(&mut self) -> Result<(), String> {
+        let data_size = 2 * self.sample_count;
+        let mut header = Vec::with_capacity(HEADER_SIZE as usize);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&(HEADER_SIZE - 8 + data_size).to_le_bytes());
+        header.extend_from_slice(b"WAVE");
+        header.extend_from_slice(b"fmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&PCM_FORMAT.to_le_bytes());
+        // Mono
+        header.extend_from_slice(&1u16.to_le_bytes());
+        header.extend_from_slice(&self.sample_rate.to_le_bytes());
+        // Bytes per second
+        header.extend_from_slice(&(2 * self.sample_rate).to_le_bytes());
+        // Bytes per frame
+        header.extend_from_slice(&2u16.to_le_bytes());
+        // Bits per sample
+        header.extend_from_slice(&16u16.to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&data_size.to_le_bytes());
+        self.file.write_all(&header).map_err(|err| format!("Failed to write WAV header. {err}"))
+    }
+}
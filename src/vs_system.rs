@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use splitbits::combinebits;
+
+use crate::cartridge::cartridge_metadata::{VsHardwareType, VsPpuType};
+use crate::controller::joypad::{Button, ButtonStatus, Joypad};
+use crate::memory::cpu::cpu_address::CpuAddress;
+use crate::memory::read_result::ReadResult;
+use crate::ppu::palette::system_palette::SystemPalette;
+
+// The RP2C04 PPUs have scrambled palettes, each a different permutation of the RP2C03 palette.
+// Entries that aren't present in the RP2C03 palette are approximated with black (0x2E) or white (0x20).
+#[rustfmt::skip]
+const RP2C04_0001_LOOKUP: [u8; 64] = [
+    0x35, 0x23, 0x16, 0x22, 0x1C, 0x09, 0x1D, 0x15, 0x20, 0x00, 0x27, 0x05, 0x04, 0x28, 0x08, 0x20,
+    0x21, 0x3E, 0x1F, 0x29, 0x3C, 0x32, 0x36, 0x12, 0x3F, 0x2B, 0x2E, 0x1E, 0x3D, 0x2D, 0x24, 0x01,
+    0x0E, 0x31, 0x33, 0x2A, 0x2C, 0x0C, 0x1B, 0x14, 0x2E, 0x07, 0x34, 0x06, 0x13, 0x02, 0x26, 0x2E,
+    0x2E, 0x19, 0x10, 0x0A, 0x39, 0x03, 0x37, 0x17, 0x0F, 0x11, 0x0B, 0x0D, 0x38, 0x25, 0x18, 0x3A,
+];
+#[rustfmt::skip]
+const RP2C04_0002_LOOKUP: [u8; 64] = [
+    0x2E, 0x27, 0x18, 0x39, 0x3A, 0x25, 0x1C, 0x31, 0x16, 0x13, 0x38, 0x34, 0x20, 0x23, 0x3C, 0x0B,
+    0x0F, 0x21, 0x06, 0x3D, 0x1B, 0x29, 0x1E, 0x22, 0x1D, 0x24, 0x0E, 0x2B, 0x32, 0x08, 0x2E, 0x03,
+    0x04, 0x36, 0x26, 0x33, 0x11, 0x1F, 0x10, 0x02, 0x14, 0x3F, 0x00, 0x09, 0x12, 0x2E, 0x28, 0x20,
+    0x3E, 0x0D, 0x2A, 0x17, 0x0C, 0x01, 0x15, 0x19, 0x2E, 0x2C, 0x07, 0x37, 0x35, 0x05, 0x0A, 0x2D,
+];
+#[rustfmt::skip]
+const RP2C04_0003_LOOKUP: [u8; 64] = [
+    0x14, 0x25, 0x3A, 0x10, 0x0B, 0x20, 0x31, 0x09, 0x01, 0x2E, 0x36, 0x08, 0x15, 0x3D, 0x3E, 0x3C,
+    0x22, 0x1C, 0x05, 0x12, 0x19, 0x18, 0x17, 0x1B, 0x00, 0x03, 0x2E, 0x02, 0x16, 0x06, 0x34, 0x35,
+    0x23, 0x0F, 0x0E, 0x37, 0x0D, 0x27, 0x26, 0x20, 0x29, 0x04, 0x21, 0x24, 0x11, 0x2D, 0x2E, 0x1F,
+    0x2C, 0x1E, 0x39, 0x33, 0x07, 0x2A, 0x28, 0x1D, 0x0A, 0x2E, 0x32, 0x38, 0x13, 0x2B, 0x3F, 0x0C,
+];
+#[rustfmt::skip]
+const RP2C04_0004_LOOKUP: [u8; 64] = [
+    0x18, 0x03, 0x1C, 0x28, 0x2E, 0x35, 0x01, 0x17, 0x10, 0x1F, 0x2A, 0x0E, 0x36, 0x37, 0x0B, 0x39,
+    0x25, 0x1E, 0x12, 0x34, 0x2E, 0x1D, 0x06, 0x26, 0x3E, 0x1B, 0x22, 0x19, 0x04, 0x2E, 0x3A, 0x21,
+    0x05, 0x0A, 0x07, 0x02, 0x13, 0x14, 0x00, 0x15, 0x0C, 0x3D, 0x11, 0x0F, 0x0D, 0x38, 0x2D, 0x24,
+    0x33, 0x20, 0x08, 0x16, 0x3F, 0x2B, 0x20, 0x3C, 0x2E, 0x27, 0x23, 0x31, 0x29, 0x32, 0x2C, 0x09,
+];
+
+#[rustfmt::skip]
+const TKO_BOXING_PROTECTION_DATA: [u8; 32] = [
+    0xFF, 0xBF, 0xB7, 0x97, 0x97, 0x17, 0x57, 0x4F, 0x6F, 0x6B, 0xEB, 0xA9, 0xB1, 0x90, 0x94, 0x14,
+    0x56, 0x4E, 0x6F, 0x6B, 0xEB, 0xA9, 0xB1, 0x90, 0xD4, 0x5C, 0x3E, 0x26, 0x87, 0x83, 0x13, 0x00,
+];
+
+// Vs. UniSystem arcade hardware: coin slots, a service button, DIP switches, copy protection, and PPU variants.
+// https://www.nesdev.org/wiki/Vs._System
+#[derive(Clone, Serialize, Deserialize)]
+pub struct VsSystem {
+    hardware_type: VsHardwareType,
+    ppu_type: VsPpuType,
+
+    coin1: ButtonStatus,
+    coin2: ButtonStatus,
+    service: ButtonStatus,
+    protection_counter: u8,
+}
+
+impl VsSystem {
+    pub fn new(hardware_type: VsHardwareType, ppu_type: VsPpuType) -> Result<VsSystem, String> {
+        if matches!(hardware_type, VsHardwareType::DualSystem | VsHardwareType::DualSystemRaidOnBungelingBayProtection) {
+            return Err("Vs. DualSystem games require two consoles, which aren't supported yet.".to_string());
+        }
+
+        Ok(VsSystem {
+            hardware_type,
+            ppu_type,
+
+            coin1: ButtonStatus::Unpressed,
+            coin2: ButtonStatus::Unpressed,
+            service: ButtonStatus::Unpressed,
+            protection_counter: 0,
+        })
+    }
+
+    // The RGB PPUs use the RP2C03 palette (directly or scrambled) instead of the NTSC palette.
+    pub fn system_palette(&self) -> SystemPalette {
+        let rgb_palette = SystemPalette::parse(include_str!("../palettes/2C03.pal")).unwrap();
+        match self.ppu_type {
+            VsPpuType::Rp2c04_0001 => rgb_palette.remapped(&RP2C04_0001_LOOKUP),
+            VsPpuType::Rp2c04_0002 => rgb_palette.remapped(&RP2C04_0002_LOOKUP),
+            VsPpuType::Rp2c04_0003 => rgb_palette.remapped(&RP2C04_0003_LOOKUP),
+            VsPpuType::Rp2c04_0004 => rgb_palette.remapped(&RP2C04_0004_LOOKUP),
+            // TODO: Stroke & Golf's PPU type isn't documented, so assume it's a plain RGB PPU.
+            VsPpuType::Rp2c03Rc2c03 | VsPpuType::StrokeAndGolf
+                | VsPpuType::Rc2c05_01 | VsPpuType::Rc2c05_02 | VsPpuType::Rc2c05_03 | VsPpuType::Rc2c05_04 => rgb_palette,
+        }
+    }
+
+    // The RC2C05 PPUs have PPUCTRL and PPUMASK at each other's addresses.
+    pub fn ppu_control_and_mask_swapped(&self) -> bool {
+        self.rc2c05_id().is_some()
+    }
+
+    // The RC2C05 PPUs return an identifier in the low bits of PPUSTATUS, which some games check.
+    pub fn peek_ppu_status(&self, status: ReadResult) -> ReadResult {
+        match self.rc2c05_id() {
+            Some(id) => ReadResult::partial(id, 0b0001_1111).dominate(status),
+            None => status,
+        }
+    }
+
+    // Peek 0x4016
+    pub fn peek_controller1(&self, joypad: &Joypad, dip_switch: u8) -> ReadResult {
+        let serial = self.joypad_serial_bit(joypad);
+        let service = self.service == ButtonStatus::Pressed;
+        let dip = dip_switch & 0b11;
+        let coin1 = self.coin1 == ButtonStatus::Pressed;
+        let coin2 = self.coin2 == ButtonStatus::Pressed;
+        // Bit 7 is only set on the secondary CPU of a DualSystem.
+        ReadResult::full(combinebits!(coin2, coin1, dip, service, serial, "0abc cd0e"))
+    }
+
+    // Peek 0x4017
+    pub fn peek_controller2(&self, joypad: &Joypad, dip_switch: u8) -> ReadResult {
+        let serial = self.joypad_serial_bit(joypad);
+        ReadResult::full((dip_switch & 0b1111_1100) | u8::from(serial))
+    }
+
+    // Copy protection chips that respond to reads in the mapper register range.
+    pub fn peek_protection(&self, addr: CpuAddress) -> Option<ReadResult> {
+        let value = match (self.hardware_type, *addr) {
+            (VsHardwareType::UnisystemRbiBaseballProtection, 0x5E00) => return None,
+            (VsHardwareType::UnisystemRbiBaseballProtection, 0x5E01) =>
+                if self.protection_counter == 9 { 0x6F } else { 0xB4 },
+            (VsHardwareType::UnisystemTkoBoxingProtection, 0x5E00) => return None,
+            (VsHardwareType::UnisystemTkoBoxingProtection, 0x5E01) =>
+                TKO_BOXING_PROTECTION_DATA[usize::from(self.protection_counter & 0x1F)],
+            (VsHardwareType::UnisystemSuperXeviousProtection, 0x54FF) => 0x05,
+            (VsHardwareType::UnisystemSuperXeviousProtection, 0x5678) =>
+                if self.protection_counter == 0 { 0x01 } else { 0x00 },
+            (VsHardwareType::UnisystemSuperXeviousProtection, 0x578F) =>
+                if self.protection_counter == 0 { 0x89 } else { 0xD1 },
+            // The value depends upon the toggle that happens during the read.
+            (VsHardwareType::UnisystemSuperXeviousProtection, 0x5567) =>
+                if self.protection_counter == 0 { 0x37 } else { 0x3E },
+            _ => return None,
+        };
+
+        Some(ReadResult::full(value))
+    }
+
+    pub fn on_cpu_read(&mut self, addr: CpuAddress) {
+        match (self.hardware_type, *addr) {
+            (VsHardwareType::UnisystemRbiBaseballProtection | VsHardwareType::UnisystemTkoBoxingProtection, 0x5E00) =>
+                self.protection_counter = 0,
+            (VsHardwareType::UnisystemRbiBaseballProtection | VsHardwareType::UnisystemTkoBoxingProtection, 0x5E01) =>
+                self.protection_counter = self.protection_counter.wrapping_add(1),
+            (VsHardwareType::UnisystemSuperXeviousProtection, 0x5567) =>
+                self.protection_counter ^= 1,
+            _ => {}
+        }
+    }
+
+    pub fn set_input_status(&mut self, input: VsInput, status: ButtonStatus) {
+        match input {
+            VsInput::Coin1 => self.coin1 = status,
+            VsInput::Coin2 => self.coin2 = status,
+            VsInput::Service => self.service = status,
+        }
+    }
+
+    fn joypad_serial_bit(&self, joypad: &Joypad) -> bool {
+        // Vs. Ice Climber's protection expects the Start line to always be held.
+        let start_held = self.hardware_type == VsHardwareType::UnisystemIceClimberProtection
+            && joypad.selected_button() == Some(Button::Start);
+        start_held || joypad.peek_status().resolve(0) & 1 == 1
+    }
+
+    fn rc2c05_id(&self) -> Option<u8> {
+        match self.ppu_type {
+            VsPpuType::Rc2c05_01 | VsPpuType::Rc2c05_04 => Some(0x1B),
+            VsPpuType::Rc2c05_02 => Some(0x3D),
+            VsPpuType::Rc2c05_03 => Some(0x1C),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum VsInput {
+    Coin1,
+    Coin2,
+    Service,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(vs_system: &mut VsSystem, addr: u16) -> Option<u8> {
+        let addr = CpuAddress::new(addr);
+        let value = vs_system.peek_protection(addr).map(ReadResult::unmasked_value);
+        vs_system.on_cpu_read(addr);
+        value
+    }
+
+    #[test]
+    fn rbi_baseball_protection() {
+        let mut vs_system = VsSystem::new(VsHardwareType::UnisystemRbiBaseballProtection, VsPpuType::Rp2c03Rc2c03).unwrap();
+        assert_eq!(read(&mut vs_system, 0x5E00), None);
+        let values: Vec<_> = (0..11).map(|_| read(&mut vs_system, 0x5E01).unwrap()).collect();
+        assert_eq!(values[9], 0x6F);
+        assert!(values.iter().enumerate().all(|(i, &value)| i == 9 || value == 0xB4));
+
+        read(&mut vs_system, 0x5E00);
+        assert_eq!(read(&mut vs_system, 0x5E01), Some(0xB4));
+    }
+
+    #[test]
+    fn super_xevious_protection() {
+        let mut vs_system = VsSystem::new(VsHardwareType::UnisystemSuperXeviousProtection, VsPpuType::Rp2c04_0001).unwrap();
+        assert_eq!(read(&mut vs_system, 0x54FF), Some(0x05));
+        assert_eq!(read(&mut vs_system, 0x5678), Some(0x01));
+        assert_eq!(read(&mut vs_system, 0x5567), Some(0x37));
+        assert_eq!(read(&mut vs_system, 0x5678), Some(0x00));
+        assert_eq!(read(&mut vs_system, 0x578F), Some(0xD1));
+        assert_eq!(read(&mut vs_system, 0x5567), Some(0x3E));
+        assert_eq!(read(&mut vs_system, 0x5E01), None);
+    }
+}
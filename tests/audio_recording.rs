@@ -0,0 +1,87 @@
+extern crate reznez;
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::{TestEmulator, TestRom};
+
+const HEADER_SIZE: usize = 44;
+
+#[test]
+fn recording_matches_output_samples() {
+    let recording_path = test_directory("matches").join("recording.wav");
+    let mut emulator = load();
+    emulator.nes_mut().start_audio_recording(&recording_path).unwrap();
+    emulator.step_frames(10);
+    let output_samples = emulator.take_samples();
+
+    // The header is kept up to date after every frame, without the recording being stopped.
+    let recorded_samples = read_wav(&recording_path);
+    assert_eq!(recorded_samples.len(), output_samples.len());
+    for (recorded, output) in recorded_samples.iter().zip(&output_samples) {
+        assert!((recorded - output).abs() < 0.001, "Recorded {recorded}, output {output}");
+    }
+
+    assert!(recorded_samples.iter().any(|&sample| sample.abs() > 0.01));
+}
+
+#[test]
+fn recording_can_be_stopped_and_started() {
+    let recording_path = test_directory("toggle").join("recording.wav");
+    let mut emulator = load();
+    assert!(!emulator.nes().is_recording_audio());
+    emulator.step_frames(1);
+
+    emulator.nes_mut().start_audio_recording(&recording_path).unwrap();
+    assert!(emulator.nes().is_recording_audio());
+    emulator.step_frames(1);
+    emulator.nes_mut().stop_audio_recording();
+    assert!(!emulator.nes().is_recording_audio());
+    let recorded_sample_count = read_wav(&recording_path).len();
+    emulator.step_frames(1);
+
+    // Roughly 735 samples per NTSC frame.
+    assert!((700..770).contains(&recorded_sample_count), "Unexpected sample count: {recorded_sample_count}");
+    assert_eq!(read_wav(&recording_path).len(), recorded_sample_count);
+}
+
+fn test_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("reznez_audio_recording_test_{name}_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+// Loads an NROM ROM that plays a square wave on pulse 1.
+fn load() -> TestEmulator {
+    let program = [
+        0xA9, 0x01,       // LDA #$01
+        0x8D, 0x15, 0x40, // STA $4015
+        0xA9, 0xBF,       // LDA #$BF
+        0x8D, 0x00, 0x40, // STA $4000
+        0xA9, 0xFD,       // LDA #$FD
+        0x8D, 0x02, 0x40, // STA $4002
+        0xA9, 0x08,       // LDA #$08
+        0x8D, 0x03, 0x40, // STA $4003
+    ];
+
+    TestRom::ines(0, 32).with_program(&program).load()
+}
+
+// Returns the samples of a 16-bit mono WAV file, checking that the header matches the data.
+fn read_wav(path: &Path) -> Vec<f32> {
+    let wav = fs::read(path).unwrap();
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(&wav[8..16], b"WAVEfmt ");
+    assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()) as usize, wav.len() - 8);
+    assert_eq!(u16::from_le_bytes([wav[22], wav[23]]), 1);
+    assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 44100);
+    assert_eq!(u16::from_le_bytes([wav[34], wav[35]]), 16);
+    assert_eq!(&wav[36..40], b"data");
+    assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()) as usize, wav.len() - HEADER_SIZE);
+
+    wav[HEADER_SIZE..].chunks_exact(2)
+        .map(|sample| f32::from(i16::from_le_bytes([sample[0], sample[1]])) / f32::from(i16::MAX))
+        .collect()
+}
@@ -0,0 +1,159 @@
+// Helpers shared by the integration tests that run a small hand-assembled program on a specific
+// mapper. Not every test uses every helper.
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use reznez::bus::AddressBusType;
+use reznez::emulator::{AudioSink, Emulator, EmulatorBuilder};
+use reznez::memory::cpu::cpu_address::CpuAddress;
+use reznez::nes::Nes;
+
+const PROGRAM_START: u16 = 0xE000;
+
+// A ROM whose last 8KiB of PRG ROM is mapped to $E000-$FFFF, which is where the program goes.
+pub struct TestRom {
+    header: [u8; 16],
+    pub prg_rom: Vec<u8>,
+    pub chr_rom: Vec<u8>,
+}
+
+impl TestRom {
+    pub fn ines(mapper_number: u8, prg_rom_kib: usize) -> Self {
+        let mut header = [0; 16];
+        header[0..4].copy_from_slice(b"NES\x1A");
+        header[4] = (prg_rom_kib / 16) as u8;
+        header[5] = 1;
+        header[6] = mapper_number << 4;
+        header[7] = mapper_number & 0xF0;
+
+        // PRG ROM is filled with NOPs, and execution starts at the program.
+        let mut rom = Self { header, prg_rom: vec![0xEA; prg_rom_kib * 1024], chr_rom: vec![0; 8 * 1024] };
+        rom.write(0xFFFC, &PROGRAM_START.to_le_bytes());
+        rom
+    }
+
+    pub fn nes2(mapper_number: u8, submapper_number: u8, prg_rom_kib: usize) -> Self {
+        let mut rom = Self::ines(mapper_number, prg_rom_kib);
+        rom.header[7] |= 0b0000_1000;
+        rom.header[8] = submapper_number << 4;
+        rom
+    }
+
+    pub fn with_battery(mut self) -> Self {
+        self.header[6] |= 0b0000_0010;
+        self
+    }
+
+    // Fills all of PRG ROM, including the reset vector, so this must come before anything else.
+    pub fn with_prg_rom_filled(mut self, value: u8) -> Self {
+        self.prg_rom.fill(value);
+        self.write(0xFFFC, &PROGRAM_START.to_le_bytes());
+        self
+    }
+
+    // Places the program at $E000, followed by a loop so that execution never runs past it.
+    pub fn with_program(mut self, program: &[u8]) -> Self {
+        let loop_address = PROGRAM_START + program.len() as u16;
+        self.write(PROGRAM_START, program);
+        self.write(loop_address, &[0x4C, loop_address as u8, (loop_address >> 8) as u8]);
+        self
+    }
+
+    // Places the IRQ handler at $E100, and points the IRQ vector at it.
+    pub fn with_irq_handler(mut self, irq_handler: &[u8]) -> Self {
+        self.write(0xE100, irq_handler);
+        self.write(0xFFFE, &0xE100u16.to_le_bytes());
+        self
+    }
+
+    // Places arbitrary bytes somewhere in $E000-$FFFF.
+    pub fn with_bytes(mut self, addr: u16, bytes: &[u8]) -> Self {
+        self.write(addr, bytes);
+        self
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut rom = self.header.to_vec();
+        rom.extend_from_slice(&self.prg_rom);
+        rom.extend_from_slice(&self.chr_rom);
+        rom
+    }
+
+    pub fn builder(&self) -> EmulatorBuilder {
+        EmulatorBuilder::from_rom_bytes("test.nes", self.to_bytes())
+    }
+
+    pub fn load(&self) -> TestEmulator {
+        TestEmulator::new(self.builder())
+    }
+
+    fn write(&mut self, addr: u16, bytes: &[u8]) {
+        assert!(addr >= PROGRAM_START, "Only $E000-$FFFF can be written to.");
+        let start = self.prg_rom.len() - 0x2000 + usize::from(addr - PROGRAM_START);
+        self.prg_rom[start..start + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+// An emulator that collects all of its audio output.
+pub struct TestEmulator {
+    emulator: Emulator,
+    samples: Rc<RefCell<Vec<f32>>>,
+}
+
+impl TestEmulator {
+    pub fn new(builder: EmulatorBuilder) -> Self {
+        let samples = Rc::new(RefCell::new(Vec::new()));
+        let emulator = builder.audio_sink(SampleCollector(samples.clone())).build().unwrap();
+        Self { emulator, samples }
+    }
+
+    pub fn nes(&self) -> &Nes {
+        self.emulator.nes()
+    }
+
+    pub fn nes_mut(&mut self) -> &mut Nes {
+        self.emulator.nes_mut()
+    }
+
+    pub fn step_frames(&mut self, frame_count: u32) {
+        for _ in 0..frame_count {
+            self.emulator.step_frame();
+        }
+    }
+
+    // Runs long enough for the program to finish setting up, leaving only the samples of the last frame.
+    pub fn run(&mut self) {
+        self.step_frames(5);
+        // Discard the initial samples, where the filters are still settling.
+        self.take_samples();
+        self.step_frames(1);
+    }
+
+    pub fn take_samples(&mut self) -> Vec<f32> {
+        self.samples.take()
+    }
+
+    // The difference between the highest and lowest sample since the samples were last taken.
+    pub fn amplitude(&mut self) -> f32 {
+        let samples = self.take_samples();
+        assert!(!samples.is_empty());
+        let max = samples.iter().copied().fold(f32::MIN, f32::max);
+        let min = samples.iter().copied().fold(f32::MAX, f32::min);
+        max - min
+    }
+
+    pub fn peek(&self, addr: u16) -> u8 {
+        let nes = self.nes();
+        nes.bus().cpu_peek(nes.mapper(), AddressBusType::Cpu, CpuAddress::new(addr))
+    }
+}
+
+struct SampleCollector(Rc<RefCell<Vec<f32>>>);
+
+impl AudioSink for SampleCollector {
+    fn samples_ready(&mut self, samples: &[f32]) {
+        self.0.borrow_mut().extend_from_slice(samples);
+    }
+}
@@ -0,0 +1,122 @@
+extern crate reznez;
+
+use std::fs;
+use std::path::PathBuf;
+
+use reznez::bus::AddressBusType;
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::memory::cpu::cpu_address::CpuAddress;
+use reznez::nes::Nes;
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+
+// Stores the results of several ADC and SBC instructions at $0200-$0202 and $0204, and status flags at $0203 and $0205.
+const PROGRAM: &[u8] = &[
+    0xF8,             // 8000: SED
+    0x18,             // 8001: CLC
+    0xA9, 0x19,       // 8002: LDA #$19
+    0x69, 0x28,       // 8004: ADC #$28
+    0x8D, 0x00, 0x02, // 8006: STA $0200
+    0x38,             // 8009: SEC
+    0xA9, 0x50,       // 800A: LDA #$50
+    0xE9, 0x21,       // 800C: SBC #$21
+    0x8D, 0x01, 0x02, // 800E: STA $0201
+    0x18,             // 8011: CLC
+    0xA9, 0x99,       // 8012: LDA #$99
+    0x69, 0x01,       // 8014: ADC #$01
+    0x8D, 0x02, 0x02, // 8016: STA $0202
+    0x08,             // 8019: PHP
+    0x68,             // 801A: PLA
+    0x8D, 0x03, 0x02, // 801B: STA $0203
+    0x38,             // 801E: SEC
+    0xA9, 0x00,       // 801F: LDA #$00
+    0xE9, 0x01,       // 8021: SBC #$01
+    0x8D, 0x04, 0x02, // 8023: STA $0204
+    0x08,             // 8026: PHP
+    0x68,             // 8027: PLA
+    0x8D, 0x05, 0x02, // 8028: STA $0205
+    0x4C, 0x2B, 0x80, // 802B: JMP $802B
+];
+
+const CARRY: u8 = 0b0000_0001;
+const ZERO: u8 = 0b0000_0010;
+const NEGATIVE: u8 = 0b1000_0000;
+
+#[test]
+fn decimal_mode_famiclone_adjusts_results() {
+    let nes = run("famiclone", 3, false);
+    assert_eq!(results(&nes), [0x47, 0x29, 0x00, 0x99]);
+    // N comes from the sum before the high digit is adjusted ($A0). Z comes from the binary sum ($9A).
+    assert_eq!(peek(&nes, 0x203) & (NEGATIVE | ZERO | CARRY), NEGATIVE | CARRY);
+    // SBC flags are the same as in binary mode.
+    assert_eq!(peek(&nes, 0x205) & (NEGATIVE | ZERO | CARRY), NEGATIVE);
+}
+
+#[test]
+fn decimal_mode_can_be_enabled_from_the_command_line() {
+    let nes = run("override", 0, true);
+    assert_eq!(results(&nes), [0x47, 0x29, 0x00, 0x99]);
+}
+
+#[test]
+fn unsupported_console_type_is_rejected() {
+    // Console type 5 is a VT01 famiclone.
+    assert!(new_nes("vt01", 5, false).is_err());
+}
+
+#[test]
+fn nes_ignores_decimal_flag() {
+    let nes = run("nes", 0, false);
+    assert_eq!(results(&nes), [0x41, 0x2F, 0x9A, 0xFF]);
+    assert_eq!(peek(&nes, 0x203) & (NEGATIVE | ZERO | CARRY), NEGATIVE);
+}
+
+fn run(name: &str, console_type: u8, decimal_mode: bool) -> Nes {
+    let mut nes = new_nes(name, console_type, decimal_mode).unwrap();
+    nes.mute();
+    nes.step_frame();
+    nes
+}
+
+// Loads the ROM the same way that the command line does.
+fn new_nes(name: &str, console_type: u8, decimal_mode: bool) -> Result<Nes, String> {
+    let directory = std::env::temp_dir().join(format!("reznez_decimal_mode_test_{name}_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let rom_path = write_rom(&directory, console_type);
+
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        decimal_mode,
+        ..Opt::new(Some(rom_path))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap())?;
+    Nes::new(&HeaderDb::load(), &config, &cartridge)
+}
+
+// Writes an NES 2.0 NROM image. Console types 3 and above are extended console types, stored in byte 13.
+fn write_rom(directory: &std::path::Path, console_type: u8) -> PathBuf {
+    let basic_console_type = console_type.min(3);
+    let mut rom = vec![b'N', b'E', b'S', 0x1A, 1, 1, 0, 0b0000_1000 | basic_console_type, 0, 0, 0, 0, 0, console_type];
+    rom.resize(16, 0);
+    let mut prg_rom = vec![0xEA; 16 * 1024];
+    prg_rom[..PROGRAM.len()].copy_from_slice(PROGRAM);
+    prg_rom[0x3FFC..0x3FFE].copy_from_slice(&[0x00, 0x80]);
+    rom.extend_from_slice(&prg_rom);
+    rom.extend_from_slice(&[0; 8 * 1024]);
+
+    let path = directory.join("decimal_mode.nes");
+    fs::write(&path, rom).unwrap();
+    path
+}
+
+fn results(nes: &Nes) -> [u8; 4] {
+    [0x200, 0x201, 0x202, 0x204].map(|addr| peek(nes, addr))
+}
+
+fn peek(nes: &Nes, addr: u16) -> u8 {
+    nes.bus().cpu_peek(nes.mapper(), AddressBusType::Cpu, CpuAddress::new(addr))
+}
@@ -0,0 +1,107 @@
+extern crate reznez;
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use reznez::emulator::{AudioSink, EmulatorBuilder, SaveRamSink, VideoSink};
+use reznez::ppu::render::frame::Frame;
+
+// Increments $6000 once, then spins.
+const PROGRAM: &[u8] = &[
+    0xEE, 0x00, 0x60, // C000: INC $6000
+    0x4C, 0x03, 0xC0, // C003: JMP $C003
+];
+
+#[derive(Clone, Default)]
+struct Recorder {
+    frame_count: Rc<RefCell<u32>>,
+    sample_count: Rc<RefCell<usize>>,
+    save_rams: Rc<RefCell<Vec<Vec<u8>>>>,
+}
+
+impl VideoSink for Recorder {
+    fn frame_ready(&mut self, _frame: &Frame) {
+        *self.frame_count.borrow_mut() += 1;
+    }
+}
+
+impl AudioSink for Recorder {
+    fn samples_ready(&mut self, samples: &[f32]) {
+        *self.sample_count.borrow_mut() += samples.len();
+    }
+}
+
+impl SaveRamSink for Recorder {
+    fn save_ram_changed(&mut self, save_ram: &[u8]) {
+        self.save_rams.borrow_mut().push(save_ram.to_vec());
+    }
+}
+
+#[test]
+fn sinks_receive_frames_and_audio() {
+    let recorder = Recorder::default();
+    let mut emulator = EmulatorBuilder::from_rom_bytes("embedding_sinks.nes", rom())
+        .video_sink(recorder.clone())
+        .audio_sink(recorder.clone())
+        .build()
+        .unwrap();
+
+    for _ in 0..10 {
+        emulator.step_frame();
+    }
+
+    assert_eq!(*recorder.frame_count.borrow(), 10);
+    // Roughly 735 samples per NTSC frame.
+    let sample_count = *recorder.sample_count.borrow();
+    assert!((7000..8000).contains(&sample_count), "Unexpected sample count: {sample_count}");
+}
+
+#[test]
+fn save_ram_round_trips_through_sink() {
+    let recorder = Recorder::default();
+    let mut emulator = EmulatorBuilder::from_rom_bytes("embedding_save_ram.nes", rom())
+        .save_ram_sink(recorder.clone())
+        .build()
+        .unwrap();
+    emulator.step_frame();
+    emulator.step_frame();
+
+    // Only the frame that changed save RAM should be reported.
+    let save_rams = recorder.save_rams.borrow().clone();
+    assert_eq!(save_rams.len(), 1);
+    assert_eq!(save_rams[0].len(), 8 * 1024);
+    assert_eq!(save_rams[0][0], 1);
+
+    // Reload from the persisted save RAM: the program increments it again.
+    let recorder = Recorder::default();
+    let mut emulator = EmulatorBuilder::from_rom_bytes("embedding_save_ram.nes", rom())
+        .save_ram(save_rams[0].clone())
+        .save_ram_sink(recorder.clone())
+        .build()
+        .unwrap();
+    emulator.step_frame();
+    assert_eq!(recorder.save_rams.borrow().last().unwrap()[0], 2);
+
+    assert!(!Path::new("saveram/embedding_save_ram.prg.saveram").exists());
+}
+
+#[test]
+fn wrong_save_ram_size_is_rejected() {
+    let result = EmulatorBuilder::from_rom_bytes("embedding_bad_save_ram.nes", rom())
+        .save_ram(vec![0; 100])
+        .build();
+    assert!(result.is_err());
+}
+
+// An NES 2.0 MMC1 ROM with 16KiB PRG ROM, 8KiB battery-backed PRG RAM, and 8KiB CHR ROM.
+fn rom() -> Vec<u8> {
+    let mut rom = vec![b'N', b'E', b'S', 0x1A, 1, 1, 0b0001_0010, 0b0000_1000, 0, 0, 0x70];
+    rom.resize(16, 0);
+    let mut prg_rom = vec![0xEA; 16 * 1024];
+    prg_rom[..PROGRAM.len()].copy_from_slice(PROGRAM);
+    prg_rom[0x3FFC..0x3FFE].copy_from_slice(&[0x00, 0xC0]);
+    rom.extend_from_slice(&prg_rom);
+    rom.extend_from_slice(&[0; 8 * 1024]);
+    rom
+}
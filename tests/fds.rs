@@ -0,0 +1,143 @@
+extern crate reznez;
+
+use std::fs;
+use std::path::PathBuf;
+
+use reznez::bus::AddressBusType;
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::memory::cpu::cpu_address::CpuAddress;
+use reznez::nes::Nes;
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+
+const SIDE_SIZE: usize = 65500;
+const DISK_INFO_BLOCK_START: &[u8] = b"\x01*NINTENDO-HVC*";
+
+// Enables the disk registers, starts the motor in read mode, then stores the first 16 bytes read from the disk at $0200.
+const BIOS_PROGRAM: &[u8] = &[
+    0x78,             // E000: SEI
+    0xA9, 0x01,       // E001: LDA #$01
+    0x8D, 0x23, 0x40, // E003: STA $4023
+    0xA9, 0x27,       // E006: LDA #$27 (motor on, transfer reset, read mode)
+    0x8D, 0x25, 0x40, // E008: STA $4025
+    0xA9, 0x25,       // E00B: LDA #$25 (motor on, read mode)
+    0x8D, 0x25, 0x40, // E00D: STA $4025
+    0xAD, 0x32, 0x40, // E010: LDA $4032
+    0x29, 0x02,       // E013: AND #$02
+    0xD0, 0xF9,       // E015: BNE $E010
+    0xA9, 0x65,       // E017: LDA #$65 (motor on, read mode, drive ready)
+    0x8D, 0x25, 0x40, // E019: STA $4025
+    0xA2, 0x00,       // E01C: LDX #$00
+    0xAD, 0x30, 0x40, // E01E: LDA $4030
+    0x29, 0x02,       // E021: AND #$02
+    0xF0, 0xF9,       // E023: BEQ $E01E
+    0xAD, 0x31, 0x40, // E025: LDA $4031
+    0x9D, 0x00, 0x02, // E028: STA $0200,X
+    0xE8,             // E02B: INX
+    0xE0, 0x10,       // E02C: CPX #$10
+    0xD0, 0xEE,       // E02E: BNE $E01E
+    0x4C, 0x30, 0xE0, // E030: JMP $E030
+    0x40,             // E033: RTI
+];
+
+#[test]
+fn bios_reads_disk_info_block() {
+    let mut nes = load_nes("read");
+    for _ in 0..30 {
+        nes.step_frame();
+    }
+
+    let read_bytes: Vec<u8> = (0..16).map(|i| peek(&nes, 0x0200 + i)).collect();
+    assert_eq!(&read_bytes[..15], DISK_INFO_BLOCK_START);
+    // Maker code.
+    assert_eq!(read_bytes[15], 0xA4);
+}
+
+#[test]
+fn side_swap_takes_effect_after_delay() {
+    let mut nes = load_nes("swap");
+    nes.step_frame();
+    assert_eq!(peek(&nes, 0x4032) & 1, 0, "Disk should start inserted.");
+
+    nes.insert_disk_side(1);
+    nes.step_frame();
+    assert_eq!(peek(&nes, 0x4032) & 1, 1, "Disk should be out of the drive while being swapped.");
+
+    for _ in 0..70 {
+        nes.step_frame();
+    }
+
+    assert_eq!(peek(&nes, 0x4032) & 1, 0, "Second side should have been inserted.");
+    assert_eq!(nes.bus().disk.as_ref().unwrap().inserted_side(), Some(1));
+
+    nes.eject_disk();
+    nes.step_frame();
+    assert_eq!(peek(&nes, 0x4032) & 1, 1);
+}
+
+#[test]
+fn disk_image_requires_bios() {
+    let directory = test_directory("no_bios");
+    let disk_path = write_disk_image(&directory);
+    let opt = Opt { gui: GuiType::NoGui, prevent_saving: true, ..Opt::new(Some(disk_path)) };
+    let cartridge = Nes::load_cartridge(&opt.rom_path.clone().unwrap()).unwrap();
+    assert!(Nes::new(&HeaderDb::load(), &Config::new(&opt), &cartridge).is_err());
+}
+
+fn load_nes(name: &str) -> Nes {
+    let directory = test_directory(name);
+    let disk_path = write_disk_image(&directory);
+
+    let mut bios = vec![0xEA; 8 * 1024];
+    bios[..BIOS_PROGRAM.len()].copy_from_slice(BIOS_PROGRAM);
+    // NMI, RESET, and IRQ vectors.
+    bios[0x1FFA..].copy_from_slice(&[0x33, 0xE0, 0x00, 0xE0, 0x33, 0xE0]);
+    let bios_path = directory.join("bios.rom");
+    fs::write(&bios_path, bios).unwrap();
+
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        fds_bios: Some(bios_path),
+        ..Opt::new(Some(disk_path))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+    nes
+}
+
+fn test_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("reznez_fds_test_{name}_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+// Writes a headered two-sided image, each side with only a disk info block and an empty file list.
+fn write_disk_image(directory: &std::path::Path) -> PathBuf {
+    let mut image = vec![b'F', b'D', b'S', 0x1A, 2];
+    image.resize(16, 0);
+    for side_number in 0..2 {
+        let mut side = vec![0; SIDE_SIZE];
+        side[..DISK_INFO_BLOCK_START.len()].copy_from_slice(DISK_INFO_BLOCK_START);
+        // Maker code, game name, and side number.
+        side[15] = 0xA4;
+        side[16..19].copy_from_slice(b"TST");
+        side[22] = side_number;
+        // File amount block.
+        side[56] = 2;
+        side[57] = 0;
+        image.extend_from_slice(&side);
+    }
+
+    let path = directory.join("test.fds");
+    fs::write(&path, image).unwrap();
+    path
+}
+
+fn peek(nes: &Nes, addr: u16) -> u8 {
+    nes.bus().cpu_peek(nes.mapper(), AddressBusType::Cpu, CpuAddress::new(addr))
+}
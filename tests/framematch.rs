@@ -18,6 +18,7 @@ use walkdir::WalkDir;
 use reznez::logging::logger;
 use reznez::logging::logger::Logger;
 use reznez::config::{Config, GuiType, Opt};
+use reznez::master_clock::CpuPpuAlignment;
 use reznez::nes::Nes;
 use reznez::ppu::render::frame_rate::TargetFrameRate;
 use reznez::ppu::render::ppm::Ppm;
@@ -26,6 +27,10 @@ use reznez::util::hash_util::calculate_hash;
 type Crc = u32;
 type FrameNumber = i64;
 
+// Test ROMs whose results depend upon the exact CPU/PPU timing relationship. They must pass under every alignment.
+const ALIGNMENT_SENSITIVE_ROM_FAMILIES: &[&str] =
+    &["ppu_vbl_nmi/", "vbl_nmi_timing/", "dmc_dma_during_read4/", "sprdma_and_dmc_dma/"];
+
 #[allow(dead_code)]
 #[derive(Clone, Copy)]
 enum Event {
@@ -127,23 +132,43 @@ static SCHEDULED_EVENTS: LazyLock<BTreeMap<Crc, BTreeMap<FrameNumber, (Event, Bu
 fn framematch() {
     let expected_frames = ExpectedFrames::load("tests/expected_frames");
     let roms = Roms::load("tests/roms");
-    let test_summary = TestSummary::load(&roms, &expected_frames);
+    let test_summary = TestSummary::load(&roms, &expected_frames, CpuPpuAlignment::default());
     test_summary.print();
     assert!(test_summary.passed());
 }
 
+#[test]
+fn framematch_all_cpu_ppu_alignments() {
+    let is_alignment_sensitive = |rom_id: &RomId| {
+        ALIGNMENT_SENSITIVE_ROM_FAMILIES.iter().any(|family| rom_id.starts_with(family))
+    };
+    let expected_frames = ExpectedFrames::load("tests/expected_frames").filter(is_alignment_sensitive);
+    let roms = Roms::load("tests/roms").filter(is_alignment_sensitive);
+
+    let mut passed = true;
+    for alignment in CpuPpuAlignment::ALL {
+        println!("CPU/PPU alignment {}:", alignment.index());
+        let test_summary = TestSummary::load(&roms, &expected_frames, alignment);
+        test_summary.print();
+        passed &= test_summary.passed();
+    }
+
+    assert!(passed);
+}
+
 struct TestSummary {
     test_results: BTreeMap<RomId, TestStatus>,
 }
 
 impl TestSummary {
-    fn load(roms: &Roms, expected_frames: &ExpectedFrames) -> Self {
+    fn load(roms: &Roms, expected_frames: &ExpectedFrames, alignment: CpuPpuAlignment) -> Self {
         // Log nothing by default, but if debugging is needed, then logging can be enabled.
-        logger::init(Logger {
+        // The logger is global, so only the first test to get here actually installs it.
+        let _ = logger::init(Logger {
             disable_all: true,
             buffer: Arc::new(Mutex::new(String::new())),
             .. Logger::default()
-        }).unwrap();
+        });
 
         let test_results = DashMap::new();
 
@@ -161,6 +186,7 @@ impl TestSummary {
                     target_frame_rate: TargetFrameRate::Unbounded,
                     disable_audio: true,
                     prevent_saving: true,
+                    cpu_ppu_alignment: alignment,
                     ..Opt::new(Some(rom_entry.path.clone()))
                 };
 
@@ -202,7 +228,7 @@ impl TestSummary {
                         }
                     }
 
-                    let events = Events { should_quit: false, joypad1_button_statuses, joypad2_button_statuses: BTreeMap::new() };
+                    let events = Events { joypad1_button_statuses, ..Events::none() };
                     nes.process_gui_events(&events);
 
                     nes.step_frame();
@@ -222,7 +248,10 @@ impl TestSummary {
                             }
                         } else {
                             test_results.insert(rom_id.clone(), TestStatus::Fail);
-                            let directory: PathBuf = frame_directory.components().skip(2).collect();
+                            let mut directory: PathBuf = frame_directory.components().skip(2).collect();
+                            if alignment != CpuPpuAlignment::default() {
+                                directory = PathBuf::from(format!("alignment{}", alignment.index())).join(directory);
+                            }
                             fs::create_dir_all(format!("tests/actual_frames/{}/", directory.display())).unwrap();
                             let actual_ppm_path =
                                 format!("tests/actual_frames/{}/frame{:03}.ppm", directory.display(), frame_number);
@@ -294,6 +323,11 @@ impl Roms {
 
         Roms { entries_by_rom_id }
     }
+
+    fn filter(mut self, predicate: impl Fn(&RomId) -> bool) -> Roms {
+        self.entries_by_rom_id.retain(|rom_id, _| predicate(rom_id));
+        self
+    }
 }
 
 struct RomEntry {
@@ -355,6 +389,11 @@ impl ExpectedFrames {
 
         Self { entries_by_rom_id }
     }
+
+    fn filter(mut self, predicate: impl Fn(&RomId) -> bool) -> Self {
+        self.entries_by_rom_id.retain(|rom_id, _| predicate(rom_id));
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -0,0 +1,96 @@
+extern crate reznez;
+
+mod common;
+
+use common::{TestEmulator, TestRom};
+
+// Plays pulse 1 at full volume with its length counter halted, then saves the length counter status.
+const PULSE_PROGRAM: &[u8] = &[
+    0xA9, 0x01,       // LDA #$01
+    0x8D, 0x15, 0x50, // STA $5015
+    0xA9, 0xBF,       // LDA #$BF (50% duty, halt, constant volume 15)
+    0x8D, 0x00, 0x50, // STA $5000
+    0xA9, 0x00,       // LDA #$00
+    0x8D, 0x02, 0x50, // STA $5002
+    0xA9, 0x01,       // LDA #$01
+    0x8D, 0x03, 0x50, // STA $5003
+    0xAD, 0x15, 0x50, // LDA $5015
+    0x8D, 0x00, 0x02, // STA $0200
+];
+
+// Alternates the raw PCM output between $FF and $01.
+const PCM_WRITE_PROGRAM: &[u8] = &[
+    0xA9, 0xFF,       // LDA #$FF
+    0x8D, 0x11, 0x50, // STA $5011
+    0xCA,             // DEX
+    0xD0, 0xFD,       // BNE (DEX)
+    0xA9, 0x01,       // LDA #$01
+    0x8D, 0x11, 0x50, // STA $5011
+    0xCA,             // DEX
+    0xD0, 0xFD,       // BNE (DEX)
+    0x4C, 0x00, 0xE0, // JMP $E000
+];
+
+// Enables read mode with IRQs, then reads a zero from $8000. The IRQ handler saves $5010.
+const PCM_READ_PROGRAM: &[u8] = &[
+    0xA9, 0x40,       // LDA #$40
+    0x8D, 0x17, 0x40, // STA $4017 (Disable the APU frame IRQ)
+    0xA9, 0x81,       // LDA #$81
+    0x8D, 0x10, 0x50, // STA $5010
+    0x58,             // CLI
+    0xAD, 0x00, 0x80, // LDA $8000
+];
+
+const IRQ_HANDLER: &[u8] = &[
+    0xAD, 0x10, 0x50, // LDA $5010
+    0x8D, 0x00, 0x02, // STA $0200
+    0xEE, 0x01, 0x02, // INC $0201
+    0x40,             // RTI
+];
+
+#[test]
+fn pulse_is_audible() {
+    let mut emulator = load(PULSE_PROGRAM);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.08);
+}
+
+#[test]
+fn length_counter_status_is_readable() {
+    let mut emulator = load(PULSE_PROGRAM);
+    emulator.run();
+    assert_eq!(emulator.peek(0x0200) & 0b11, 0b01);
+}
+
+#[test]
+fn pulse_is_silenced_by_mute() {
+    let mut emulator = load(PULSE_PROGRAM);
+    emulator.nes_mut().mute();
+    emulator.run();
+    assert!(emulator.amplitude() < 0.001);
+}
+
+#[test]
+fn pcm_write_mode_is_audible() {
+    let mut emulator = load(PCM_WRITE_PROGRAM);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.3);
+}
+
+#[test]
+fn pcm_read_mode_triggers_irq_on_zero() {
+    let mut emulator = load(PCM_READ_PROGRAM);
+    emulator.run();
+    assert_eq!(emulator.peek(0x0200) & 0b1000_0000, 0b1000_0000);
+    // Reading $5010 acknowledged the IRQ, so the handler only ran once.
+    assert_eq!(emulator.peek(0x0201), 1);
+}
+
+// Loads an MMC5 ROM with zeroed PRG ROM.
+fn load(program: &[u8]) -> TestEmulator {
+    TestRom::ines(5, 32)
+        .with_prg_rom_filled(0x00)
+        .with_program(program)
+        .with_irq_handler(IRQ_HANDLER)
+        .load()
+}
@@ -0,0 +1,100 @@
+extern crate reznez;
+
+mod common;
+
+use common::{TestEmulator, TestRom};
+use reznez::ppu::pixel_index::{PixelColumn, PixelRow};
+
+// Fills the ExRAM name table with tile 1, enables the vertical split with the split CHR bank
+// set to 1, then turns on background rendering.
+const PROGRAM: &[u8] = &[
+    0x78,             // E000: SEI
+    0xA2, 0xFF,       // E001: LDX #$FF
+    0x9A,             // E003: TXS
+    0x2C, 0x02, 0x20, // E004: BIT $2002
+    0x10, 0xFB,       // E007: BPL $E004
+    0x2C, 0x02, 0x20, // E009: BIT $2002
+    0x10, 0xFB,       // E00C: BPL $E009
+    0xA9, 0x3F,       // E00E: LDA #$3F
+    0x8D, 0x06, 0x20, // E010: STA $2006
+    0xA9, 0x00,       // E013: LDA #$00
+    0x8D, 0x06, 0x20, // E015: STA $2006
+    0xA9, 0x0F,       // E018: LDA #$0F
+    0x8D, 0x07, 0x20, // E01A: STA $2007
+    0xA9, 0x30,       // E01D: LDA #$30
+    0x8D, 0x07, 0x20, // E01F: STA $2007
+    0xA9, 0x00,       // E022: LDA #$00
+    0x8D, 0x06, 0x20, // E024: STA $2006
+    0x8D, 0x06, 0x20, // E027: STA $2006
+    0x8D, 0x04, 0x51, // E02A: STA $5104
+    0xAA,             // E02D: TAX
+    0xA9, 0x01,       // E02E: LDA #$01
+    0x9D, 0x00, 0x5C, // E030: STA $5C00,X
+    0x9D, 0x00, 0x5D, // E033: STA $5D00,X
+    0x9D, 0x00, 0x5E, // E036: STA $5E00,X
+    0x9D, 0x00, 0x5F, // E039: STA $5F00,X
+    0xE8,             // E03C: INX
+    0xD0, 0xF1,       // E03D: BNE $E030
+    0xA9, 0x00,       // E03F: LDA #$00
+    0xA2, 0xC0,       // E041: LDX #$C0
+    0x9D, 0x00, 0x5F, // E043: STA $5F00,X
+    0xE8,             // E046: INX
+    0xD0, 0xFA,       // E047: BNE $E043
+    0xA9, 0x90,       // E049: LDA #$90 (Patched with the split mode)
+    0x8D, 0x00, 0x52, // E04B: STA $5200
+    0xA9, 0x00,       // E04E: LDA #$00
+    0x8D, 0x01, 0x52, // E050: STA $5201
+    0xA9, 0x01,       // E053: LDA #$01
+    0x8D, 0x02, 0x52, // E055: STA $5202
+    0xA9, 0x0A,       // E058: LDA #$0A
+    0x8D, 0x01, 0x20, // E05A: STA $2001
+];
+const SPLIT_MODE_ADDRESS: u16 = 0xE04A;
+
+// Color $30 in the default system palette.
+const WHITE: (u8, u8, u8) = (0xEC, 0xEE, 0xEC);
+
+#[test]
+fn left_split_shows_ext_ram_tiles() {
+    // Enabled, left side, threshold at tile column 16.
+    let emulator = run(0b1001_0000);
+    assert_eq!(pixel(&emulator, 4, 100), WHITE);
+    assert_eq!(pixel(&emulator, 100, 100), WHITE);
+    assert_ne!(pixel(&emulator, 200, 100), WHITE);
+}
+
+#[test]
+fn right_split_shows_ext_ram_tiles() {
+    // Enabled, right side, threshold at tile column 16.
+    let emulator = run(0b1101_0000);
+    assert_ne!(pixel(&emulator, 4, 100), WHITE);
+    assert_ne!(pixel(&emulator, 100, 100), WHITE);
+    assert_eq!(pixel(&emulator, 200, 100), WHITE);
+}
+
+#[test]
+fn disabled_split_shows_normal_background() {
+    let emulator = run(0b0001_0000);
+    assert_ne!(pixel(&emulator, 4, 100), WHITE);
+    assert_ne!(pixel(&emulator, 200, 100), WHITE);
+}
+
+// Runs an MMC5 ROM with zeroed PRG ROM. The normal background only uses the blank first 4KiB of
+// CHR ROM, while the split bank's tile 1 is solid.
+fn run(split_mode: u8) -> TestEmulator {
+    let mut rom = TestRom::ines(5, 32)
+        .with_prg_rom_filled(0x00)
+        .with_program(PROGRAM)
+        .with_bytes(SPLIT_MODE_ADDRESS, &[split_mode]);
+    // Tile 1 of the second 4KiB bank: every pixel uses color 1.
+    rom.chr_rom[0x1010..0x1018].fill(0xFF);
+
+    let mut emulator = rom.load();
+    emulator.step_frames(6);
+    emulator
+}
+
+fn pixel(emulator: &TestEmulator, column: u8, row: u8) -> (u8, u8, u8) {
+    let (rgb, _) = emulator.nes().frame().pixel(PixelColumn::new(column), PixelRow::try_from_u8(row).unwrap());
+    (rgb.red(), rgb.green(), rgb.blue())
+}
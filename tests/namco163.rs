@@ -0,0 +1,120 @@
+extern crate reznez;
+
+mod common;
+
+use common::{TestEmulator, TestRom};
+
+// Fills internal RAM through the auto-incrementing data port, then reads the first three bytes back.
+const RAM_PORT_PROGRAM: &[u8] = &[
+    0xA9, 0x80,       // LDA #$80 (Address 0, auto-increment)
+    0x8D, 0x00, 0xF8, // STA $F800
+    0xA2, 0x00,       // LDX #$00
+    0xBD, 0x00, 0xE1, // LDA $E100,X
+    0x8D, 0x00, 0x48, // STA $4800
+    0xE8,             // INX
+    0xE0, 0x80,       // CPX #$80
+    0xD0, 0xF5,       // BNE (LDA $E100,X)
+    0xA9, 0x80,       // LDA #$80
+    0x8D, 0x00, 0xF8, // STA $F800
+    0xAD, 0x00, 0x48, // LDA $4800
+    0x8D, 0x00, 0x02, // STA $0200
+    0xAD, 0x00, 0x48, // LDA $4800
+    0x8D, 0x01, 0x02, // STA $0201
+    0xAD, 0x00, 0x48, // LDA $4800
+    0x8D, 0x02, 0x02, // STA $0202
+];
+
+#[test]
+fn internal_ram_port_auto_increments() {
+    let mut emulator = load(3, 0x00, 0x0F);
+    emulator.run();
+    assert_eq!(emulator.peek(0x0200), 0xFF);
+    assert_eq!(emulator.peek(0x0201), 0xFF);
+    assert_eq!(emulator.peek(0x0202), 0xFF);
+}
+
+#[test]
+fn wavetable_channel_is_audible() {
+    let mut emulator = load(3, 0x00, 0x0F);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.1);
+}
+
+#[test]
+fn deprecated_battery_submapper_is_audible() {
+    let mut emulator = load(1, 0x00, 0x0F);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.1);
+}
+
+#[test]
+fn more_channels_are_quieter() {
+    let mut emulator = load(3, 0x00, 0x0F);
+    emulator.run();
+    let one_channel_amplitude = emulator.amplitude();
+
+    // Enable all eight channels, leaving the extra ones silent.
+    let mut emulator = load(3, 0x00, 0x7F);
+    emulator.run();
+    let eight_channel_amplitude = emulator.amplitude();
+    assert!(eight_channel_amplitude > 0.01);
+    assert!(eight_channel_amplitude < one_channel_amplitude / 4.0);
+}
+
+#[test]
+fn sound_disable_bit_silences_audio() {
+    let mut emulator = load(3, 0x40, 0x0F);
+    emulator.run();
+    assert!(emulator.amplitude() < 0.001);
+}
+
+#[test]
+fn internal_ram_is_included_in_save_states() {
+    let mut emulator = load(3, 0x00, 0x0F);
+    emulator.run();
+    let state = emulator.nes().save_state().unwrap();
+
+    // The program hasn't run yet, so internal RAM is still empty.
+    let mut loaded_emulator = load(3, 0x00, 0x0F);
+    loaded_emulator.nes_mut().load_state(&state).unwrap();
+    assert_eq!(emulator.nes().save_state().unwrap(), loaded_emulator.nes().save_state().unwrap());
+}
+
+#[test]
+fn internal_ram_is_included_in_save_ram() {
+    let mut emulator = load(3, 0x00, 0x0F);
+    emulator.run();
+    // There is no PRG RAM, so the save RAM is only the internal RAM.
+    let save_ram = emulator.nes().save_ram();
+    assert_eq!(save_ram.len(), 128);
+    assert_eq!(save_ram[0..4], [0xFF; 4]);
+
+    let mut loaded_emulator = load(3, 0x00, 0x0F);
+    loaded_emulator.nes_mut().load_save_ram(&save_ram).unwrap();
+    assert_eq!(loaded_emulator.nes().save_ram(), save_ram);
+    assert!(loaded_emulator.nes_mut().load_save_ram(&save_ram[1..]).is_err());
+}
+
+// Loads a Namco 163 ROM with a battery. The program loads internal RAM with a 16-sample square wave
+// and channel 7 playing it, then writes the bank register value to $E000.
+fn load(submapper_number: u8, bank_register_value: u8, volume_register_value: u8) -> TestEmulator {
+    let mut program = RAM_PORT_PROGRAM.to_vec();
+    program.extend_from_slice(&[
+        0xA9, bank_register_value, // LDA #bank_register_value
+        0x8D, 0x00, 0xE0,          // STA $E000
+    ]);
+
+    // Waveform: eight samples of 15 then eight samples of 0.
+    let mut internal_ram = [0; 128];
+    internal_ram[0..4].copy_from_slice(&[0xFF; 4]);
+    // Channel 7: frequency $01000, length 16, wave address 0. The volume register also holds the channel count.
+    internal_ram[0x7A] = 0x10;
+    internal_ram[0x7C] = 0xF0;
+    internal_ram[0x7F] = volume_register_value;
+
+    TestRom::nes2(19, submapper_number, 32)
+        .with_battery()
+        .with_program(&program)
+        .with_bytes(0xE100, &internal_ram)
+        .load()
+}
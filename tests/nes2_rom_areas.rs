@@ -0,0 +1,63 @@
+extern crate reznez;
+
+use std::fs;
+use std::path::PathBuf;
+
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::nes::Nes;
+
+const MISCELLANEOUS_ROM: &[u8] = b"Option ROM contents";
+
+#[test]
+fn exponent_multiplier_rom_sizes() {
+    // 2^14 * 1 bytes of PRG ROM, 2^12 * 3 bytes of CHR ROM.
+    let path = write_rom("exponent", 0x0F, 0x0F, 0b0011_1000, 0b0011_0001, 12 * 1024, &[]);
+    let cartridge = Nes::load_cartridge(&path).unwrap();
+    assert_eq!(cartridge.prg_rom_size(), 16 * 1024);
+    assert_eq!(cartridge.chr_rom_size(), 12 * 1024);
+}
+
+#[test]
+fn too_large_exponent_is_rejected() {
+    let path = write_rom("large_exponent", 0x0F, 0x00, 0b1111_1100, 1, 8 * 1024, &[]);
+    assert!(Nes::load_cartridge(&path).is_err());
+}
+
+#[test]
+fn miscellaneous_rom_is_loaded() {
+    let path = write_rom("misc", 0x00, 0x00, 1, 1, 8 * 1024, MISCELLANEOUS_ROM);
+    let cartridge = Nes::load_cartridge(&path).unwrap();
+    assert_eq!(cartridge.miscellaneous_rom().as_slice(), MISCELLANEOUS_ROM);
+
+    let opt = Opt { gui: GuiType::NoGui, disable_audio: true, prevent_saving: true, ..Opt::new(Some(path)) };
+    let nes = Nes::new(&HeaderDb::load(), &Config::new(&opt), &cartridge).unwrap();
+    assert_eq!(nes.resolved_metadata().miscellaneous_rom_count, 1);
+    assert_eq!(nes.bus().miscellaneous_rom.as_slice(), MISCELLANEOUS_ROM);
+}
+
+// Writes an NES 2.0 NROM image. The PRG ROM is filled with NOPs, except for a reset vector pointing at $8000.
+fn write_rom(
+    name: &str,
+    prg_msb: u8,
+    chr_msb: u8,
+    prg_lsb: u8,
+    chr_lsb: u8,
+    chr_rom_size: usize,
+    miscellaneous_rom: &[u8],
+) -> PathBuf {
+    let miscellaneous_rom_count = u8::from(!miscellaneous_rom.is_empty());
+    let mut rom = vec![b'N', b'E', b'S', 0x1A, prg_lsb, chr_lsb, 0, 0b0000_1000, 0, chr_msb << 4 | prg_msb, 0, 0, 0, 0,
+        miscellaneous_rom_count, 0];
+    let mut prg_rom = vec![0xEA; 16 * 1024];
+    prg_rom[0x3FFC..0x3FFE].copy_from_slice(&[0x00, 0x80]);
+    rom.extend_from_slice(&prg_rom);
+    rom.extend_from_slice(&vec![0; chr_rom_size]);
+    rom.extend_from_slice(miscellaneous_rom);
+
+    let directory = std::env::temp_dir().join(format!("reznez_nes2_test_{name}_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("test.nes");
+    fs::write(&path, rom).unwrap();
+    path
+}
@@ -15,7 +15,7 @@ use reznez::cpu::status::Status;
 use reznez::memory::cpu::cpu_address::CpuAddress;
 use reznez::bus::AddressBusType;
 use reznez::nes::Nes;
-use reznez::ppu::ppu_clock::{LastCycle, MAX_SCANLINE, PpuClock};
+use reznez::ppu::ppu_clock::{LastCycle, NTSC_MAX_SCANLINE, PpuClock};
 use reznez::ppu::render::frame_rate::TargetFrameRate;
 use reznez::logging::logger;
 use reznez::logging::logger::Logger;
@@ -54,7 +54,7 @@ fn nestest() {
     // Nestest starts the first instruction a cycle early compared to the NES Manual and Mesen.
     config.starting_cpu_cycle = -1;
     // Nestest starts the first instruction on cycle 0, but PPU stuff happens before that.
-    config.ppu_clock = PpuClock::starting_at(-1, MAX_SCANLINE, LastCycle::Normal as u16 - 22);
+    config.ppu_clock = PpuClock::starting_at(-1, NTSC_MAX_SCANLINE, LastCycle::Normal as u16 - 22);
 
     let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
     let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
@@ -0,0 +1,118 @@
+extern crate reznez;
+
+use std::fs;
+use std::path::PathBuf;
+
+use reznez::bus::AddressBusType;
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::memory::cpu::cpu_address::CpuAddress;
+use reznez::nes::Nes;
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+
+const INIT_TRACK: u16 = 0x0300;
+const INIT_CALL_COUNT: u16 = 0x0301;
+const PLAY_CALL_COUNT: u16 = 0x0302;
+
+// INIT records the track that it was passed and how many times it has been called. PLAY counts its calls.
+const PROGRAM: &[u8] = &[
+    0x8D, 0x00, 0x03, // 8000: STA $0300
+    0xEE, 0x01, 0x03, // 8003: INC $0301
+    0x60,             // 8006: RTS
+    0xEE, 0x02, 0x03, // 8007: INC $0302
+    0x60,             // 800A: RTS
+];
+
+#[test]
+fn init_receives_starting_track_then_play_runs_every_frame() {
+    let mut nes = load_nes("start");
+    assert_eq!(nes.nsf_player().unwrap().title(), "Test Tune");
+    assert_eq!(nes.nsf_player().unwrap().track_count(), 3);
+
+    for _ in 0..60 {
+        nes.step_frame();
+    }
+
+    assert_eq!(peek(&nes, INIT_TRACK), 1);
+    assert_eq!(peek(&nes, INIT_CALL_COUNT), 1);
+    let play_call_count = peek(&nes, PLAY_CALL_COUNT);
+    assert!((58..=60).contains(&play_call_count), "PLAY was called {play_call_count} times in 60 frames.");
+}
+
+#[test]
+fn selecting_a_track_restarts_the_tune() {
+    let mut nes = load_nes("select");
+    for _ in 0..30 {
+        nes.step_frame();
+    }
+
+    nes.play_nsf_track(2);
+    for _ in 0..10 {
+        nes.step_frame();
+    }
+
+    assert_eq!(nes.nsf_player().unwrap().track(), 2);
+    assert_eq!(peek(&nes, INIT_TRACK), 2);
+    // RAM was cleared before INIT was called again.
+    assert_eq!(peek(&nes, INIT_CALL_COUNT), 1);
+    assert!(peek(&nes, PLAY_CALL_COUNT) <= 10);
+}
+
+#[test]
+fn stopping_prevents_init_and_play() {
+    let mut nes = load_nes("stop");
+    for _ in 0..10 {
+        nes.step_frame();
+    }
+
+    nes.stop_nsf();
+    for _ in 0..10 {
+        nes.step_frame();
+    }
+
+    assert!(!nes.nsf_player().unwrap().is_playing());
+    assert_eq!(peek(&nes, INIT_CALL_COUNT), 0);
+    assert_eq!(peek(&nes, PLAY_CALL_COUNT), 0);
+}
+
+fn load_nes(name: &str) -> Nes {
+    let directory = std::env::temp_dir().join(format!("reznez_nsf_test_{name}_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let nsf_path = write_nsf(&directory);
+
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        ..Opt::new(Some(nsf_path))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+    nes
+}
+
+// Writes an NTSC NSF with three tracks that starts on the second one.
+fn write_nsf(directory: &std::path::Path) -> PathBuf {
+    let mut nsf = vec![0; 0x80];
+    nsf[..5].copy_from_slice(b"NESM\x1A");
+    nsf[0x05] = 1;
+    nsf[0x06] = 3;
+    nsf[0x07] = 2;
+    // Load, init, and play addresses.
+    nsf[0x08..0x0E].copy_from_slice(&[0x00, 0x80, 0x00, 0x80, 0x07, 0x80]);
+    nsf[0x0E..0x0E + 9].copy_from_slice(b"Test Tune");
+    // 60Hz.
+    nsf[0x6E..0x70].copy_from_slice(&16639u16.to_le_bytes());
+    nsf.extend_from_slice(PROGRAM);
+
+    let path = directory.join("test.nsf");
+    fs::write(&path, nsf).unwrap();
+    path
+}
+
+fn peek(nes: &Nes, addr: u16) -> u8 {
+    nes.bus().cpu_peek(nes.mapper(), AddressBusType::Cpu, CpuAddress::new(addr))
+}
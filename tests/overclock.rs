@@ -0,0 +1,103 @@
+extern crate reznez;
+
+use std::fs;
+use std::path::PathBuf;
+
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::nes::Nes;
+use reznez::ppu::ppu_clock::{Overclock, OverclockPosition};
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+
+const EXTRA_SCANLINES: u16 = 30;
+const PPU_CYCLES_PER_SCANLINE: i64 = 341;
+
+#[test]
+fn extra_scanlines_add_cpu_time_but_not_apu_time() {
+    for position in [OverclockPosition::PostRender, OverclockPosition::Vblank] {
+        let (normal_cpu_cycles, normal_apu_cycles) = cycles_per_frame("normal", 0, position);
+        let (overclocked_cpu_cycles, overclocked_apu_cycles) =
+            cycles_per_frame(&format!("{position:?}"), EXTRA_SCANLINES, position);
+
+        let extra_cpu_cycles = overclocked_cpu_cycles - normal_cpu_cycles;
+        let expected_extra_cpu_cycles = i64::from(EXTRA_SCANLINES) * PPU_CYCLES_PER_SCANLINE / 3;
+        assert!((extra_cpu_cycles - expected_extra_cpu_cycles).abs() <= 1, "{position:?}: {extra_cpu_cycles}");
+        assert_eq!(overclocked_apu_cycles, normal_apu_cycles);
+    }
+}
+
+#[test]
+fn overclock_can_be_changed_while_running() {
+    let (normal_cpu_cycles, _) = cycles_per_frame("runtime_normal", 0, OverclockPosition::PostRender);
+
+    let mut nes = load_nes("runtime", 0, OverclockPosition::PostRender);
+    nes.step_frame();
+    nes.step_frame();
+    // Partway through the frame, before the vblank scanlines.
+    let start_cpu_cycle = nes.bus().cpu_cycle();
+    while nes.bus().ppu_clock().scanline() < 100 {
+        nes.step();
+    }
+
+    nes.set_overclock(Overclock { extra_scanlines: EXTRA_SCANLINES, position: OverclockPosition::Vblank });
+    assert_eq!(nes.overclock().extra_scanlines, EXTRA_SCANLINES);
+    nes.step_frame();
+    // The change doesn't affect the frame that was already in progress.
+    let cpu_cycles = nes.bus().cpu_cycle() - start_cpu_cycle;
+    assert!((cpu_cycles - normal_cpu_cycles).abs() <= 1, "{cpu_cycles}");
+
+    let start_cpu_cycle = nes.bus().cpu_cycle();
+    nes.step_frame();
+
+    let extra_cpu_cycles = nes.bus().cpu_cycle() - start_cpu_cycle - normal_cpu_cycles;
+    let expected_extra_cpu_cycles = i64::from(EXTRA_SCANLINES) * PPU_CYCLES_PER_SCANLINE / 3;
+    assert!((extra_cpu_cycles - expected_extra_cpu_cycles).abs() <= 1, "{extra_cpu_cycles}");
+}
+
+// Rendering stays disabled, so no frame has its last cycle skipped.
+fn cycles_per_frame(name: &str, extra_scanlines: u16, position: OverclockPosition) -> (i64, u64) {
+    let mut nes = load_nes(name, extra_scanlines, position);
+    nes.step_frame();
+    nes.step_frame();
+    let start_cpu_cycle = nes.bus().cpu_cycle();
+    let start_apu_cycle = nes.bus().apu_clock().raw_apu_cycle();
+    nes.step_frame();
+    (nes.bus().cpu_cycle() - start_cpu_cycle, nes.bus().apu_clock().raw_apu_cycle() - start_apu_cycle)
+}
+
+fn load_nes(name: &str, overclock_scanlines: u16, overclock_position: OverclockPosition) -> Nes {
+    let directory = std::env::temp_dir().join(format!("reznez_overclock_test_{name}_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let rom_path = write_rom(&directory);
+
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        overclock_scanlines,
+        overclock_position,
+        ..Opt::new(Some(rom_path))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+    nes
+}
+
+// Writes an NROM image that loops forever with rendering disabled.
+fn write_rom(directory: &std::path::Path) -> PathBuf {
+    let mut rom = vec![b'N', b'E', b'S', 0x1A, 1, 1, 0, 0];
+    rom.resize(16, 0);
+    let mut prg_rom = vec![0xEA; 16 * 1024];
+    // JMP $C000
+    prg_rom[..3].copy_from_slice(&[0x4C, 0x00, 0xC0]);
+    prg_rom[0x3FFC..0x3FFE].copy_from_slice(&[0x00, 0xC0]);
+    rom.extend_from_slice(&prg_rom);
+    rom.extend_from_slice(&[0; 8 * 1024]);
+
+    let path = directory.join("overclock.nes");
+    fs::write(&path, rom).unwrap();
+    path
+}
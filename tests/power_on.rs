@@ -0,0 +1,85 @@
+extern crate reznez;
+
+use std::fs;
+use std::path::PathBuf;
+
+use reznez::bus::AddressBusType;
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::memory::cpu::cpu_address::CpuAddress;
+use reznez::nes::Nes;
+use reznez::power_on::PowerOnState;
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+
+#[test]
+fn all_ff_fills_internal_and_work_ram() {
+    let nes = load_nes("ff", Some(PowerOnState::AllFf), None);
+    assert_eq!(internal_ram(&nes), vec![0xFF; 0x800]);
+    assert_eq!(peek(&nes, 0x6000), 0xFF);
+    assert_eq!(peek(&nes, 0x7FFF), 0xFF);
+}
+
+#[test]
+fn pattern_alternates_every_four_bytes() {
+    let nes = load_nes("pattern", Some(PowerOnState::Pattern), None);
+    assert_eq!(internal_ram(&nes)[..12], [0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0]);
+}
+
+#[test]
+fn random_state_is_reproducible_from_seed() {
+    let first = internal_ram(&load_nes("seed_a", Some(PowerOnState::Random), Some(1234)));
+    let second = internal_ram(&load_nes("seed_b", Some(PowerOnState::Random), Some(1234)));
+    let other_seed = internal_ram(&load_nes("seed_c", Some(PowerOnState::Random), Some(4321)));
+    assert_eq!(first, second);
+    assert_ne!(first, other_seed);
+    assert!(first.iter().any(|&value| value != first[0]));
+}
+
+#[test]
+fn default_leaves_internal_ram_zeroed() {
+    let nes = load_nes("default", None, None);
+    assert_eq!(internal_ram(&nes), vec![0; 0x800]);
+}
+
+fn load_nes(name: &str, power_on_state: Option<PowerOnState>, power_on_seed: Option<u64>) -> Nes {
+    let directory = std::env::temp_dir().join(format!("reznez_power_on_test_{name}_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let rom_path = write_rom(&directory);
+
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        power_on_state,
+        power_on_seed,
+        ..Opt::new(Some(rom_path))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+    nes
+}
+
+// Writes an NES 2.0 MMC1 ROM with 16KiB PRG ROM, 8KiB PRG RAM, and 8KiB CHR ROM.
+fn write_rom(directory: &std::path::Path) -> PathBuf {
+    let mut rom = vec![b'N', b'E', b'S', 0x1A, 1, 1, 0b0001_0000, 0b0000_1000, 0, 0, 0x07];
+    rom.resize(16, 0);
+    let mut prg_rom = vec![0xEA; 16 * 1024];
+    prg_rom[0x3FFC..0x3FFE].copy_from_slice(&[0x00, 0xC0]);
+    rom.extend_from_slice(&prg_rom);
+    rom.extend_from_slice(&[0; 8 * 1024]);
+
+    let path = directory.join("power_on.nes");
+    fs::write(&path, rom).unwrap();
+    path
+}
+
+fn internal_ram(nes: &Nes) -> Vec<u8> {
+    (0..0x800).map(|addr| peek(nes, addr)).collect()
+}
+
+fn peek(nes: &Nes, addr: u16) -> u8 {
+    nes.bus().cpu_peek(nes.mapper(), AddressBusType::Cpu, CpuAddress::new(addr))
+}
@@ -0,0 +1,87 @@
+extern crate reznez;
+
+use std::path::PathBuf;
+
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::nes::Nes;
+use reznez::ppu::palette::color::Color;
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+use reznez::region::Region;
+
+const ROM_PATH: &str = "tests/roms/mmc3_test/4-scanline_timing.nes";
+const FRAME_COUNT: i64 = 10;
+
+#[test]
+fn ntsc_frame_length() {
+    let (nes, cpu_cycles_per_frame) = run_frames(Region::Ntsc);
+    assert_eq!(nes.region(), Region::Ntsc);
+    // 341 * 262 PPU cycles, minus one on odd frames when rendering, at 3 PPU cycles per CPU cycle.
+    assert!((cpu_cycles_per_frame - 29780.5).abs() <= 0.5, "{cpu_cycles_per_frame}");
+}
+
+#[test]
+fn pal_frame_length() {
+    let (nes, cpu_cycles_per_frame) = run_frames(Region::Pal);
+    assert_eq!(nes.region(), Region::Pal);
+    // 341 * 312 PPU cycles, at 3.2 PPU cycles per CPU cycle.
+    assert!((cpu_cycles_per_frame - 33247.5).abs() <= 0.5, "{cpu_cycles_per_frame}");
+}
+
+#[test]
+fn dendy_frame_length() {
+    let (nes, cpu_cycles_per_frame) = run_frames(Region::Dendy);
+    assert_eq!(nes.region(), Region::Dendy);
+    // 341 * 312 PPU cycles, at 3 PPU cycles per CPU cycle.
+    assert!((cpu_cycles_per_frame - 35464.0).abs() <= 0.5, "{cpu_cycles_per_frame}");
+}
+
+#[test]
+fn save_state_rejects_other_region() {
+    let (ntsc, _) = run_frames(Region::Ntsc);
+    let (mut pal, _) = run_frames(Region::Pal);
+    let state = ntsc.save_state().unwrap();
+    assert!(pal.load_state(&state).is_err());
+}
+
+#[test]
+fn pal_consoles_use_pal_palette() {
+    let ntsc_blue = rgb(&run_frames(Region::Ntsc).0, 0x21);
+    let pal_blue = rgb(&run_frames(Region::Pal).0, 0x21);
+    let dendy_blue = rgb(&run_frames(Region::Dendy).0, 0x21);
+    assert_ne!(pal_blue, ntsc_blue);
+    assert_eq!(dendy_blue, pal_blue);
+
+    // Grays have no hue, so they are the same for every region.
+    assert_eq!(rgb(&run_frames(Region::Pal).0, 0x10), rgb(&run_frames(Region::Ntsc).0, 0x10));
+}
+
+fn rgb(nes: &Nes, color: u8) -> (u8, u8, u8) {
+    let rgb = nes.bus().system_palette.emphasis_section(0).lookup_rgb(Color::from(color));
+    (rgb.red(), rgb.green(), rgb.blue())
+}
+
+fn run_frames(region: Region) -> (Nes, f64) {
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        region: Some(region),
+        ..Opt::new(Some(PathBuf::from(ROM_PATH)))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+
+    // The first frame starts partway through, so it isn't measured.
+    nes.step_frame();
+    let start_cycle = nes.bus().master_clock.cpu_cycle();
+    for _ in 0..FRAME_COUNT {
+        nes.step_frame();
+    }
+
+    let cpu_cycles = nes.bus().master_clock.cpu_cycle() - start_cycle;
+    (nes, cpu_cycles as f64 / FRAME_COUNT as f64)
+}
@@ -0,0 +1,64 @@
+extern crate reznez;
+
+mod common;
+
+use common::{TestEmulator, TestRom};
+use reznez::apu::sample_rate::SampleRate;
+
+// 60 NTSC frames.
+const MEASURED_FRAME_COUNT: u32 = 60;
+const MEASURED_SECONDS: f64 = MEASURED_FRAME_COUNT as f64 * 29780.5 / 1_789_773.0;
+
+#[test]
+fn sample_count_matches_output_rate() {
+    for sample_rate in [SampleRate::Hz44100, SampleRate::Hz48000, SampleRate::Hz96000] {
+        let mut emulator = load(0x40, sample_rate);
+        emulator.run();
+        emulator.take_samples();
+        emulator.step_frames(MEASURED_FRAME_COUNT);
+
+        let sample_count = emulator.take_samples().len() as f64;
+        let expected_count = f64::from(sample_rate.hz()) * MEASURED_SECONDS;
+        assert!((sample_count - expected_count).abs() < 2.0, "{sample_rate}: {sample_count} samples, expected {expected_count}");
+    }
+}
+
+#[test]
+fn audible_triangle_is_output() {
+    // About 440Hz.
+    let mut emulator = load(0xFD, SampleRate::Hz44100);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.1);
+}
+
+#[test]
+fn ultrasonic_triangle_does_not_alias() {
+    // About 28kHz, which is above the Nyquist frequency at 44.1kHz and 48kHz, but not at 96kHz.
+    for sample_rate in [SampleRate::Hz44100, SampleRate::Hz48000] {
+        let mut emulator = load(0x01, sample_rate);
+        emulator.run();
+        let amplitude = emulator.amplitude();
+        assert!(amplitude < 0.005, "{sample_rate}: {amplitude}");
+    }
+
+    let mut emulator = load(0x01, SampleRate::Hz96000);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.05);
+}
+
+// Loads an NROM ROM that plays the triangle channel continuously at the specified timer period.
+fn load(triangle_period: u8, sample_rate: SampleRate) -> TestEmulator {
+    let program = [
+        0xA9, 0x04,            // LDA #$04
+        0x8D, 0x15, 0x40,      // STA $4015
+        0xA9, 0xFF,            // LDA #$FF
+        0x8D, 0x08, 0x40,      // STA $4008
+        0xA9, triangle_period, // LDA #period
+        0x8D, 0x0A, 0x40,      // STA $400A
+        0xA9, 0x08,            // LDA #$08
+        0x8D, 0x0B, 0x40,      // STA $400B
+    ];
+
+    let rom = TestRom::ines(0, 32).with_program(&program);
+    TestEmulator::new(rom.builder().sample_rate(sample_rate))
+}
@@ -0,0 +1,121 @@
+extern crate reznez;
+
+use std::path::PathBuf;
+
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, Event, GuiType, Opt};
+use reznez::gui::gui::{execute_frame, Events};
+use reznez::gui::rewind::Rewind;
+use reznez::nes::Nes;
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+use reznez::util::hash_util::calculate_hash;
+
+const ROM_PATH: &str = "tests/roms/mmc3_test/4-scanline_timing.nes";
+const FRAME_COUNT: usize = 40;
+
+#[test]
+fn step_back_reproduces_earlier_frames() {
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        rewind_snapshot_interval: 7,
+        ..Opt::new(Some(PathBuf::from(ROM_PATH)))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+
+    let mut rewind = Rewind::new(&config);
+    let mut hashes = Vec::new();
+    for _ in 0..FRAME_COUNT {
+        rewind.record_frame(&nes, &Events::none());
+        nes.step_frame();
+        hashes.push(calculate_hash(&nes.frame().to_ppm()));
+    }
+
+    let final_state = nes.save_state().unwrap();
+
+    // Rewind into the middle of the oldest snapshot interval, checking every frame along the way.
+    for expected_hash in hashes[3..FRAME_COUNT - 1].iter().rev() {
+        assert!(rewind.step_back(&mut nes));
+        assert_eq!(calculate_hash(&nes.frame().to_ppm()), *expected_hash);
+    }
+
+    // Running forward again must end up in the same state as the original run.
+    for _ in 4..FRAME_COUNT {
+        rewind.record_frame(&nes, &Events::none());
+        nes.step_frame();
+    }
+
+    assert_eq!(nes.save_state().unwrap(), final_state);
+}
+
+#[test]
+fn step_back_stops_at_oldest_snapshot() {
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        rewind_snapshot_interval: 2,
+        rewind_max_snapshot_count: 3,
+        ..Opt::new(Some(PathBuf::from(ROM_PATH)))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+
+    let mut rewind = Rewind::new(&config);
+    for _ in 0..10 {
+        rewind.record_frame(&nes, &Events::none());
+        nes.step_frame();
+    }
+
+    // The oldest snapshot is from the start of frame 4, so the frames starting at 9 through 4 can be reached.
+    let mut steps = 0;
+    while rewind.step_back(&mut nes) {
+        steps += 1;
+    }
+
+    assert_eq!(steps, 6);
+}
+
+#[test]
+fn step_back_replays_scheduled_reset() {
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        rewind_snapshot_interval: 7,
+        ..Opt::new(Some(PathBuf::from(ROM_PATH)))
+    };
+    let mut config = Config::new(&opt);
+    // Resets happen on both the press and the release, so the release is scheduled after the end of the run.
+    config.add_scheduled_button_press_and_release(12, 100, Event::Reset);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+
+    let mut rewind = Rewind::new(&config);
+    for _ in 0..FRAME_COUNT {
+        execute_frame(&mut nes, &config, &mut rewind, Events::none(), |_, _| {});
+    }
+
+    let final_state = nes.save_state().unwrap();
+
+    // Stepping back to frame 13 re-simulates forward from the snapshot at frame 7, through the reset.
+    while nes.bus().ppu_clock().frame() > 13 {
+        assert!(rewind.step_back(&mut nes));
+    }
+
+    for _ in 13..FRAME_COUNT {
+        execute_frame(&mut nes, &config, &mut rewind, Events::none(), |_, _| {});
+    }
+
+    assert_eq!(nes.save_state().unwrap(), final_state);
+}
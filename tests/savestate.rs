@@ -0,0 +1,102 @@
+extern crate reznez;
+
+use std::path::PathBuf;
+
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::master_clock::CpuPpuAlignment;
+use reznez::nes::Nes;
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+use reznez::util::hash_util::calculate_hash;
+
+const FRAMES_BEFORE_SAVE: usize = 30;
+const FRAMES_AFTER_SAVE: usize = 60;
+
+#[test]
+fn save_then_load_matches_uninterrupted_run() {
+    let header_db = HeaderDb::load();
+    for rom_path in [
+        // NROM
+        "tests/roms/palette_ram.nes",
+        // MMC1
+        "tests/roms/instr_misc.nes",
+        // MMC3 with scanline IRQs
+        "tests/roms/mmc3_test/4-scanline_timing.nes",
+        // FME-7 with an IRQ counter and Work RAM
+        "tests/roms/holydiverbatman/M69_P128K_C64K_W8K.nes",
+        // MMC5
+        "tests/roms/exram/mmc5exram.nes",
+    ] {
+        // Save at the end of a frame and also in the middle of one.
+        for extra_cycles in [0, 12345] {
+            let mut nes = new_nes(&header_db, rom_path);
+            for _ in 0..FRAMES_BEFORE_SAVE {
+                nes.step_frame();
+            }
+
+            for _ in 0..extra_cycles {
+                nes.step();
+            }
+
+            let state = nes.save_state().unwrap();
+            let expected_hashes = frame_hashes(&mut nes);
+
+            let mut loaded_nes = new_nes(&header_db, rom_path);
+            loaded_nes.step_frame();
+            loaded_nes.load_state(&state).unwrap();
+            let actual_hashes = frame_hashes(&mut loaded_nes);
+
+            assert_eq!(expected_hashes, actual_hashes, "Frames differ after loading state for {rom_path} (extra cycles: {extra_cycles}).");
+            assert_eq!(nes.save_state().unwrap(), loaded_nes.save_state().unwrap(),
+                "Final states differ for {rom_path} (extra cycles: {extra_cycles}).");
+        }
+    }
+}
+
+#[test]
+fn load_rejects_state_from_different_rom() {
+    let header_db = HeaderDb::load();
+    let state = new_nes(&header_db, "tests/roms/palette_ram.nes").save_state().unwrap();
+    let mut nes = new_nes(&header_db, "tests/roms/instr_misc.nes");
+    assert!(nes.load_state(&state).is_err());
+    assert!(nes.load_state(&[]).is_err());
+}
+
+#[test]
+fn load_rejects_state_from_different_alignment() {
+    let header_db = HeaderDb::load();
+    let rom_path = "tests/roms/palette_ram.nes";
+    let state = new_nes(&header_db, rom_path).save_state().unwrap();
+    let mut nes = new_nes_with_alignment(&header_db, rom_path, CpuPpuAlignment::ALL[2]);
+    assert!(nes.load_state(&state).is_err());
+}
+
+fn new_nes(header_db: &HeaderDb, rom_path: &str) -> Nes {
+    new_nes_with_alignment(header_db, rom_path, CpuPpuAlignment::default())
+}
+
+fn new_nes_with_alignment(header_db: &HeaderDb, rom_path: &str, cpu_ppu_alignment: CpuPpuAlignment) -> Nes {
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        cpu_ppu_alignment,
+        ..Opt::new(Some(PathBuf::from(rom_path)))
+    };
+
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(header_db, &config, &cartridge).unwrap();
+    nes.mute();
+    nes
+}
+
+fn frame_hashes(nes: &mut Nes) -> Vec<u64> {
+    (0..FRAMES_AFTER_SAVE)
+        .map(|_| {
+            nes.step_frame();
+            calculate_hash(&nes.frame().to_ppm())
+        })
+        .collect()
+}
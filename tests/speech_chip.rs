@@ -0,0 +1,99 @@
+extern crate reznez;
+
+mod common;
+
+use common::{TestEmulator, TestRom};
+
+const CLIP_NUMBER: u8 = 5;
+const CLIP_SAMPLE_RATE: u32 = 8000;
+// Half a second, so about 30 frames.
+const CLIP_SAMPLE_COUNT: usize = 4000;
+
+#[test]
+fn supplied_clip_plays_until_finished() {
+    // Mapper 86: /ST is bit 5 and /RESET is bit 4 of $7000, the clip number is in the low bits.
+    let mut emulator = load(&mapper086_program(&[0x20 | CLIP_NUMBER, CLIP_NUMBER]), 86);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.05);
+
+    emulator.step_frames(30);
+    emulator.take_samples();
+    emulator.step_frames(1);
+    assert!(emulator.amplitude() < 0.001);
+}
+
+#[test]
+fn reset_stops_playback() {
+    let mut emulator = load(&mapper086_program(&[0x20 | CLIP_NUMBER, 0x10 | CLIP_NUMBER]), 86);
+    emulator.run();
+    assert!(emulator.amplitude() < 0.001);
+}
+
+#[test]
+fn missing_clip_is_silent() {
+    let mut emulator = load(&mapper086_program(&[0x20 | (CLIP_NUMBER + 1), CLIP_NUMBER + 1]), 86);
+    emulator.run();
+    assert!(emulator.amplitude() < 0.001);
+}
+
+#[test]
+fn jf17_takes_clip_number_from_address() {
+    let mut emulator = load(&jf17_program(), 72);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.05);
+}
+
+// Writes each value to $7000, then loops forever.
+fn mapper086_program(values: &[u8]) -> Vec<u8> {
+    let mut program = Vec::new();
+    for &value in values {
+        program.extend_from_slice(&[
+            0xA9, value,      // LDA #value
+            0x8D, 0x00, 0x70, // STA $7000
+        ]);
+    }
+
+    program
+}
+
+// Starts the clip by writing to an address whose low bits are the clip number. The PRG ROM is
+// filled with 0xFF there, so the write isn't affected by bus conflicts.
+fn jf17_program() -> Vec<u8> {
+    vec![
+        0xA9, 0x20,                     // LDA #$20
+        0x8D, CLIP_NUMBER, 0x80,        // STA $80xx
+        0xA9, 0x00,                     // LDA #$00
+        0x8D, CLIP_NUMBER, 0x80,        // STA $80xx
+    ]
+}
+
+// Loads a ROM with PRG ROM filled with 0xFF, with only the one clip supplied.
+fn load(program: &[u8], mapper_number: u8) -> TestEmulator {
+    let rom = TestRom::ines(mapper_number, 32)
+        .with_prg_rom_filled(0xFF)
+        .with_program(program);
+    TestEmulator::new(rom.builder().speech_clip(CLIP_NUMBER, clip()))
+}
+
+// A 16-bit mono square wave at 500Hz and half of full scale.
+fn clip() -> Vec<u8> {
+    let data: Vec<u8> = (0..CLIP_SAMPLE_COUNT)
+        .flat_map(|i| if i % 16 < 8 { 0x4000i16 } else { -0x4000i16 }.to_le_bytes())
+        .collect();
+
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&CLIP_SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(2 * CLIP_SAMPLE_RATE).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    wav.extend_from_slice(&data);
+    wav
+}
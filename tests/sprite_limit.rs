@@ -0,0 +1,152 @@
+extern crate reznez;
+
+use std::fs;
+use std::path::PathBuf;
+
+use reznez::bus::AddressBusType;
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::memory::cpu::cpu_address::CpuAddress;
+use reznez::nes::Nes;
+use reznez::ppu::pixel_index::{PixelColumn, PixelRow};
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+
+// Fills OAM from the table at $9000, enables sprite rendering, then stores PPUSTATUS to $0200 at every vblank.
+const PROGRAM: &[u8] = &[
+    0x78,             // 8000: SEI
+    0xA2, 0xFF,       // 8001: LDX #$FF
+    0x9A,             // 8003: TXS
+    0x2C, 0x02, 0x20, // 8004: BIT $2002
+    0x10, 0xFB,       // 8007: BPL $8004
+    0x2C, 0x02, 0x20, // 8009: BIT $2002
+    0x10, 0xFB,       // 800C: BPL $8009
+    0xA9, 0x3F,       // 800E: LDA #$3F
+    0x8D, 0x06, 0x20, // 8010: STA $2006
+    0xA9, 0x11,       // 8013: LDA #$11
+    0x8D, 0x06, 0x20, // 8015: STA $2006
+    0xA9, 0x30,       // 8018: LDA #$30
+    0x8D, 0x07, 0x20, // 801A: STA $2007
+    0xA9, 0x00,       // 801D: LDA #$00
+    0x8D, 0x03, 0x20, // 801F: STA $2003
+    0xAA,             // 8022: TAX
+    0xBD, 0x00, 0x90, // 8023: LDA $9000,X
+    0x8D, 0x04, 0x20, // 8026: STA $2004
+    0xE8,             // 8029: INX
+    0xD0, 0xF7,       // 802A: BNE $8023
+    0xA9, 0x14,       // 802C: LDA #$14
+    0x8D, 0x01, 0x20, // 802E: STA $2001
+    0xAD, 0x02, 0x20, // 8031: LDA $2002
+    0x10, 0xFB,       // 8034: BPL $8031
+    0x8D, 0x00, 0x02, // 8036: STA $0200
+    0x4C, 0x31, 0x80, // 8039: JMP $8031
+];
+
+const SPRITE_Y: u8 = 100;
+const SPRITE_COUNT: u8 = 9;
+const SPRITE_SPACING: u8 = 20;
+
+#[test]
+fn all_sprites_are_shown_when_limit_is_removed() {
+    let nes = run("unlimited", true);
+    let first_sprite_pixel = sprite_row_pixel(&nes, 0);
+    let ninth_sprite_pixel = sprite_row_pixel(&nes, 8);
+    assert_eq!(ninth_sprite_pixel, first_sprite_pixel);
+}
+
+#[test]
+fn ninth_sprite_is_dropped_by_default() {
+    let nes = run("limited", false);
+    let first_sprite_pixel = sprite_row_pixel(&nes, 0);
+    let ninth_sprite_pixel = sprite_row_pixel(&nes, 8);
+    assert_ne!(ninth_sprite_pixel, first_sprite_pixel);
+}
+
+#[test]
+fn limit_is_kept_for_latched_chr_banks() {
+    // MMC4 switches CHR banks partway through the sprite pattern fetches, so it doesn't support the option.
+    let nes = run_with_mapper("mmc4", 10, true);
+    let first_sprite_pixel = sprite_row_pixel(&nes, 0);
+    let ninth_sprite_pixel = sprite_row_pixel(&nes, 8);
+    assert_ne!(ninth_sprite_pixel, first_sprite_pixel);
+}
+
+#[test]
+fn removing_limit_is_invisible_to_the_cpu() {
+    let limited = run("cpu_limited", false);
+    let unlimited = run("cpu_unlimited", true);
+    // The sprite overflow flag must still be set.
+    assert_eq!(peek(&unlimited, 0x0200) & 0b0010_0000, 0b0010_0000);
+    assert_eq!(internal_ram(&unlimited), internal_ram(&limited));
+    assert_eq!(unlimited.bus().cpu_cycle(), limited.bus().cpu_cycle());
+}
+
+fn run(name: &str, show_all_sprites: bool) -> Nes {
+    run_with_mapper(name, 0, show_all_sprites)
+}
+
+fn run_with_mapper(name: &str, mapper_number: u8, show_all_sprites: bool) -> Nes {
+    let directory = std::env::temp_dir().join(format!("reznez_sprite_limit_test_{name}_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let rom_path = write_rom(&directory, mapper_number);
+
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        ..Opt::new(Some(rom_path))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+    *nes.frame_mut().show_all_sprites_mut() = show_all_sprites;
+    for _ in 0..6 {
+        nes.step_frame();
+    }
+
+    nes
+}
+
+// Writes an image with a row of nine sprites using a solid tile. All other sprites are off screen.
+// Only mappers that power on with the first PRG and CHR banks everywhere can be used.
+fn write_rom(directory: &std::path::Path, mapper_number: u8) -> PathBuf {
+    let mut rom = vec![b'N', b'E', b'S', 0x1A, 1, 1, mapper_number << 4, mapper_number & 0xF0];
+    rom.resize(16, 0);
+    let mut prg_rom = vec![0xEA; 16 * 1024];
+    prg_rom[..PROGRAM.len()].copy_from_slice(PROGRAM);
+    let mut oam = [0xFF; 256];
+    for i in 0..SPRITE_COUNT {
+        let start = 4 * usize::from(i);
+        oam[start..start + 4].copy_from_slice(&[SPRITE_Y, 1, 0, i * SPRITE_SPACING]);
+    }
+
+    prg_rom[0x1000..0x1100].copy_from_slice(&oam);
+    prg_rom[0x3FFC..0x3FFE].copy_from_slice(&[0x00, 0x80]);
+    rom.extend_from_slice(&prg_rom);
+
+    let mut chr_rom = vec![0; 8 * 1024];
+    // Tile 1: every pixel uses color 1.
+    chr_rom[0x10..0x18].fill(0xFF);
+    rom.extend_from_slice(&chr_rom);
+
+    let path = directory.join("sprite_limit.nes");
+    fs::write(&path, rom).unwrap();
+    path
+}
+
+// The middle of the specified sprite in the row of sprites.
+fn sprite_row_pixel(nes: &Nes, sprite_index: u8) -> (u8, u8, u8) {
+    let column = PixelColumn::new(sprite_index * SPRITE_SPACING + 4);
+    let row = PixelRow::try_from_u8(SPRITE_Y + 4).unwrap();
+    let (rgb, _) = nes.frame().pixel(column, row);
+    (rgb.red(), rgb.green(), rgb.blue())
+}
+
+fn internal_ram(nes: &Nes) -> Vec<u8> {
+    (0..0x800).map(|addr| peek(nes, addr)).collect()
+}
+
+fn peek(nes: &Nes, addr: u16) -> u8 {
+    nes.bus().cpu_peek(nes.mapper(), AddressBusType::Cpu, CpuAddress::new(addr))
+}
@@ -0,0 +1,82 @@
+extern crate reznez;
+
+mod common;
+
+use common::{TestEmulator, TestRom};
+
+#[test]
+fn tone_channel_is_audible() {
+    let mut emulator = load(&tone_a_writes(0x0F));
+    emulator.run();
+    assert!(emulator.amplitude() > 0.1);
+}
+
+#[test]
+fn volume_is_logarithmic() {
+    let mut emulator = load(&tone_a_writes(0x0F));
+    emulator.run();
+    let full_volume_amplitude = emulator.amplitude();
+
+    // Two volume steps down is 6dB quieter, which halves the amplitude.
+    let mut emulator = load(&tone_a_writes(0x0D));
+    emulator.run();
+    let ratio = emulator.amplitude() / full_volume_amplitude;
+    assert!(ratio > 0.4 && ratio < 0.6, "Ratio: {ratio}");
+}
+
+#[test]
+fn mixer_disables_tone() {
+    let mut writes = tone_a_writes(0x0F);
+    // Disable every tone and noise generator, leaving channel A at a constant level.
+    writes.push((0x7, 0b0011_1111));
+    let mut emulator = load(&writes);
+    emulator.run();
+    assert!(emulator.amplitude() < 0.001);
+}
+
+#[test]
+fn envelope_controls_volume() {
+    let mut writes = tone_a_writes(0x10);
+    // Disable the tone, so only the envelope (period $0010, repeating sawtooth) varies the output.
+    writes.extend_from_slice(&[(0x7, 0b0011_1111), (0xB, 0x10), (0xC, 0x00), (0xD, 0b1000)]);
+    let mut emulator = load(&writes);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.1);
+}
+
+#[test]
+fn register_select_with_high_bits_blocks_writes() {
+    let mut emulator = load(&tone_a_writes(0x0F));
+    emulator.run();
+    let unblocked_amplitude = emulator.amplitude();
+
+    let mut emulator = load_with_select_mask(&tone_a_writes(0x0F), 0xF0);
+    emulator.run();
+    assert!(unblocked_amplitude > 0.1);
+    assert!(emulator.amplitude() < 0.001);
+}
+
+// Channel A: period $100, tone enabled, noise disabled, at the specified volume register value.
+fn tone_a_writes(volume: u8) -> Vec<(u8, u8)> {
+    vec![(0x0, 0x00), (0x1, 0x01), (0x7, 0b0011_1110), (0x8, volume)]
+}
+
+fn load(writes: &[(u8, u8)]) -> TestEmulator {
+    load_with_select_mask(writes, 0x00)
+}
+
+// Loads a mapper 69 ROM whose program writes each (register, value) pair to the audio registers,
+// with the select mask ORed into each register number.
+fn load_with_select_mask(writes: &[(u8, u8)], select_mask: u8) -> TestEmulator {
+    let mut program = Vec::new();
+    for &(register, value) in writes {
+        program.extend_from_slice(&[
+            0xA9, register | select_mask, // LDA #register
+            0x8D, 0x00, 0xC0,             // STA $C000
+            0xA9, value,                  // LDA #value
+            0x8D, 0x00, 0xE0,             // STA $E000
+        ]);
+    }
+
+    TestRom::ines(69, 32).with_program(&program).load()
+}
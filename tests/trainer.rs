@@ -0,0 +1,73 @@
+extern crate reznez;
+
+use std::fs;
+use std::path::PathBuf;
+
+use reznez::bus::AddressBusType;
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::memory::cpu::cpu_address::CpuAddress;
+use reznez::nes::Nes;
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+
+// Copies the last trainer byte to $0200 to show that PRG ROM starts after the trainer.
+const PROGRAM: &[u8] = &[
+    0xAD, 0xFF, 0x71, // C000: LDA $71FF
+    0x8D, 0x00, 0x02, // C003: STA $0200
+    0x4C, 0x06, 0xC0, // C006: JMP $C006
+];
+
+#[test]
+fn trainer_is_loaded_at_7000() {
+    let trainer: Vec<u8> = (0..512).map(|i| (i % 251) as u8 + 1).collect();
+    let mut nes = load_nes(&trainer);
+    nes.step_frame();
+
+    for (i, &value) in trainer.iter().enumerate() {
+        assert_eq!(peek(&nes, 0x7000 + i as u16), value);
+    }
+
+    assert_eq!(peek(&nes, 0x0200), trainer[511]);
+    assert_eq!(nes.resolved_metadata().trainer_hash, Some(crc32fast::hash(&trainer)));
+}
+
+fn load_nes(trainer: &[u8]) -> Nes {
+    let directory = std::env::temp_dir().join(format!("reznez_trainer_test_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let rom_path = write_rom(&directory, trainer);
+
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        ..Opt::new(Some(rom_path))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+    nes
+}
+
+// Writes an NES 2.0 MMC1 ROM with 16KiB PRG ROM, 8KiB PRG RAM, 8KiB CHR ROM, and a trainer.
+fn write_rom(directory: &std::path::Path, trainer: &[u8]) -> PathBuf {
+    let mut rom = vec![b'N', b'E', b'S', 0x1A, 1, 1, 0b0001_0100, 0b0000_1000, 0, 0, 0x07];
+    rom.resize(16, 0);
+    rom.extend_from_slice(trainer);
+
+    let mut prg_rom = vec![0xEA; 16 * 1024];
+    prg_rom[..PROGRAM.len()].copy_from_slice(PROGRAM);
+    // NMI, RESET, and IRQ vectors.
+    prg_rom[0x3FFA..].copy_from_slice(&[0x06, 0xC0, 0x00, 0xC0, 0x06, 0xC0]);
+    rom.extend_from_slice(&prg_rom);
+    rom.extend_from_slice(&[0; 8 * 1024]);
+
+    let path = directory.join("trainer.nes");
+    fs::write(&path, rom).unwrap();
+    path
+}
+
+fn peek(nes: &Nes, addr: u16) -> u8 {
+    nes.bus().cpu_peek(nes.mapper(), AddressBusType::Cpu, CpuAddress::new(addr))
+}
@@ -0,0 +1,77 @@
+extern crate reznez;
+
+use std::fs;
+use std::path::PathBuf;
+
+use reznez::bus::AddressBusType;
+use reznez::cartridge::header_db::HeaderDb;
+use reznez::config::{Config, GuiType, Opt};
+use reznez::memory::cpu::cpu_address::CpuAddress;
+use reznez::nes::Nes;
+use reznez::ppu::name_table::name_table_mirroring::NameTableMirroring;
+use reznez::ppu::render::frame_rate::TargetFrameRate;
+
+// Stores the first byte of the second PRG chunk to $0200 to show that the chunks were concatenated in order.
+const PROGRAM: &[u8] = &[
+    0xAD, 0x00, 0xC0, // 8000: LDA $C000
+    0x8D, 0x00, 0x02, // 8003: STA $0200
+    0x4C, 0x06, 0x80, // 8006: JMP $8006
+];
+
+#[test]
+fn unif_nrom_runs() {
+    let mut nes = load_nes();
+    assert_eq!(nes.resolved_metadata().mapper_number, 0);
+    assert_eq!(nes.resolved_metadata().prg_rom_size, 32 * 1024);
+    assert_eq!(nes.resolved_metadata().chr_rom_size, 8 * 1024);
+    assert_eq!(nes.bus().name_table_mirroring(), NameTableMirroring::VERTICAL);
+
+    nes.step_frame();
+    assert_eq!(nes.bus().cpu_peek(nes.mapper(), AddressBusType::Cpu, CpuAddress::new(0x0200)), 0x42);
+}
+
+fn load_nes() -> Nes {
+    let directory = std::env::temp_dir().join(format!("reznez_unif_test_{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let rom_path = write_unif(&directory);
+
+    let opt = Opt {
+        gui: GuiType::NoGui,
+        target_frame_rate: TargetFrameRate::Unbounded,
+        disable_audio: true,
+        prevent_saving: true,
+        ..Opt::new(Some(rom_path))
+    };
+    let config = Config::new(&opt);
+    let cartridge = Nes::load_cartridge(&opt.rom_path.unwrap()).unwrap();
+    let mut nes = Nes::new(&HeaderDb::load(), &config, &cartridge).unwrap();
+    nes.mute();
+    nes
+}
+
+// Writes an NROM-256 UNIF image whose PRG ROM is split across two chunks, stored out of order.
+fn write_unif(directory: &std::path::Path) -> PathBuf {
+    let mut prg0 = vec![0xEA; 16 * 1024];
+    prg0[..PROGRAM.len()].copy_from_slice(PROGRAM);
+    let mut prg1 = vec![0xEA; 16 * 1024];
+    prg1[0] = 0x42;
+    prg1[0x3FFC..0x3FFE].copy_from_slice(&[0x00, 0x80]);
+
+    let mut unif = b"UNIF".to_vec();
+    unif.extend_from_slice(&7u32.to_le_bytes());
+    unif.resize(32, 0);
+    let mut chunk = |id: &[u8], data: &[u8]| {
+        unif.extend_from_slice(id);
+        unif.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        unif.extend_from_slice(data);
+    };
+    chunk(b"MAPR", b"NES-NROM-256\0");
+    chunk(b"PRG1", &prg1);
+    chunk(b"PRG0", &prg0);
+    chunk(b"CHR0", &[0; 8 * 1024]);
+    chunk(b"MIRR", &[1]);
+
+    let path = directory.join("test.unf");
+    fs::write(&path, unif).unwrap();
+    path
+}
@@ -0,0 +1,64 @@
+extern crate reznez;
+
+mod common;
+
+use common::{TestEmulator, TestRom};
+
+#[test]
+fn vrc6a_banks_prg_and_plays_pulse() {
+    let mut emulator = load(24, 0x9001, 0x9002);
+    emulator.run();
+    assert_eq!(emulator.peek(0x0200), 3);
+    assert_eq!(emulator.peek(0x0201), 0x50);
+    assert!(emulator.amplitude() > 0.08);
+}
+
+#[test]
+fn vrc6b_banks_prg_and_plays_pulse() {
+    // VRC6b swaps A0 and A1, so the period registers are at swapped addresses.
+    let mut emulator = load(26, 0x9002, 0x9001);
+    emulator.run();
+    assert_eq!(emulator.peek(0x0200), 3);
+    assert_eq!(emulator.peek(0x0201), 0x50);
+    assert!(emulator.amplitude() > 0.08);
+}
+
+#[test]
+fn vrc6_audio_is_silenced_by_mute() {
+    let mut emulator = load(24, 0x9001, 0x9002);
+    emulator.nes_mut().mute();
+    emulator.run();
+    assert!(emulator.amplitude() < 0.001);
+}
+
+// Loads a ROM with 128KiB PRG ROM. The first byte of each 16KiB PRG bank is its bank number, except
+// the first byte of 8KiB bank 5, which is 0x50.
+fn load(mapper_number: u8, period_low_addr: u16, period_high_addr: u16) -> TestEmulator {
+    let [period_low_lo, period_low_hi] = period_low_addr.to_le_bytes();
+    let [period_high_lo, period_high_hi] = period_high_addr.to_le_bytes();
+    let program = [
+        0xA9, 0x03,       // LDA #$03
+        0x8D, 0x00, 0x80, // STA $8000
+        0xA9, 0x05,       // LDA #$05
+        0x8D, 0x00, 0xC0, // STA $C000
+        0xAD, 0x00, 0x80, // LDA $8000
+        0x8D, 0x00, 0x02, // STA $0200
+        0xAD, 0x00, 0xC0, // LDA $C000
+        0x8D, 0x01, 0x02, // STA $0201
+        // Pulse 1: 50% duty, full volume, period $100.
+        0xA9, 0x7F,       // LDA #$7F
+        0x8D, 0x00, 0x90, // STA $9000
+        0xA9, 0x00,       // LDA #$00
+        0x8D, period_low_lo, period_low_hi,   // STA period low
+        0xA9, 0x81,       // LDA #$81
+        0x8D, period_high_lo, period_high_hi, // STA period high and enable
+    ];
+
+    let mut rom = TestRom::ines(mapper_number, 128).with_program(&program);
+    for bank in 0..8 {
+        rom.prg_rom[bank * 0x4000] = bank as u8;
+    }
+
+    rom.prg_rom[5 * 0x2000] = 0x50;
+    rom.load()
+}
@@ -0,0 +1,42 @@
+extern crate reznez;
+
+mod common;
+
+use common::{TestEmulator, TestRom};
+
+#[test]
+fn built_in_instrument_is_audible() {
+    let mut emulator = load(0x00);
+    emulator.run();
+    assert!(emulator.amplitude() > 0.02);
+}
+
+#[test]
+fn silence_bit_mutes_the_synth() {
+    let mut emulator = load(0x40);
+    emulator.run();
+    assert!(emulator.amplitude() < 0.001);
+}
+
+// Loads a VRC7a ROM that plays middle C with the built-in flute on channel 0.
+// The control value is written to $E000 afterwards.
+fn load(control_value: u8) -> TestEmulator {
+    let program = [
+        0xA9, 0x30,       // LDA #$30
+        0x8D, 0x10, 0x90, // STA $9010
+        0xA9, 0x40,       // LDA #$40 (Flute, full volume)
+        0x8D, 0x30, 0x90, // STA $9030
+        0xA9, 0x10,       // LDA #$10
+        0x8D, 0x10, 0x90, // STA $9010
+        0xA9, 0xAC,       // LDA #$AC (F-number low)
+        0x8D, 0x30, 0x90, // STA $9030
+        0xA9, 0x20,       // LDA #$20
+        0x8D, 0x10, 0x90, // STA $9010
+        0xA9, 0x18,       // LDA #$18 (Key on, octave 4)
+        0x8D, 0x30, 0x90, // STA $9030
+        0xA9, control_value, // LDA #control_value
+        0x8D, 0x00, 0xE0, // STA $E000
+    ];
+
+    TestRom::nes2(85, 2, 16).with_program(&program).load()
+}